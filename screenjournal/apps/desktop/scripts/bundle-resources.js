@@ -15,6 +15,7 @@
  * BUNDLED RESOURCES:
  *   - ActivityWatch: aw-server, aw-watcher-window, etc.
  *   - FFmpeg: Video encoding for screen recording
+ *   - Tesseract: OCR binary and tessdata for the recording text index
  *   - Binaries: Go executables (sj-collector, sj-tracker-report)
  *   - Python: Python virtual environment and chat agent source
  *   - Databases: MongoDB and InfluxDB binaries
@@ -83,7 +84,7 @@ function main() {
   
   console.log(`🎯 Bundle target: ${bundlePath}\n`);
   
-  // Bundle each resource from resource-manager (activitywatch, ffmpeg)
+  // Bundle each resource from resource-manager (activitywatch, ffmpeg, tesseract)
   const resourceNames = getAllResourceNames();
   const results = {};
   
@@ -102,6 +103,8 @@ function main() {
         console.error(`     Run: npm run setup-aw\n`);
       } else if (resourceName === 'ffmpeg') {
         console.error(`     Run: npm run setup-ffmpeg\n`);
+      } else if (resourceName === 'tesseract') {
+        console.error(`     Run: npm run setup-tesseract\n`);
       }
     }
   }
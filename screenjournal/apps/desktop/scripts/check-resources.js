@@ -12,12 +12,14 @@
  *   node scripts/check-resources.js                  # Check all resources for current platform
  *   node scripts/check-resources.js activitywatch    # Check only ActivityWatch
  *   node scripts/check-resources.js ffmpeg           # Check only FFmpeg
+ *   node scripts/check-resources.js tesseract        # Check only Tesseract
  *   node scripts/check-resources.js --verbose        # Show all platforms status
  *   node scripts/check-resources.js --all            # Check all platforms (CI mode)
  * 
  * ENVIRONMENT VARIABLES:
- *   SKIP_AW_CHECK=true      Skip ActivityWatch check
- *   SKIP_FFMPEG_CHECK=true  Skip FFmpeg check
+ *   SKIP_AW_CHECK=true         Skip ActivityWatch check
+ *   SKIP_FFMPEG_CHECK=true     Skip FFmpeg check
+ *   SKIP_TESSERACT_CHECK=true  Skip Tesseract check
  * 
  */
 
@@ -154,6 +156,13 @@ function main() {
           console.log(`   ${status} ${platform}`);
         }
       }
+      
+      // Data files are shared, so report them even without --verbose
+      for (const [dataPath, exists] of Object.entries(result.dataFiles || {})) {
+        if (!exists) {
+          console.log(`   ✗ ${dataPath}`);
+        }
+      }
     }
   }
   
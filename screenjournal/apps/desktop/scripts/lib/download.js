@@ -140,11 +140,28 @@ function extractTarXz(tarPath, extractTo) {
   }
 }
 
+/**
+ * Extract a 7z archive or installer (e.g. NSIS .exe) with 7-Zip
+ * @param {string} archivePath - Path to archive
+ * @param {string} extractTo - Destination directory
+ */
+function extract7z(archivePath, extractTo) {
+  console.log(`  📦 Extracting with 7-Zip...`);
+  ensureDir(extractTo);
+  
+  try {
+    execSync(`7z x -y -o"${extractTo}" "${archivePath}"`, { stdio: 'pipe' });
+    console.log(`  ✅ Extracted successfully`);
+  } catch (error) {
+    throw new Error(`Failed to extract with 7-Zip (is 7z installed?): ${error.message}`);
+  }
+}
+
 /**
  * Extract an archive (auto-detect type or use explicit type)
  * @param {string} archivePath - Path to archive
  * @param {string} extractTo - Destination directory
- * @param {string} type - Archive type: 'zip', 'tar.xz', or 'auto'
+ * @param {string} type - Archive type: 'zip', 'tar.xz', 'tar.gz', '7z', or 'auto'
  */
 function extractArchive(archivePath, extractTo, type = 'auto') {
   let archiveType = type;
@@ -168,6 +185,9 @@ function extractArchive(archivePath, extractTo, type = 'auto') {
     case 'tar.xz':
       extractTarXz(archivePath, extractTo);
       break;
+    case '7z':
+      extract7z(archivePath, extractTo);
+      break;
     case 'tar.gz':
       console.log(`  📦 Extracting tar.gz archive...`);
       ensureDir(extractTo);
@@ -187,5 +207,6 @@ module.exports = {
   downloadFile,
   extractZip,
   extractTarXz,
+  extract7z,
   extractArchive,
 };
@@ -87,11 +87,51 @@ async function setupResource(resourceName, options = {}) {
     results[platform] = await setupResourcePlatform(resourceName, platform, { force, verbose });
   }
   
+  // Shared data files (e.g. Tesseract language data)
+  const dataFiles = await setupDataFiles(resourceName, { force, verbose });
+  
   // Cleanup temp directory
   removeDir(PATHS.tempDir);
   
-  const allSuccess = Object.values(results).every(r => r.success);
-  return { success: allSuccess, platforms: results };
+  const allSuccess = Object.values(results).every(r => r.success) && dataFiles.success;
+  return { success: allSuccess, platforms: results, dataFiles };
+}
+
+/**
+ * Download the data files shared by all platforms of a resource
+ * @private
+ */
+async function setupDataFiles(resourceName, options = {}) {
+  const { force = false, verbose = true } = options;
+  
+  const resource = getResource(resourceName);
+  
+  for (const dataFile of resource.dataFiles || []) {
+    const targetPath = path.join(PATHS.resourcesDir, resource.resourceDir, dataFile.path);
+    
+    if (verbose) {
+      console.log(`\n  📍 ${dataFile.path}:`);
+    }
+    
+    if (!force && fileExists(targetPath)) {
+      if (verbose) {
+        console.log(`    ✅ Already installed`);
+      }
+      continue;
+    }
+    
+    try {
+      ensureDir(path.dirname(targetPath));
+      await downloadFile(dataFile.url, targetPath, { showProgress: verbose });
+    } catch (error) {
+      if (verbose) {
+        console.error(`    ❌ Failed: ${error.message}`);
+      }
+      return { success: false, error: error.message };
+    }
+  }
+  
+  return { success: true };
 }
 
 /**
@@ -129,15 +169,23 @@ async function setupResourcePlatform(resourceName, platform, options = {}) {
     
     // Download
     const url = getDownloadUrl(resourceName, platform);
-    const archiveExt = platformConfig.archiveType === 'tar.xz' ? '.tar.xz' : '.zip';
+    if (!url) {
+      throw new Error(platformConfig.missingUrlHint || 'No download URL configured');
+    }
+    const archiveExt = { 'tar.xz': '.tar.xz', '7z': '.7z', binary: '' }[platformConfig.archiveType] ?? '.zip';
     const archivePath = path.join(PATHS.tempDir, `${resourceName}-${platform}${archiveExt}`);
     
     await downloadFile(url, archivePath, { showProgress: verbose });
     
-    // Extract
+    // Extract (a plain binary download is used as is)
     const extractDir = path.join(PATHS.tempDir, `extract-${resourceName}-${platform}`);
     removeDir(extractDir);
-    extractArchive(archivePath, extractDir, platformConfig.archiveType);
+    if (platformConfig.archiveType === 'binary') {
+      ensureDir(extractDir);
+      copyFile(archivePath, path.join(extractDir, platformConfig.binaryName));
+    } else {
+      extractArchive(archivePath, extractDir, platformConfig.archiveType);
+    }
     
     // Ensure target directory exists
     ensureDir(targetDir);
@@ -278,8 +326,14 @@ function checkResource(resourceName, options = {}) {
     results[platform] = checkResourcePlatform(resourceName, platform, { verify, verbose });
   }
   
-  const allSuccess = Object.values(results).every(r => r.exists);
-  return { success: allSuccess, platforms: results };
+  // Check shared data files
+  const dataFiles = {};
+  for (const dataFile of resource.dataFiles || []) {
+    dataFiles[dataFile.path] = fileExists(path.join(PATHS.resourcesDir, resource.resourceDir, dataFile.path));
+  }
+  
+  const allSuccess = Object.values(results).every(r => r.exists) && Object.values(dataFiles).every(Boolean);
+  return { success: allSuccess, platforms: results, dataFiles };
 }
 
 /**
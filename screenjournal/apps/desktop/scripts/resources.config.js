@@ -10,11 +10,12 @@
  * 1. Add a new entry to the `resources` object below
  * 2. Define platforms with URLs, archive types, and binary paths
  * 3. Optionally specify a verification command
+ * 4. Optionally list shared data files (downloaded once, not per platform)
  */
 
 const path = require('path');
 const fs = require('fs');
-const { copyDirRecursive } = require('./lib/utils');
+const { copyDirRecursive, findFiles } = require('./lib/utils');
 
 // =============================================================================
 // Resource Definitions
@@ -153,6 +154,86 @@ const resources = {
     // Verification command (run ffmpeg -version)
     verifyCommand: (binaryPath) => `"${binaryPath}" -version`,
   },
+  
+  /**
+   * Tesseract - Local OCR for the recording text index
+   * https://github.com/tesseract-ocr/tesseract
+   * 
+   * There is no upstream self-contained macOS build; point TESSERACT_MACOS_URL
+   * at a zip holding tesseract and its dylibs (linked with @loader_path).
+   */
+  tesseract: {
+    name: 'Tesseract',
+    version: '5.5.0',
+    resourceDir: 'tesseract',
+    skipEnvVar: 'SKIP_TESSERACT_CHECK',
+    
+    platforms: {
+      'darwin-aarch64': {
+        url: () => process.env.TESSERACT_MACOS_URL || null,
+        missingUrlHint: 'Set TESSERACT_MACOS_URL to a zip with tesseract and its dylibs',
+        archiveType: 'zip',
+        binaryName: 'tesseract',
+      },
+      'darwin-x86_64': {
+        url: () => process.env.TESSERACT_MACOS_URL || null,
+        missingUrlHint: 'Set TESSERACT_MACOS_URL to a zip with tesseract and its dylibs',
+        archiveType: 'zip',
+        binaryName: 'tesseract',
+      },
+      'windows-x86_64': {
+        // NSIS installer from UB Mannheim, unpacked with 7-Zip
+        url: 'https://github.com/UB-Mannheim/tesseract/releases/download/v5.4.0.20240606/tesseract-ocr-w64-setup-5.4.0.20240606.exe',
+        archiveType: '7z',
+        binaryName: 'tesseract.exe',
+      },
+      'linux-x86_64': {
+        // Self-contained AppImage, used as the binary itself
+        url: (version) => `https://github.com/AlexanderP/tesseract-appimage/releases/download/v${version}/tesseract-${version}-x86_64.AppImage`,
+        archiveType: 'binary',
+        binaryName: 'tesseract',
+      },
+    },
+    
+    // Language data shared by all platforms (relative to resources/tesseract)
+    dataFiles: [
+      {
+        path: 'tessdata/eng.traineddata',
+        url: 'https://github.com/tesseract-ocr/tessdata_fast/raw/main/eng.traineddata',
+      },
+    ],
+    
+    // Primary binary for verification
+    primaryBinary: (platform) => {
+      const isWindows = platform.startsWith('windows');
+      return isWindows ? 'tesseract.exe' : 'tesseract';
+    },
+    
+    // Copy the binary together with the shared libraries next to it
+    extractBinaries: (extractDir, targetDir, platform) => {
+      const isWindows = platform.startsWith('windows');
+      const binaryName = isWindows ? 'tesseract.exe' : 'tesseract';
+      const binaryPattern = isWindows ? /[\/\\]tesseract\.exe$/ : /[\/\\]tesseract$/;
+      
+      const binary = findFiles(extractDir, binaryPattern)[0];
+      if (!binary) {
+        throw new Error('Could not find tesseract in extracted archive');
+      }
+      
+      const binaryDir = path.dirname(binary);
+      const libraries = fs.readdirSync(binaryDir).filter(f => /\.(dll|dylib)$|\.so(\.\d+)*$/.test(f));
+      
+      fs.copyFileSync(binary, path.join(targetDir, binaryName));
+      for (const library of libraries) {
+        fs.copyFileSync(path.join(binaryDir, library), path.join(targetDir, library));
+      }
+      
+      return [binaryName, ...libraries];
+    },
+    
+    // Verification command (run tesseract --version)
+    verifyCommand: (binaryPath) => `"${binaryPath}" --version`,
+  },
 };
 
 // =============================================================================
@@ -10,6 +10,7 @@
  * USAGE:
  *   node scripts/setup-resource.js activitywatch     # Setup AW for current platform
  *   node scripts/setup-resource.js ffmpeg            # Setup FFmpeg for current platform
+ *   node scripts/setup-resource.js tesseract         # Setup Tesseract (+ tessdata) for current platform
  *   node scripts/setup-resource.js activitywatch --all   # Setup AW for all platforms
  *   node scripts/setup-resource.js ffmpeg --all      # Setup FFmpeg for all platforms
  *   node scripts/setup-resource.js --all             # Setup ALL resources for all platforms
@@ -66,6 +67,8 @@ Usage: node scripts/setup-resource.js <resource> [options]
 Resources:
   activitywatch   ActivityWatch time tracking binaries
   ffmpeg          FFmpeg video encoding binary
+  tesseract       Tesseract OCR binary and language data
+                  (Windows needs 7-Zip; macOS needs TESSERACT_MACOS_URL)
 
 Options:
   --all           Download for all platforms (default: current platform only)
@@ -75,6 +78,7 @@ Examples:
   npm run setup-aw                    # Setup ActivityWatch for current platform
   npm run setup-ffmpeg                # Setup FFmpeg for current platform
   npm run setup-ffmpeg -- --all       # Setup FFmpeg for all platforms
+  npm run setup-tesseract             # Setup Tesseract for current platform
   npm run setup -- --all              # Setup all resources for all platforms
 `);
     process.exit(0);
@@ -136,6 +140,10 @@ Examples:
         console.log(`      ${pStatus} ${platform}${extra}`);
       }
     }
+    
+    if (result.dataFiles && !result.dataFiles.success) {
+      console.log(`      ✗ data files`);
+    }
   }
   
   console.log('═'.repeat(60));
@@ -97,7 +97,7 @@ function signAppBundle() {
   // This ensures all binaries are properly signed with hardened runtime and timestamps
   // Required for notarization - all nested binaries must be signed individually
   const resourcesPath = path.join(appBundlePath, 'Contents', 'Resources');
-  const additionalResources = ['binaries', 'python', 'databases', 'activitywatch', 'ffmpeg'];
+  const additionalResources = ['binaries', 'python', 'databases', 'activitywatch', 'ffmpeg', 'tesseract'];
   
   console.log('🔏 Signing all resource directories...');
   for (const resourceDir of additionalResources) {
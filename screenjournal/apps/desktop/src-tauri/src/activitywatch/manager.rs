@@ -15,6 +15,11 @@ static AW_WATCHERS: Lazy<Mutex<HashMap<String, Child>>> = Lazy::new(|| Mutex::ne
 pub static AW_BASE_URL: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
 static AW_PORT: Lazy<Mutex<Option<u16>>> = Lazy::new(|| Mutex::new(None));
 
+// Base URL of the running aw-server, if any (for non-command callers such as recording)
+pub fn current_base_url() -> Option<String> {
+    AW_BASE_URL.lock().unwrap().clone()
+}
+
 fn app_data_dir(app: &AppHandle) -> PathBuf {
     app.path()
         .app_data_dir()
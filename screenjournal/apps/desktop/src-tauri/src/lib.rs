@@ -33,14 +33,39 @@ pub fn run() {
             crate::recording::manager::update_recording_config,
             crate::recording::manager::get_display_count,
             crate::recording::manager::get_recordings_by_date_range,
+            crate::recording::manager::get_timelapses,
+            crate::recording::manager::generate_timelapse,
+            crate::recording::manager::search_recorded_text,
+            crate::recording::manager::import_recording,
+            crate::recording::manager::get_input_activity,
+            crate::recording::manager::export_input_overlay,
+            crate::recording::manager::get_recording_coverage,
+            crate::recording::manager::get_recordings_dir,
+            crate::recording::manager::get_recordings_migration_status,
+            crate::recording::manager::seek_recording,
+            crate::recording::manager::verify_recording_integrity,
             // Gemini AI integration commands
             crate::recording::manager::has_gemini_api_key,
             crate::recording::manager::get_gemini_config,
             crate::recording::manager::update_gemini_config,
+            crate::recording::manager::list_prompt_templates,
+            crate::recording::manager::save_prompt_template,
             crate::recording::manager::get_gemini_queue_status,
+            crate::recording::manager::list_gemini_jobs,
+            crate::recording::manager::cancel_gemini_job,
+            crate::recording::manager::retry_failed_gemini_jobs,
+            crate::recording::manager::prioritize_gemini_jobs,
+            crate::recording::manager::get_segment_timeline,
+            crate::recording::manager::get_timelines_by_date_range,
+            crate::recording::manager::resend_timelines,
+            crate::recording::manager::reanalyze_segments,
+            crate::recording::manager::set_segment_high_risk,
             crate::recording::manager::set_gemini_api_key,
             crate::recording::manager::get_gemini_api_key_status,
             crate::recording::manager::delete_gemini_api_key,
+            crate::recording::manager::set_openai_api_key,
+            crate::recording::manager::get_openai_api_key_status,
+            crate::recording::manager::delete_openai_api_key,
             // Collector commands
             crate::collector::manager::start_collector,
             crate::collector::manager::stop_collector,
@@ -76,17 +101,31 @@ pub fn run() {
             // Initialize recording system
             // 1. Initialize FFmpeg path (must be done before any recording operations)
             crate::recording::capture::init_ffmpeg_path(&app.handle());
+            crate::recording::ocr::init_tesseract_path(&app.handle());
             
             // 2. Load recording config on startup
             let recording_config = crate::recording::config::load_config(&app.handle())
                 .unwrap_or_default();
             crate::recording::manager::init_config(recording_config);
+            crate::recording::migration::resume(&app.handle());
             
             // 3. Initialize Gemini queue
             let gemini_config = crate::recording::config::load_gemini_config(&app.handle())
                 .unwrap_or_default();
             crate::recording::gemini::init_queue(&app.handle(), gemini_config);
             
+            // 4. Start daily timelapse worker (no-op passes while disabled in config)
+            crate::recording::timelapse::start_worker(&app.handle());
+            
+            // 5. Coverage: mark the launch (explains gaps after crashes) and start reporting
+            crate::recording::coverage::record_event(
+                &app.handle(),
+                crate::recording::types::CoverageIncidentKind::AppStarted,
+                None,
+                None,
+            );
+            crate::recording::coverage::start_worker(&app.handle());
+            
             // Start all backend services on app launch
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
@@ -137,7 +137,7 @@ pub fn init_ffmpeg_path(app: &AppHandle) {
 }
 
 // Get the stored FFmpeg binary path
-fn get_ffmpeg_path() -> Result<PathBuf, String> {
+pub fn get_ffmpeg_path() -> Result<PathBuf, String> {
     let path_guard = FFMPEG_PATH.lock().unwrap();
     path_guard.clone().ok_or_else(|| {
         "FFmpeg path not initialized. Call init_ffmpeg_path() on app startup.".to_string()
@@ -249,6 +249,12 @@ pub struct CaptureResult {
     pub height: u32,
     pub frame_count: u64,
     pub file_size: u64,
+    
+    // Wall-clock time the first frame was captured
+    pub first_frame_at: chrono::DateTime<chrono::Utc>,
+    
+    // Capture time of each encoded frame, in ms since the first frame
+    pub frame_offsets_ms: Vec<u32>,
 }
 
 // =============================================================================
@@ -432,7 +438,12 @@ pub fn capture_display_to_file(
     
     // Capture loop - primary exit via shutdown signal, safety_timeout is fallback
     let start_time = Instant::now();
+    let first_frame_at = chrono::Utc::now();
     let mut frame_count: u64 = 1; // Already wrote first frame
+    
+    // Encoded frames are played back at a constant fps, but captured whenever scap
+    // delivers them - keep real capture times so seeking can map wall-clock exactly
+    let mut frame_offsets_ms: Vec<u32> = vec![0];
     let expected_frame_size = (width * height * 4) as usize; // BGRA = 4 bytes per pixel
     
     // Frame buffer for handling empty frames from scap
@@ -484,6 +495,7 @@ pub fn capture_display_to_file(
                 }
                 
                 frame_count += 1;
+                frame_offsets_ms.push(start_time.elapsed().as_millis() as u32);
                 
                 // Log progress periodically
                 if frame_count % (fps as u64 * 10) == 0 {
@@ -557,6 +569,8 @@ pub fn capture_display_to_file(
         height,
         frame_count,
         file_size,
+        first_frame_at,
+        frame_offsets_ms,
     })
 }
 
@@ -0,0 +1,360 @@
+/**
+ * ============================================================================
+ * SEGMENT CLASSIFICATION MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Detect segments not worth analyzing (black, static, locked screens)
+ *
+ * METHOD:
+ * 1. Sample one frame every few seconds per display video via bundled FFmpeg,
+ *    downscaled to a small grayscale thumbnail
+ * 2. Compute mean luminance and standard deviation per sample, and the mean
+ *    absolute difference between consecutive samples
+ * 3. Check ActivityWatch window events for a lock screen in the foreground
+ *
+ * CLASSES:
+ * - blank: Every sample is near-black and flat (sleeping monitor, screensaver)
+ * - locked: Lock screen app was in the foreground for most of the segment
+ * - static: Content visible but no sample differs from the previous one
+ * - active: Anything else
+ *
+ * The class is stored on each DisplayRecording in the metadata sidecar; the
+ * Gemini queue skips classes listed in GeminiConfig::skip_classifications.
+ *
+ * ============================================================================
+ */
+
+use crate::activitywatch::types::EventInfo;
+use crate::recording::{capture, integrity, storage};
+use crate::recording::types::{RecordingMetadata, ScreenClassification};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use tauri::AppHandle;
+
+/// Seconds between sampled frames
+const SAMPLE_INTERVAL_SECONDS: u32 = 5;
+
+/// Sample thumbnail size (16:9, one byte per pixel)
+const SAMPLE_WIDTH: usize = 64;
+const SAMPLE_HEIGHT: usize = 36;
+
+/// Samples darker and flatter than this count as black
+const BLANK_MAX_LUMA: f64 = 20.0;
+const BLANK_MAX_STDDEV: f64 = 6.0;
+
+/// Mean absolute pixel difference below which consecutive samples are identical
+/// (leaves room for encoder noise and a blinking cursor)
+const STATIC_MAX_DIFF: f64 = 1.0;
+
+/// Share of the segment a lock screen must cover to classify as locked
+const LOCKED_MIN_SHARE: f64 = 0.5;
+
+/// Foreground app names reported by aw-watcher-window while the screen is locked
+const LOCK_SCREEN_APPS: &[&str] = &[
+    "loginwindow",
+    "ScreenSaverEngine",
+    "LockApp.exe",
+    "LockApp",
+    "LogonUI.exe",
+    "gnome-screensaver",
+    "xscreensaver",
+    "i3lock",
+    "swaylock",
+    "kscreenlocker_greet",
+];
+
+/// Luminance statistics of one sampled frame
+#[derive(Debug, Clone, PartialEq)]
+struct FrameSample {
+    pixels: Vec<u8>,
+    mean: f64,
+    stddev: f64,
+}
+
+impl FrameSample {
+    fn new(pixels: Vec<u8>) -> Self {
+        let count = pixels.len().max(1) as f64;
+        let mean = pixels.iter().map(|&p| p as f64).sum::<f64>() / count;
+        let variance = pixels.iter().map(|&p| (p as f64 - mean).powi(2)).sum::<f64>() / count;
+        Self {
+            pixels,
+            mean,
+            stddev: variance.sqrt(),
+        }
+    }
+
+    fn is_blank(&self) -> bool {
+        self.mean <= BLANK_MAX_LUMA && self.stddev <= BLANK_MAX_STDDEV
+    }
+
+    // Mean absolute per-pixel difference to another sample
+    fn difference(&self, other: &FrameSample) -> f64 {
+        let count = self.pixels.len().max(1) as f64;
+        self.pixels
+            .iter()
+            .zip(&other.pixels)
+            .map(|(&a, &b)| (a as f64 - b as f64).abs())
+            .sum::<f64>()
+            / count
+    }
+}
+
+// =============================================================================
+// Segment Classification
+// =============================================================================
+
+/**
+ * Classify every display of a finalized segment and update its sidecar
+ * Runs in the background; on failure the display stays unclassified
+ *
+ * # Returns
+ * * The metadata with classifications filled in (unchanged on error)
+ */
+pub async fn classify_segment(app: AppHandle, date: NaiveDate, metadata: RecordingMetadata) -> RecordingMetadata {
+    let locked = match segment_range(&metadata) {
+        Some((start, end)) => lock_screen_share(&fetch_window_events(start, end).await, start, end) >= LOCKED_MIN_SHARE,
+        None => false,
+    };
+
+    let app_clone = app.clone();
+    let original = metadata.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let mut metadata = metadata;
+        for display in metadata.displays.iter_mut() {
+            let video_path = storage::find_video_path(&app_clone, &date, &metadata.id, display.display_index);
+            match sample_frames(&video_path) {
+                Ok(samples) => display.classification = classify_samples(&samples, locked),
+                Err(e) => log::warn!(
+                    "[CLASSIFY] Segment {} display {}: {}",
+                    metadata.id, display.display_index, e
+                ),
+            }
+        }
+        metadata
+    })
+    .await;
+
+    let metadata = match result {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            log::error!("[CLASSIFY] Classification task failed for {}: {}", original.id, e);
+            return original;
+        }
+    };
+
+    if metadata.displays.iter().all(|d| d.classification.is_none()) {
+        return metadata;
+    }
+
+    if let Err(e) = storage::save_metadata(&app, &date, &metadata) {
+        log::error!("[CLASSIFY] Failed to save classification for {}: {}", metadata.id, e);
+        return original;
+    }
+
+    if let Err(e) = integrity::amend_segment(&app, &date, &metadata) {
+        log::error!("[CLASSIFY] Failed to amend hash chain for {}: {}", metadata.id, e);
+    }
+
+    log::info!(
+        "[CLASSIFY] Segment {}: {:?}",
+        metadata.id,
+        metadata.displays.iter().map(|d| d.classification).collect::<Vec<_>>()
+    );
+
+    metadata
+}
+
+/**
+ * Classify a display from its frame samples
+ * Returns None when there is nothing to judge (no frames decoded)
+ */
+fn classify_samples(samples: &[FrameSample], locked: bool) -> Option<ScreenClassification> {
+    if samples.is_empty() {
+        return None;
+    }
+
+    if samples.iter().all(FrameSample::is_blank) {
+        return Some(ScreenClassification::Blank);
+    }
+
+    if locked {
+        return Some(ScreenClassification::Locked);
+    }
+
+    let unchanged = samples
+        .windows(2)
+        .all(|pair| pair[0].difference(&pair[1]) < STATIC_MAX_DIFF);
+
+    if unchanged {
+        Some(ScreenClassification::Static)
+    } else {
+        Some(ScreenClassification::Active)
+    }
+}
+
+// =============================================================================
+// Frame Sampling
+// =============================================================================
+
+// Decode sampled grayscale thumbnails from a display video
+fn sample_frames(video_path: &Path) -> Result<Vec<FrameSample>, String> {
+    let ffmpeg_path = capture::get_ffmpeg_path()?;
+    let filter = format!(
+        "fps=1/{},scale={}:{},format=gray",
+        SAMPLE_INTERVAL_SECONDS, SAMPLE_WIDTH, SAMPLE_HEIGHT
+    );
+
+    let mut child = Command::new(&ffmpeg_path)
+        .arg("-i")
+        .arg(video_path)
+        .args(["-vf", &filter, "-f", "rawvideo", "-"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to run FFmpeg at {:?}: {}", ffmpeg_path, e))?;
+
+    let mut raw = Vec::new();
+    if let Some(mut stdout) = child.stdout.take() {
+        stdout
+            .read_to_end(&mut raw)
+            .map_err(|e| format!("Failed to read sampled frames: {}", e))?;
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("Failed to wait for FFmpeg: {}", e))?;
+    if !status.success() {
+        return Err(format!("FFmpeg exited with error: {:?}", status.code()));
+    }
+
+    Ok(raw
+        .chunks_exact(SAMPLE_WIDTH * SAMPLE_HEIGHT)
+        .map(|chunk| FrameSample::new(chunk.to_vec()))
+        .collect())
+}
+
+// =============================================================================
+// Lock Screen Detection
+// =============================================================================
+
+fn segment_range(metadata: &RecordingMetadata) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let start = DateTime::parse_from_rfc3339(&metadata.start_time).ok()?;
+    let end = DateTime::parse_from_rfc3339(&metadata.end_time).ok()?;
+    Some((start.with_timezone(&Utc), end.with_timezone(&Utc)))
+}
+
+async fn fetch_window_events(start: DateTime<Utc>, end: DateTime<Utc>) -> Vec<EventInfo> {
+    let Some(base_url) = crate::activitywatch::manager::current_base_url() else {
+        return Vec::new();
+    };
+
+    match crate::activitywatch::client::fetch_events_by_range(&base_url, start, end).await {
+        Ok(events) => events.window_events,
+        Err(e) => {
+            log::warn!("[CLASSIFY] Could not fetch window events: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+// Fraction of [start, end] during which a lock screen app was in the foreground
+fn lock_screen_share(events: &[EventInfo], start: DateTime<Utc>, end: DateTime<Utc>) -> f64 {
+    let segment_seconds = (end - start).num_milliseconds() as f64 / 1000.0;
+    if segment_seconds <= 0.0 {
+        return 0.0;
+    }
+
+    let locked_seconds: f64 = events
+        .iter()
+        .filter(|e| {
+            e.data
+                .get("app")
+                .and_then(|a| a.as_str())
+                .is_some_and(|app| LOCK_SCREEN_APPS.iter().any(|l| l.eq_ignore_ascii_case(app)))
+        })
+        .filter_map(|e| {
+            let event_start = DateTime::parse_from_rfc3339(&e.timestamp).ok()?.with_timezone(&Utc);
+            let event_end = event_start + chrono::Duration::milliseconds((e.duration * 1000.0) as i64);
+            let overlap = (event_end.min(end) - event_start.max(start)).num_milliseconds() as f64 / 1000.0;
+            Some(overlap.max(0.0))
+        })
+        .sum();
+
+    (locked_seconds / segment_seconds).min(1.0)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn solid(value: u8) -> FrameSample {
+        FrameSample::new(vec![value; SAMPLE_WIDTH * SAMPLE_HEIGHT])
+    }
+
+    fn gradient(offset: u8) -> FrameSample {
+        FrameSample::new(
+            (0..SAMPLE_WIDTH * SAMPLE_HEIGHT)
+                .map(|i| ((i % 200) as u8).wrapping_add(offset))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_classify_samples() {
+        assert_eq!(classify_samples(&[], false), None);
+        assert_eq!(
+            classify_samples(&[solid(0), solid(8), solid(3)], false),
+            Some(ScreenClassification::Blank)
+        );
+        // A black screen stays blank even with the lock app in front
+        assert_eq!(classify_samples(&[solid(0)], true), Some(ScreenClassification::Blank));
+        assert_eq!(
+            classify_samples(&[gradient(0), gradient(0)], true),
+            Some(ScreenClassification::Locked)
+        );
+        assert_eq!(
+            classify_samples(&[gradient(0), gradient(0), gradient(0)], false),
+            Some(ScreenClassification::Static)
+        );
+        assert_eq!(
+            classify_samples(&[gradient(0), gradient(0), gradient(40)], false),
+            Some(ScreenClassification::Active)
+        );
+        // Black then content (monitor woke up) is not blank
+        assert_eq!(
+            classify_samples(&[solid(0), gradient(0)], false),
+            Some(ScreenClassification::Active)
+        );
+    }
+
+    #[test]
+    fn test_lock_screen_share() {
+        let start = DateTime::parse_from_rfc3339("2025-01-15T10:00:00Z").unwrap().with_timezone(&Utc);
+        let end = start + chrono::Duration::seconds(60);
+        let events = vec![
+            EventInfo {
+                id: None,
+                timestamp: "2025-01-15T09:59:30Z".to_string(),
+                duration: 75.0,
+                data: json!({"app": "loginwindow", "title": ""}),
+            },
+            EventInfo {
+                id: None,
+                timestamp: "2025-01-15T10:00:45Z".to_string(),
+                duration: 15.0,
+                data: json!({"app": "Code", "title": "main.rs"}),
+            },
+        ];
+
+        assert!((lock_screen_share(&events, start, end) - 0.75).abs() < 1e-9);
+        assert_eq!(lock_screen_share(&events[1..], start, end), 0.0);
+    }
+}
@@ -15,7 +15,7 @@
 
 use crate::recording::gemini::GeminiConfig;
 use crate::recording::types::RecordingConfig;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Manager};
 
 // Get config file path
@@ -115,7 +115,7 @@ pub fn save_gemini_config(app: &AppHandle, config: &GeminiConfig) -> Result<(),
 }
 
 // =============================================================================
-// Gemini API Key Storage (Separate from config for security)
+// API Key Storage (Separate from config for security)
 // =============================================================================
 
 // Get Gemini API key file path
@@ -126,33 +126,65 @@ fn gemini_api_key_path(app: &AppHandle) -> PathBuf {
         .join("gemini_api_key.txt")
 }
 
+// Get OpenAI-compatible analyzer API key file path
+fn openai_api_key_path(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .expect("app_data_dir available")
+        .join("openai_api_key.txt")
+}
+
 // Load Gemini API key from secure storage
 pub fn load_gemini_api_key(app: &AppHandle) -> Result<Option<String>, String> {
-    let path = gemini_api_key_path(app);
-    
+    load_api_key(&gemini_api_key_path(app), "Gemini")
+}
+
+// Save Gemini API key to secure storage
+pub fn save_gemini_api_key(app: &AppHandle, api_key: &str) -> Result<(), String> {
+    save_api_key(&gemini_api_key_path(app), api_key, "Gemini")
+}
+
+// Delete Gemini API key from secure storage
+pub fn delete_gemini_api_key(app: &AppHandle) -> Result<(), String> {
+    delete_api_key(&gemini_api_key_path(app), "Gemini")
+}
+
+// Load OpenAI-compatible API key from secure storage
+pub fn load_openai_api_key(app: &AppHandle) -> Result<Option<String>, String> {
+    load_api_key(&openai_api_key_path(app), "OpenAI-compatible")
+}
+
+// Save OpenAI-compatible API key to secure storage
+pub fn save_openai_api_key(app: &AppHandle, api_key: &str) -> Result<(), String> {
+    save_api_key(&openai_api_key_path(app), api_key, "OpenAI-compatible")
+}
+
+// Delete OpenAI-compatible API key from secure storage
+pub fn delete_openai_api_key(app: &AppHandle) -> Result<(), String> {
+    delete_api_key(&openai_api_key_path(app), "OpenAI-compatible")
+}
+
+fn load_api_key(path: &Path, label: &str) -> Result<Option<String>, String> {
     if !path.exists() {
-        log::debug!("No Gemini API key file found");
+        log::debug!("No {} API key file found", label);
         return Ok(None);
     }
     
-    let key = std::fs::read_to_string(&path)
+    let key = std::fs::read_to_string(path)
         .map_err(|e| format!("Failed to read API key file: {}", e))?;
     
     let trimmed_key = key.trim().to_string();
     
     if trimmed_key.is_empty() {
-        log::debug!("Gemini API key file is empty");
+        log::debug!("{} API key file is empty", label);
         return Ok(None);
     }
     
-    log::debug!("Loaded Gemini API key from secure storage");
+    log::debug!("Loaded {} API key from secure storage", label);
     Ok(Some(trimmed_key))
 }
 
-// Save Gemini API key to secure storage
-pub fn save_gemini_api_key(app: &AppHandle, api_key: &str) -> Result<(), String> {
-    let path = gemini_api_key_path(app);
-    
+fn save_api_key(path: &Path, api_key: &str, label: &str) -> Result<(), String> {
     // Ensure directory exists
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)
@@ -163,41 +195,38 @@ pub fn save_gemini_api_key(app: &AppHandle, api_key: &str) -> Result<(), String>
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
-        let mut perms = std::fs::metadata(&path.parent().unwrap())
+        let mut perms = std::fs::metadata(path.parent().unwrap())
             .map_err(|_| "Failed to get parent dir metadata")?
             .permissions();
         perms.set_mode(0o700); // rwx------
-        std::fs::set_permissions(&path.parent().unwrap(), perms)
+        std::fs::set_permissions(path.parent().unwrap(), perms)
             .map_err(|_| "Failed to set directory permissions")?;
     }
     
-    std::fs::write(&path, api_key.trim())
+    std::fs::write(path, api_key.trim())
         .map_err(|e| format!("Failed to write API key file: {}", e))?;
     
     // Set restrictive file permissions on the key file itself
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
-        let mut perms = std::fs::metadata(&path)
+        let mut perms = std::fs::metadata(path)
             .map_err(|_| "Failed to get file metadata")?
             .permissions();
         perms.set_mode(0o600); // rw-------
-        std::fs::set_permissions(&path, perms)
+        std::fs::set_permissions(path, perms)
             .map_err(|_| "Failed to set file permissions")?;
     }
     
-    log::info!("Saved Gemini API key to secure storage");
+    log::info!("Saved {} API key to secure storage", label);
     Ok(())
 }
 
-// Delete Gemini API key from secure storage
-pub fn delete_gemini_api_key(app: &AppHandle) -> Result<(), String> {
-    let path = gemini_api_key_path(app);
-    
+fn delete_api_key(path: &Path, label: &str) -> Result<(), String> {
     if path.exists() {
-        std::fs::remove_file(&path)
+        std::fs::remove_file(path)
             .map_err(|e| format!("Failed to delete API key file: {}", e))?;
-        log::info!("Deleted Gemini API key from secure storage");
+        log::info!("Deleted {} API key from secure storage", label);
     }
     
     Ok(())
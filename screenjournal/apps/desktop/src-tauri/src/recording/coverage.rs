@@ -0,0 +1,702 @@
+/**
+ * ============================================================================
+ * RECORDING COVERAGE MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Detect when recording silently wasn't running
+ *
+ * FUNCTIONALITY:
+ * - Record incidents (capture failures, stops, app launches) as they happen
+ * - Compare a day's segments against expected recording windows per display
+ * - Expected windows: configured schedule, else ActivityWatch not-afk time,
+ *   else the span between the day's first and last segment
+ * - Attribute each gap to a likely cause from segments and incidents
+ * - Push a `recording_coverage` measurement per display to the collector
+ *
+ * STORAGE:
+ * ~/.screenjournal/coverage/{date}.json - incidents recorded on that (UTC) day
+ *
+ * ============================================================================
+ */
+
+use crate::collector::{batch, config as collector_config, formatter};
+use crate::recording::storage;
+use crate::recording::types::{
+    CoverageGap, CoverageIncident, CoverageIncidentKind, CoverageReport, CoverageSchedule,
+    DisplayCoverage, GapCause, RecordingConfig, RecordingMetadata,
+};
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use once_cell::sync::Lazy;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tokio::time::{sleep, Duration};
+
+/// Gaps shorter than this are segment rotation overhead, not missing coverage
+const MIN_GAP_SECONDS: i64 = 30;
+
+/// How far before/after a gap an incident may be and still explain it
+const CAUSE_TOLERANCE_SECONDS: i64 = 120;
+
+/// How often the worker pushes coverage measurements
+const WORKER_INTERVAL_SECS: u64 = 3600; // 1 hour
+
+/// Delay before the first push so ActivityWatch has time to start
+const WORKER_STARTUP_DELAY_SECS: u64 = 300;
+
+// Serializes read-modify-write of incident files
+static INCIDENT_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+type Interval = (DateTime<Utc>, DateTime<Utc>);
+
+// =============================================================================
+// Incidents
+// =============================================================================
+
+fn incidents_path(app: &AppHandle, date: &NaiveDate) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .expect("app_data_dir available")
+        .join("coverage")
+        .join(format!("{}.json", date.format("%Y-%m-%d")))
+}
+
+/**
+ * Append an incident to the log of the day it started on
+ * Failures are logged, never propagated - coverage must not break recording
+ */
+pub fn record_incident(app: &AppHandle, incident: CoverageIncident) {
+    let date = match parse_time(&incident.start_time) {
+        Some(t) => t.date_naive(),
+        None => return,
+    };
+
+    let _guard = INCIDENT_LOCK.lock().unwrap();
+    let path = incidents_path(app, &date);
+
+    let mut incidents = load_incidents_from(&path);
+    incidents.push(incident);
+
+    let result = path
+        .parent()
+        .map(std::fs::create_dir_all)
+        .unwrap_or(Ok(()))
+        .map_err(|e| e.to_string())
+        .and_then(|_| serde_json::to_string_pretty(&incidents).map_err(|e| e.to_string()))
+        .and_then(|contents| std::fs::write(&path, contents).map_err(|e| e.to_string()));
+
+    if let Err(e) = result {
+        log::warn!("[COVERAGE] Failed to record incident: {}", e);
+    }
+}
+
+/**
+ * Record an incident that happened at a single moment
+ */
+pub fn record_event(
+    app: &AppHandle,
+    kind: CoverageIncidentKind,
+    display_index: Option<u32>,
+    message: Option<String>,
+) {
+    let now = Utc::now().to_rfc3339();
+    record_incident(
+        app,
+        CoverageIncident {
+            kind,
+            start_time: now.clone(),
+            end_time: now,
+            display_index,
+            message,
+        },
+    );
+}
+
+// Load incidents recorded on a day
+pub fn load_incidents(app: &AppHandle, date: &NaiveDate) -> Vec<CoverageIncident> {
+    load_incidents_from(&incidents_path(app, date))
+}
+
+fn load_incidents_from(path: &PathBuf) -> Vec<CoverageIncident> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+// =============================================================================
+// Report
+// =============================================================================
+
+/**
+ * Build the coverage report for one day
+ *
+ * # Arguments
+ * * `date` - UTC day (same as the recordings/ directory layout)
+ * * `config` - Recording config (coverage schedule)
+ */
+pub async fn build_report(
+    app: &AppHandle,
+    date: &NaiveDate,
+    config: &RecordingConfig,
+) -> Result<CoverageReport, String> {
+    let day_start = Utc.from_utc_datetime(&date.and_time(NaiveTime::MIN));
+    let day_end = (day_start + chrono::Duration::days(1)).min(Utc::now());
+    if day_end <= day_start {
+        return Err(format!("{} is in the future", date));
+    }
+
+    let segments = storage::get_segments_for_date(app, date);
+    let incidents = load_incidents(app, date);
+
+    let (expected, source) = match &config.coverage_schedule {
+        Some(schedule) => (schedule_windows(schedule, (day_start, day_end))?, "schedule"),
+        None => match activity_windows((day_start, day_end)).await {
+            Some(windows) => (windows, "activitywatch"),
+            None => (segment_span(&segments), "segments"),
+        },
+    };
+
+    Ok(compute_report(date, &expected, source, &segments, &incidents))
+}
+
+/**
+ * Compare segments against expected windows for every display seen that day
+ */
+pub fn compute_report(
+    date: &NaiveDate,
+    expected: &[Interval],
+    expected_source: &str,
+    segments: &[RecordingMetadata],
+    incidents: &[CoverageIncident],
+) -> CoverageReport {
+    let expected = merge_intervals(expected.to_vec());
+    let expected_seconds = total_seconds(&expected);
+
+    let mut display_indexes: BTreeSet<u32> = segments
+        .iter()
+        .flat_map(|s| s.displays.iter().map(|d| d.display_index))
+        .chain(incidents.iter().filter_map(|i| i.display_index))
+        .collect();
+    if display_indexes.is_empty() {
+        display_indexes.insert(0);
+    }
+
+    let any_display = merge_intervals(segments.iter().filter_map(segment_interval).collect());
+
+    let displays = display_indexes
+        .into_iter()
+        .map(|display_index| {
+            let covered = merge_intervals(
+                segments
+                    .iter()
+                    .filter(|s| s.displays.iter().any(|d| d.display_index == display_index))
+                    .filter_map(segment_interval)
+                    .collect(),
+            );
+
+            let missing = subtract_intervals(&expected, &covered);
+            let recorded_seconds = (expected_seconds - total_seconds(&missing)).max(0.0);
+
+            let gaps = missing
+                .into_iter()
+                .filter(|(start, end)| (*end - *start).num_seconds() >= MIN_GAP_SECONDS)
+                .map(|gap| {
+                    let (cause, detail) = attribute_cause(gap, display_index, incidents, &any_display);
+                    CoverageGap {
+                        start_time: gap.0.to_rfc3339(),
+                        end_time: gap.1.to_rfc3339(),
+                        duration_seconds: seconds(gap),
+                        cause,
+                        detail,
+                    }
+                })
+                .collect();
+
+            DisplayCoverage {
+                display_index,
+                expected_seconds,
+                recorded_seconds,
+                coverage_ratio: if expected_seconds > 0.0 {
+                    recorded_seconds / expected_seconds
+                } else {
+                    1.0
+                },
+                gaps,
+            }
+        })
+        .collect();
+
+    CoverageReport {
+        date: date.format("%Y-%m-%d").to_string(),
+        generated_at: Utc::now().to_rfc3339(),
+        expected_source: expected_source.to_string(),
+        displays,
+    }
+}
+
+/**
+ * Pick the most specific explanation for a gap
+ *
+ * Order: capture error on this display > other displays recorded >
+ * clean stop before the gap > app relaunch without a stop > not running
+ */
+fn attribute_cause(
+    gap: Interval,
+    display_index: u32,
+    incidents: &[CoverageIncident],
+    any_display: &[Interval],
+) -> (GapCause, Option<String>) {
+    let tolerance = chrono::Duration::seconds(CAUSE_TOLERANCE_SECONDS);
+    let near_gap = (gap.0 - tolerance, gap.1 + tolerance);
+
+    let parsed: Vec<(&CoverageIncident, Interval)> = incidents
+        .iter()
+        .filter_map(|i| Some((i, (parse_time(&i.start_time)?, parse_time(&i.end_time)?))))
+        .collect();
+
+    let capture_error = parsed.iter().find(|(i, span)| {
+        i.kind == CoverageIncidentKind::CaptureError
+            && i.display_index.is_none_or(|d| d == display_index)
+            && span.0 <= near_gap.1
+            && span.1 >= near_gap.0
+    });
+    if let Some((incident, _)) = capture_error {
+        return (GapCause::CaptureError, incident.message.clone());
+    }
+
+    let other_recorded: f64 = any_display
+        .iter()
+        .map(|interval| seconds((interval.0.max(gap.0), interval.1.min(gap.1))).max(0.0))
+        .sum();
+    if other_recorded * 2.0 >= seconds(gap) {
+        return (GapCause::DisplayMissing, None);
+    }
+
+    let stopped = parsed.iter().any(|(i, span)| {
+        i.kind == CoverageIncidentKind::RecordingStopped && span.0 >= near_gap.0 && span.0 <= gap.1
+    });
+    if stopped {
+        return (GapCause::RecordingStopped, None);
+    }
+
+    let restarted = parsed.iter().any(|(i, span)| {
+        i.kind == CoverageIncidentKind::AppStarted && span.0 >= gap.0 && span.0 <= near_gap.1
+    });
+    if restarted {
+        return (GapCause::AppRestarted, None);
+    }
+
+    (GapCause::NotRunning, None)
+}
+
+// =============================================================================
+// Expected Windows
+// =============================================================================
+
+/**
+ * Convert a local-time schedule into UTC windows clipped to the day
+ * A UTC day can overlap two local days, so neighbours are checked too
+ */
+fn schedule_windows(schedule: &CoverageSchedule, day: Interval) -> Result<Vec<Interval>, String> {
+    let parse = |s: &str| {
+        NaiveTime::parse_from_str(s, "%H:%M")
+            .map_err(|e| format!("Invalid schedule time '{}' (expected HH:MM): {}", s, e))
+    };
+    let start = parse(&schedule.start_time)?;
+    let end = parse(&schedule.end_time)?;
+
+    let local_day = day.0.with_timezone(&Local).date_naive();
+    let mut windows = Vec::new();
+
+    for offset in -1..=1 {
+        let local_date = local_day + chrono::Duration::days(offset);
+        if !schedule.weekdays.contains(&local_date.weekday().number_from_monday()) {
+            continue;
+        }
+
+        // Overnight schedules (e.g. 22:00-06:00) end on the next local day
+        let end_date = if end <= start { local_date + chrono::Duration::days(1) } else { local_date };
+
+        let window_start = Local.from_local_datetime(&local_date.and_time(start)).earliest();
+        let window_end = Local.from_local_datetime(&end_date.and_time(end)).latest();
+
+        if let (Some(s), Some(e)) = (window_start, window_end) {
+            let s = s.with_timezone(&Utc).max(day.0);
+            let e = e.with_timezone(&Utc).min(day.1);
+            if s < e {
+                windows.push((s, e));
+            }
+        }
+    }
+
+    Ok(windows)
+}
+
+// ActivityWatch not-afk periods within the day (None if AW is unavailable)
+async fn activity_windows(day: Interval) -> Option<Vec<Interval>> {
+    let base_url = crate::activitywatch::manager::current_base_url()?;
+
+    let events = match crate::activitywatch::client::fetch_events_by_range(&base_url, day.0, day.1).await {
+        Ok(events) => events,
+        Err(e) => {
+            log::warn!("[COVERAGE] Could not fetch AFK events: {}", e);
+            return None;
+        }
+    };
+
+    let windows = events
+        .afk_events
+        .iter()
+        .filter(|e| e.data.get("status").and_then(|s| s.as_str()) == Some("not-afk"))
+        .filter_map(|e| {
+            let start = parse_time(&e.timestamp)?;
+            let end = start + chrono::Duration::milliseconds((e.duration * 1000.0) as i64);
+            let (start, end) = (start.max(day.0), end.min(day.1));
+            (start < end).then_some((start, end))
+        })
+        .collect();
+
+    Some(windows)
+}
+
+// First segment start to last segment end - finds gaps between segments only
+fn segment_span(segments: &[RecordingMetadata]) -> Vec<Interval> {
+    let intervals: Vec<Interval> = segments.iter().filter_map(segment_interval).collect();
+    match (intervals.iter().map(|i| i.0).min(), intervals.iter().map(|i| i.1).max()) {
+        (Some(start), Some(end)) => vec![(start, end)],
+        _ => Vec::new(),
+    }
+}
+
+// =============================================================================
+// Interval Helpers
+// =============================================================================
+
+fn parse_time(s: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(s).ok().map(|t| t.with_timezone(&Utc))
+}
+
+fn segment_interval(segment: &RecordingMetadata) -> Option<Interval> {
+    Some((parse_time(&segment.start_time)?, parse_time(&segment.end_time)?))
+}
+
+fn seconds(interval: Interval) -> f64 {
+    (interval.1 - interval.0).num_milliseconds() as f64 / 1000.0
+}
+
+fn total_seconds(intervals: &[Interval]) -> f64 {
+    intervals.iter().map(|i| seconds(*i)).sum()
+}
+
+// Sort and merge overlapping/touching intervals
+fn merge_intervals(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.retain(|(start, end)| start < end);
+    intervals.sort_by_key(|i| i.0);
+
+    let mut merged: Vec<Interval> = Vec::with_capacity(intervals.len());
+    for interval in intervals {
+        match merged.last_mut() {
+            Some(last) if interval.0 <= last.1 => last.1 = last.1.max(interval.1),
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
+// Parts of `from` not covered by `remove` (both must be merged)
+fn subtract_intervals(from: &[Interval], remove: &[Interval]) -> Vec<Interval> {
+    let mut result = Vec::new();
+
+    for &(start, end) in from {
+        let mut cursor = start;
+        for &(r_start, r_end) in remove {
+            if r_end <= cursor || r_start >= end {
+                continue;
+            }
+            if r_start > cursor {
+                result.push((cursor, r_start));
+            }
+            cursor = cursor.max(r_end);
+        }
+        if cursor < end {
+            result.push((cursor, end));
+        }
+    }
+
+    result
+}
+
+// =============================================================================
+// Collector
+// =============================================================================
+
+/**
+ * Format one display's coverage as line protocol
+ * Uses end of day as timestamp so repeated pushes for a day overwrite each other
+ *
+ * Example output:
+ * recording_coverage,date=2025-01-15,display=0,hostname=laptop1,source=activitywatch expected_seconds=28800,recorded_seconds=27000,coverage_ratio=0.9375,gap_count=3i,largest_gap_seconds=900 1736985599000000000
+ */
+pub fn format_coverage_line(
+    report: &CoverageReport,
+    display: &DisplayCoverage,
+    hostname: &str,
+) -> Result<String, String> {
+    let nanos = formatter::timestamp_to_nanos(&format!("{}T23:59:59Z", report.date))?;
+
+    let largest_gap = display
+        .gaps
+        .iter()
+        .map(|g| g.duration_seconds)
+        .fold(0.0, f64::max);
+
+    Ok(format!(
+        "recording_coverage,date={},display={},hostname={},source={} expected_seconds={},recorded_seconds={},coverage_ratio={},gap_count={}i,largest_gap_seconds={} {}",
+        formatter::escape_tag_value(&report.date),
+        display.display_index,
+        formatter::escape_tag_value(hostname),
+        formatter::escape_tag_value(&report.expected_source),
+        display.expected_seconds as i64,
+        display.recorded_seconds as i64,
+        display.coverage_ratio,
+        display.gaps.len(),
+        largest_gap as i64,
+        nanos
+    ))
+}
+
+/**
+ * Queue coverage measurements for every display in the report
+ */
+pub fn send_to_collector(report: &CoverageReport) -> Result<(), String> {
+    if !collector_config::is_enabled() {
+        return Ok(());
+    }
+
+    let hostname = hostname::get()
+        .ok()
+        .and_then(|h| h.into_string().ok())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    for display in &report.displays {
+        batch::add_event(format_coverage_line(report, display, &hostname)?)?;
+    }
+
+    log::info!(
+        "[COVERAGE] Queued coverage for {} ({} display(s))",
+        report.date,
+        report.displays.len()
+    );
+
+    Ok(())
+}
+
+// =============================================================================
+// Background Worker
+// =============================================================================
+
+/**
+ * Start the coverage worker
+ * Pushes yesterday's (final) and today's (partial) coverage every hour
+ */
+pub fn start_worker(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        sleep(Duration::from_secs(WORKER_STARTUP_DELAY_SECS)).await;
+
+        loop {
+            let config = crate::recording::manager::current_config();
+
+            if config.enabled {
+                let today = Utc::now().date_naive();
+                for date in [today - chrono::Duration::days(1), today] {
+                    match build_report(&app, &date, &config).await {
+                        Ok(report) => {
+                            if let Err(e) = send_to_collector(&report) {
+                                log::warn!("[COVERAGE] Failed to queue coverage for {}: {}", date, e);
+                            }
+                        }
+                        Err(e) => log::warn!("[COVERAGE] Failed to build report for {}: {}", date, e),
+                    }
+                }
+            }
+
+            sleep(Duration::from_secs(WORKER_INTERVAL_SECS)).await;
+        }
+    });
+
+    log::info!("Coverage worker started");
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::types::DisplayRecording;
+
+    fn t(s: &str) -> DateTime<Utc> {
+        parse_time(&format!("2025-01-15T{}Z", s)).unwrap()
+    }
+
+    fn segment(start: &str, end: &str, displays: &[u32]) -> RecordingMetadata {
+        RecordingMetadata {
+            id: format!("segment_{}", start),
+            format: "mp4".to_string(),
+            codec: "h264".to_string(),
+            framerate: 4,
+            start_time: t(start).to_rfc3339(),
+            end_time: t(end).to_rfc3339(),
+            duration_seconds: seconds((t(start), t(end))),
+            total_file_size_bytes: 0,
+            display_count: displays.len() as u32,
+            displays: displays
+                .iter()
+                .map(|&d| DisplayRecording {
+                    display_index: d,
+                    width: 1280,
+                    height: 720,
+                    frame_count: 0,
+                    file_size_bytes: 0,
+                    filename: String::new(),
+                    classification: None,
+                })
+                .collect(),
+            imported_from: None,
+            high_risk: false,
+        }
+    }
+
+    #[test]
+    fn test_merge_and_subtract_intervals() {
+        let merged = merge_intervals(vec![
+            (t("10:05:00"), t("10:10:00")),
+            (t("10:00:00"), t("10:06:00")),
+            (t("11:00:00"), t("11:05:00")),
+        ]);
+        assert_eq!(merged, vec![(t("10:00:00"), t("10:10:00")), (t("11:00:00"), t("11:05:00"))]);
+
+        let missing = subtract_intervals(&[(t("09:00:00"), t("12:00:00"))], &merged);
+        assert_eq!(
+            missing,
+            vec![
+                (t("09:00:00"), t("10:00:00")),
+                (t("10:10:00"), t("11:00:00")),
+                (t("11:05:00"), t("12:00:00")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gap_causes() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let segments = vec![
+            segment("10:00:00", "10:05:00", &[0, 1]),
+            segment("10:05:01", "10:15:00", &[0]),
+            segment("10:20:00", "10:25:00", &[0, 1]),
+        ];
+        let incidents = vec![CoverageIncident {
+            kind: CoverageIncidentKind::AppStarted,
+            start_time: t("10:19:50").to_rfc3339(),
+            end_time: t("10:19:50").to_rfc3339(),
+            display_index: None,
+            message: None,
+        }];
+
+        let report = compute_report(
+            &date,
+            &[(t("10:00:00"), t("10:25:00"))],
+            "activitywatch",
+            &segments,
+            &incidents,
+        );
+
+        // Display 0: 1s rotation gap ignored, 10:15-10:20 explained by relaunch
+        let d0 = &report.displays[0];
+        assert_eq!(d0.gaps.len(), 1);
+        assert_eq!(d0.gaps[0].cause, GapCause::AppRestarted);
+        assert_eq!(d0.gaps[0].duration_seconds, 300.0);
+
+        // Display 1: 10:05-10:20 missing while display 0 recorded most of it
+        let d1 = &report.displays[1];
+        assert_eq!(d1.gaps.len(), 1);
+        assert_eq!(d1.gaps[0].cause, GapCause::DisplayMissing);
+    }
+
+    #[test]
+    fn test_capture_error_cause() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let incidents = vec![CoverageIncident {
+            kind: CoverageIncidentKind::CaptureError,
+            start_time: t("10:05:00").to_rfc3339(),
+            end_time: t("10:10:00").to_rfc3339(),
+            display_index: Some(0),
+            message: Some("FFmpeg exited with error".to_string()),
+        }];
+
+        let report = compute_report(
+            &date,
+            &[(t("10:00:00"), t("10:10:00"))],
+            "schedule",
+            &[segment("10:00:00", "10:05:00", &[0])],
+            &incidents,
+        );
+
+        let gap = &report.displays[0].gaps[0];
+        assert_eq!(gap.cause, GapCause::CaptureError);
+        assert_eq!(gap.detail.as_deref(), Some("FFmpeg exited with error"));
+        assert_eq!(report.displays[0].coverage_ratio, 0.5);
+    }
+
+    #[test]
+    fn test_failed_rotation_gap() {
+        // Rotation failed at 10:05; recording was restarted at 10:40
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let incidents = vec![CoverageIncident {
+            kind: CoverageIncidentKind::CaptureError,
+            start_time: t("10:05:00").to_rfc3339(),
+            end_time: t("10:05:01").to_rfc3339(),
+            display_index: None,
+            message: Some("Failed to start FFmpeg".to_string()),
+        }];
+        let segments = vec![
+            segment("10:00:00", "10:05:00", &[0, 1]),
+            segment("10:40:00", "10:45:00", &[0, 1]),
+        ];
+
+        let report = compute_report(&date, &segment_span(&segments), "segments", &segments, &incidents);
+
+        for display in &report.displays {
+            assert_eq!(display.gaps.len(), 1);
+            assert_eq!(display.gaps[0].start_time, t("10:05:00").to_rfc3339());
+            assert_eq!(display.gaps[0].end_time, t("10:40:00").to_rfc3339());
+            assert_eq!(display.gaps[0].cause, GapCause::CaptureError);
+        }
+    }
+
+    #[test]
+    fn test_format_coverage_line() {
+        let report = CoverageReport {
+            date: "2025-01-15".to_string(),
+            generated_at: String::new(),
+            expected_source: "activitywatch".to_string(),
+            displays: vec![DisplayCoverage {
+                display_index: 0,
+                expected_seconds: 3600.0,
+                recorded_seconds: 3000.0,
+                coverage_ratio: 3000.0 / 3600.0,
+                gaps: vec![],
+            }],
+        };
+
+        let line = format_coverage_line(&report, &report.displays[0], "my laptop").unwrap();
+        assert!(line.starts_with(
+            "recording_coverage,date=2025-01-15,display=0,hostname=my\\ laptop,source=activitywatch expected_seconds=3600,recorded_seconds=3000,"
+        ));
+        assert!(line.contains("gap_count=0i,largest_gap_seconds=0 "));
+    }
+}
@@ -0,0 +1,907 @@
+/**
+ * ============================================================================
+ * VIDEO ANALYZER MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Provider-agnostic video analysis behind the job queue
+ *
+ * BACKENDS:
+ * - gemini: Google Gemini, video sent inline or uploaded via the Files API
+ *   (client.rs, files.rs)
+ * - openai_compatible: Any OpenAI-compatible chat completions endpoint with
+ *   image input (OpenAI, Azure, vLLM, llama.cpp server) (openai.rs)
+ * - local: Local model server speaking the Ollama API (local.rs)
+ *
+ * Frame-based backends receive still frames sampled from the video with
+ * bundled FFmpeg; the prompt tells the model which timestamp each frame shows
+ * so the timeline keeps the same MM:SS format as Gemini's.
+ *
+ * MODEL TARGETS:
+ * A ModelTarget (entry of the fallback chain) picks the backend and may
+ * override its model and endpoint; the queue walks the chain on failure.
+ *
+ * ERRORS:
+ * All backends classify failures as GeminiError so the queue's retry and
+ * rate-limit handling works unchanged.
+ *
+ * LONG VIDEOS:
+ * Videos longer than analysis_window_minutes are cut into overlapping
+ * windows (re-encoded with FFmpeg so each starts exactly where requested),
+ * analyzed one by one and stitched back together (validation.rs).
+ *
+ * SPEED-UP (speedup_mode, trades timeline granularity for token cost):
+ * - reencode: FFmpeg re-encodes the video at speedup_factor x speed before
+ *   upload; the timeline is rescaled back to real time (validation.rs)
+ * - reduced_frame_rate: the video is kept; Gemini samples it at a lower
+ *   frame rate and frame-based backends get fewer frames
+ *
+ * Frame-based backends already cap frames at max_frames, so only
+ * reduced_frame_rate lowers their cost. The factor and estimated token
+ * savings are recorded in the analysis.
+ *
+ * USAGE:
+ * Token usage reported by a backend (Gemini's usageMetadata) is summed per
+ * job and recorded in the usage ledger (usage.rs), failed attempts included.
+ *
+ * VALIDATION:
+ * Every backend's reply goes through validation.rs. A reply that can't be
+ * parsed or validated is sent back to the model once with the reason
+ * (self-correction) before the job fails.
+ *
+ * ============================================================================
+ */
+
+use crate::recording::capture;
+use crate::recording::gemini::{
+    client::{GeminiAnalyzer, DEFAULT_GEMINI_MODEL, GEMINI_API_BASE},
+    context::{fetch_window_spans, window_slice, window_switches},
+    local::LocalAnalyzer,
+    openai::OpenAiCompatibleAnalyzer,
+    prompt::{self, PromptContext, PromptTemplate},
+    types::{
+        AnalyzerBackend, GeminiConfig, GeminiError, GeminiJob, HttpAnalyzerConfig, ModelTarget, SpeedupSummary,
+        TimelineAnalysis, TokenUsage,
+    },
+    validation::{self, ValidatedTimeline, WindowTimeline},
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use futures_util::future::BoxFuture;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Request timeout (video upload and local inference can take time)
+pub(crate) const REQUEST_TIMEOUT_SECS: u64 = 300; // 5 minutes
+
+/// Width of frames sent to frame-based backends
+const FRAME_WIDTH: u32 = 1280;
+
+/// Gemini video tokens per second at default sampling (1 frame/s, 258 tokens/frame)
+const VIDEO_TOKENS_PER_SECOND: f64 = 258.0;
+
+// =============================================================================
+// Analyzer Trait
+// =============================================================================
+
+/// Video to analyze
+#[derive(Debug, Clone)]
+pub struct AnalysisRequest {
+    pub video_path: PathBuf,
+    pub video_duration_seconds: f64,
+    
+    /// Rendered timeline prompt (frame-based backends add their frame legend)
+    pub prompt: String,
+    
+    /// Sample this many times fewer frames than normal (1 = normal)
+    pub sampling_factor: f64,
+}
+
+/// A rejected reply and why, sent back to the model for self-correction
+#[derive(Debug, Clone)]
+pub struct Correction {
+    pub previous_response: String,
+    pub prompt: String,
+}
+
+/**
+ * A video analysis provider
+ * Returns a validated timeline with MM:SS times relative to the video start
+ */
+pub trait VideoAnalyzer: Send + Sync {
+    /// Backend identifier (for logs)
+    fn backend(&self) -> AnalyzerBackend;
+
+    /// Model name used for requests
+    fn model(&self) -> &str;
+
+    fn analyze<'a>(&'a self, request: &'a AnalysisRequest) -> BoxFuture<'a, Result<ValidatedTimeline, GeminiError>>;
+
+    /// Tokens used by this analyzer's requests so far (empty if not reported)
+    fn usage(&self) -> TokenUsage {
+        TokenUsage::default()
+    }
+}
+
+/**
+ * Parse and validate a model reply, asking the model to correct itself once
+ *
+ * # Arguments
+ * * `request` - Request being analyzed (duration for validation)
+ * * `send` - Sends the conversation and returns the reply text; called with
+ *   None first, then with the correction turn if the reply was rejected
+ */
+pub(crate) async fn validate_with_correction<F, Fut>(
+    request: &AnalysisRequest,
+    mut send: F,
+) -> Result<ValidatedTimeline, GeminiError>
+where
+    F: FnMut(Option<Correction>) -> Fut,
+    Fut: Future<Output = Result<String, GeminiError>>,
+{
+    let text = send(None).await?;
+    let error = match parse_and_validate(&text, request.video_duration_seconds) {
+        Ok(validated) => return Ok(validated),
+        Err(error) => error,
+    };
+
+    log::warn!("[ANALYZER] Rejected timeline, asking the model to correct it: {}", error);
+    let correction = Correction {
+        previous_response: text,
+        prompt: prompt::build_correction_prompt(&error, request.video_duration_seconds),
+    };
+
+    // The correction turn is a request of its own
+    super::queue::wait_for_rate_limit().await;
+    let text = send(Some(correction)).await?;
+    let mut validated = parse_and_validate(&text, request.video_duration_seconds).map_err(|e| GeminiError::Permanent {
+        message: format!("Invalid timeline after self-correction: {}", e),
+    })?;
+    validated.quality.corrected = true;
+    Ok(validated)
+}
+
+fn parse_and_validate(text: &str, video_duration_seconds: f64) -> Result<ValidatedTimeline, String> {
+    let entries = super::client::parse_timeline_json(text)?;
+    validation::validate_timeline(entries, video_duration_seconds)
+}
+
+/**
+ * Create the analyzer for one model target
+ *
+ * # Arguments
+ * * `config` - Gemini configuration (backend endpoints and defaults)
+ * * `target` - Backend, model and endpoint overrides to use
+ * * `app` - App handle for user-provided API keys
+ */
+pub fn create_analyzer(
+    config: &GeminiConfig,
+    target: &ModelTarget,
+    app: Option<&tauri::AppHandle>,
+) -> Result<Box<dyn VideoAnalyzer>, GeminiError> {
+    match target.backend {
+        AnalyzerBackend::Gemini => {
+            let api_key = match app {
+                Some(app_handle) => super::get_api_key_with_app(app_handle),
+                None => super::get_api_key(),
+            }
+            .map_err(|e| GeminiError::Permanent { message: e })?;
+
+            Ok(Box::new(GeminiAnalyzer::new(
+                api_key,
+                target.base_url.clone().unwrap_or_else(|| GEMINI_API_BASE.to_string()),
+                target.model.clone().unwrap_or_else(|| DEFAULT_GEMINI_MODEL.to_string()),
+                config.thinking_budget,
+                config.rate_limit_max_wait_seconds,
+                config.files_api_threshold_mb * 1_000_000,
+            )))
+        }
+        AnalyzerBackend::OpenaiCompatible => {
+            let endpoint = resolve_endpoint(&config.openai, target);
+            validate_endpoint(&endpoint.base_url, &endpoint.model)?;
+            Ok(Box::new(OpenAiCompatibleAnalyzer::new(
+                endpoint,
+                super::get_openai_api_key(app),
+                config.rate_limit_max_wait_seconds,
+            )))
+        }
+        AnalyzerBackend::Local => {
+            let endpoint = resolve_endpoint(&config.local, target);
+            validate_endpoint(&endpoint.base_url, &endpoint.model)?;
+            Ok(Box::new(LocalAnalyzer::new(endpoint)))
+        }
+    }
+}
+
+/**
+ * Check whether a model target has what it needs to run
+ * Gemini needs an API key; HTTP backends need an endpoint and model
+ */
+pub fn is_configured(config: &GeminiConfig, target: &ModelTarget, app: Option<&tauri::AppHandle>) -> bool {
+    match target.backend {
+        AnalyzerBackend::Gemini => match app {
+            Some(app_handle) => super::has_api_key_with_app(app_handle),
+            None => super::has_api_key(),
+        },
+        AnalyzerBackend::OpenaiCompatible => {
+            let endpoint = resolve_endpoint(&config.openai, target);
+            validate_endpoint(&endpoint.base_url, &endpoint.model).is_ok()
+        }
+        AnalyzerBackend::Local => {
+            let endpoint = resolve_endpoint(&config.local, target);
+            validate_endpoint(&endpoint.base_url, &endpoint.model).is_ok()
+        }
+    }
+}
+
+// Apply a target's model/endpoint overrides to a backend's configured endpoint
+fn resolve_endpoint(base: &HttpAnalyzerConfig, target: &ModelTarget) -> HttpAnalyzerConfig {
+    HttpAnalyzerConfig {
+        base_url: target.base_url.clone().unwrap_or_else(|| base.base_url.clone()),
+        model: target.model.clone().unwrap_or_else(|| base.model.clone()),
+        max_frames: base.max_frames,
+    }
+}
+
+fn validate_endpoint(base_url: &str, model: &str) -> Result<(), GeminiError> {
+    if !(base_url.starts_with("http://") || base_url.starts_with("https://")) {
+        return Err(GeminiError::Permanent {
+            message: format!("Invalid analyzer base URL: '{}'", base_url),
+        });
+    }
+    if model.trim().is_empty() {
+        return Err(GeminiError::Permanent {
+            message: "No analyzer model configured".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/**
+ * Analyze a job's video file with one model target
+ *
+ * # Arguments
+ * * `job` - Job with the video path, segment and display to analyze
+ * * `config` - Gemini configuration (backend endpoints)
+ * * `target` - Model target to use (entry of the job's model chain)
+ *
+ * # Returns
+ * * `Ok(TimelineAnalysis)` on success
+ * * `Err(GeminiError)` with classified error for retry handling
+ */
+pub async fn analyze_video(
+    job: &GeminiJob,
+    config: &GeminiConfig,
+    target: &ModelTarget,
+    app: Option<&tauri::AppHandle>,
+) -> Result<TimelineAnalysis, GeminiError> {
+    let analyzer = create_analyzer(config, target, app)?;
+    let segment_id = &job.segment_id;
+    let display_index = job.display_index;
+    let video_duration_seconds = job.metadata.duration_seconds;
+
+    log::info!(
+        "Starting {:?} analysis ({}) for segment {} display {} ({:.1}s video)",
+        analyzer.backend(),
+        analyzer.model(),
+        segment_id,
+        display_index,
+        video_duration_seconds
+    );
+
+    let speed = config.playback_speed();
+    let sampling_factor = config.sampling_factor();
+
+    let template = prompt::resolve_template(app, config).map_err(|message| GeminiError::Permanent { message })?;
+    let context = PromptContext {
+        playback_speed: speed,
+        video_duration_seconds,
+        hostname: hostname::get()
+            .ok()
+            .and_then(|h| h.into_string().ok())
+            .unwrap_or_else(|| "unknown".to_string()),
+        local_time: local_time(&job.metadata.start_time),
+        rubric: config.prompt_rubric.clone(),
+        window_switches: if config.window_context {
+            window_switches(&fetch_window_spans(job).await)
+        } else {
+            Vec::new()
+        },
+    };
+
+    // Window length is what the model watches, so sped-up windows cover more real time
+    let windows = analysis_windows(
+        video_duration_seconds,
+        ((config.analysis_window_minutes * 60) as f64 * speed) as u64,
+        config.analysis_window_overlap_seconds,
+    );
+    let result = if windows.len() > 1 || speed > 1.0 {
+        analyze_windows(
+            analyzer.as_ref(),
+            &job.video_path,
+            &windows,
+            &template,
+            &context,
+            sampling_factor,
+        )
+        .await
+    } else {
+        let request = AnalysisRequest {
+            video_path: job.video_path.clone(),
+            video_duration_seconds,
+            prompt: template.render(&context),
+            sampling_factor,
+        };
+        analyzer.analyze(&request).await
+    };
+
+    // Failed attempts cost tokens too, so usage counts either way
+    let usage = analyzer.usage();
+    if !usage.is_empty() {
+        super::queue::record_usage(job, analyzer.backend(), analyzer.model(), &usage);
+    }
+    let validated = result?;
+
+    let analysis = TimelineAnalysis {
+        segment_id: segment_id.clone(),
+        display_index,
+        analyzed_at: chrono::Utc::now().to_rfc3339(),
+        video_duration_seconds,
+        segment_start_time: job.metadata.start_time.clone(),
+        timeline: validated.timeline,
+        backend: analyzer.backend(),
+        model: analyzer.model().to_string(),
+        prompt_version: template.prompt_version(),
+        quality: Some(validated.quality),
+        speedup: speedup_summary(config, video_duration_seconds),
+        usage: (!usage.is_empty()).then_some(usage),
+        synthetic: false,
+        fusion: None,
+        analysis_version: 1,
+    };
+
+    log::info!(
+        "{:?} analysis complete for segment {} display {} (prompt {}): {} timeline entries, quality {:?}, speed-up {:?}",
+        analyzer.backend(),
+        segment_id,
+        display_index,
+        analysis.prompt_version,
+        analysis.timeline.len(),
+        analysis.quality,
+        analysis.speedup
+    );
+
+    Ok(analysis)
+}
+
+// =============================================================================
+// Analysis Windows
+// =============================================================================
+
+/**
+ * Split a video into equally long windows overlapping by `overlap_seconds`
+ * Returns (start, end) in whole seconds; a single window when the video
+ * fits into `window_seconds` (or splitting is off, window_seconds = 0)
+ */
+fn analysis_windows(video_duration_seconds: f64, window_seconds: u64, overlap_seconds: u64) -> Vec<(u32, u32)> {
+    let duration = video_duration_seconds.round().max(0.0) as u64;
+    if window_seconds == 0 || duration <= window_seconds {
+        return vec![(0, duration as u32)];
+    }
+
+    // Overlap may use at most half a window so every window moves forward
+    let overlap = overlap_seconds.min(window_seconds / 2);
+    let step = window_seconds - overlap;
+    let count = (duration - overlap).div_ceil(step);
+
+    // Spread the video evenly instead of leaving a short last window
+    let length = (duration + (count - 1) * overlap).div_ceil(count);
+    (0..count)
+        .map(|i| {
+            let start = i * (length - overlap);
+            (start as u32, (start + length).min(duration) as u32)
+        })
+        .collect()
+}
+
+/**
+ * Analyze each window of a video and stitch the timelines together
+ * Windows are re-encoded at context.playback_speed and their timelines
+ * rescaled back to real time
+ */
+async fn analyze_windows(
+    analyzer: &dyn VideoAnalyzer,
+    video_path: &Path,
+    windows: &[(u32, u32)],
+    template: &PromptTemplate,
+    context: &PromptContext,
+    sampling_factor: f64,
+) -> Result<ValidatedTimeline, GeminiError> {
+    let speed = context.playback_speed;
+    let work_dir = std::env::temp_dir().join(format!("screenjournal-windows-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&work_dir).map_err(|e| GeminiError::Permanent {
+        message: format!("Failed to create window directory: {}", e),
+    })?;
+
+    let result = async {
+        let mut parts = Vec::with_capacity(windows.len());
+        for (i, &(start, end)) in windows.iter().enumerate() {
+            // The queue paced the first request; pace the others too
+            if i > 0 {
+                super::queue::wait_for_rate_limit().await;
+            }
+
+            let window_path = work_dir.join(format!("window_{:03}.mp4", i));
+            let (source, target) = (video_path.to_path_buf(), window_path.clone());
+            let length = (i + 1 < windows.len()).then_some(end - start);
+            tokio::task::spawn_blocking(move || cut_window(&source, &target, start, length, speed))
+                .await
+                .map_err(|e| GeminiError::Permanent {
+                    message: format!("Window cutting task failed: {}", e),
+                })?
+                .map_err(|e| GeminiError::Permanent { message: e })?;
+
+            log::info!(
+                "[ANALYZER] Analyzing window {}/{} ({} - {}, {}x speed)",
+                i + 1,
+                windows.len(),
+                prompt::format_duration(start as f64),
+                prompt::format_duration(end as f64),
+                speed
+            );
+
+            let window_duration = (end - start) as f64 / speed;
+            let request = AnalysisRequest {
+                video_path: window_path,
+                video_duration_seconds: window_duration,
+                prompt: template.render(&PromptContext {
+                    video_duration_seconds: window_duration,
+                    window_switches: window_slice(&context.window_switches, start as f64, end as f64),
+                    ..context.clone()
+                }),
+                sampling_factor,
+            };
+            let mut timeline = analyzer.analyze(&request).await?;
+            let _ = std::fs::remove_file(&request.video_path);
+
+            if speed > 1.0 {
+                timeline = validation::rescale_timeline(timeline, speed, end - start)
+                    .map_err(|e| GeminiError::Permanent { message: e })?;
+            }
+
+            parts.push(WindowTimeline {
+                start_seconds: start,
+                end_seconds: end,
+                timeline,
+            });
+        }
+
+        validation::stitch_windows(parts).map_err(|e| GeminiError::Permanent { message: e })
+    }
+    .await;
+
+    let _ = std::fs::remove_dir_all(&work_dir);
+    result
+}
+
+/**
+ * Cut a window out of a video (re-encoded so it starts exactly at `start`)
+ * Blocking (runs FFmpeg); call from spawn_blocking
+ *
+ * # Arguments
+ * * `length` - Window length in seconds, None = until the end of the video
+ * * `speed` - Playback speed of the output (1 = real time; frames are
+ *   dropped to keep the source frame rate, so the output is smaller)
+ */
+fn cut_window(
+    video_path: &Path,
+    output_path: &Path,
+    start: u32,
+    length: Option<u32>,
+    speed: f64,
+) -> Result<(), String> {
+    if !video_path.exists() {
+        return Err(format!("Video file not found: {:?}", video_path));
+    }
+
+    let ffmpeg_path = capture::get_ffmpeg_path()?;
+    let mut command = Command::new(&ffmpeg_path);
+    command.args(["-y", "-ss", &start.to_string()]);
+    if let Some(length) = length {
+        // Input option, so the length is in source time even when sped up
+        command.args(["-t", &length.to_string()]);
+    }
+    command.arg("-i").arg(video_path);
+    if speed > 1.0 {
+        command.args(["-vf", &format!("setpts=PTS/{}", speed)]);
+    }
+
+    let status = command
+        .args([
+            "-map", "0:v:0",
+            "-an",
+            "-c:v", "libx264",
+            "-preset", "veryfast",
+            "-crf", "28",
+            "-pix_fmt", "yuv420p",
+            "-movflags", "+faststart",
+        ])
+        .arg(output_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| format!("Failed to run FFmpeg at {:?}: {}", ffmpeg_path, e))?;
+
+    if !status.success() {
+        let _ = std::fs::remove_file(output_path);
+        return Err(format!("FFmpeg exited with error: {:?}", status.code()));
+    }
+
+    Ok(())
+}
+
+// Estimated video tokens of the configured speed-up (None when off)
+fn speedup_summary(config: &GeminiConfig, video_duration_seconds: f64) -> Option<SpeedupSummary> {
+    let factor = config.playback_speed().max(config.sampling_factor());
+    if factor <= 1.0 {
+        return None;
+    }
+
+    let real_time_tokens = video_duration_seconds.max(0.0) * VIDEO_TOKENS_PER_SECOND;
+    let estimated_tokens = real_time_tokens / factor;
+    Some(SpeedupSummary {
+        mode: config.speedup_mode,
+        factor,
+        estimated_tokens: estimated_tokens.round() as u64,
+        estimated_tokens_saved: (real_time_tokens - estimated_tokens).round() as u64,
+    })
+}
+
+// =============================================================================
+// Shared Helpers
+// =============================================================================
+
+// Segment start (RFC 3339) as local wall-clock time for the prompt
+fn local_time(segment_start_time: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(segment_start_time)
+        .map(|t| t.with_timezone(&chrono::Local).format("%A %Y-%m-%d %H:%M (UTC%:z)").to_string())
+        .unwrap_or_else(|_| segment_start_time.to_string())
+}
+
+/// A still frame sampled from a video
+#[derive(Debug, Clone)]
+pub struct SampledFrame {
+    /// Position in the video (seconds)
+    pub offset_seconds: f64,
+
+    /// JPEG bytes, base64 encoded
+    pub jpeg_base64: String,
+}
+
+/**
+ * Sample up to `max_frames` evenly spaced JPEG frames from a video
+ * Blocking (runs FFmpeg); call from spawn_blocking
+ */
+pub fn sample_frames(video_path: &Path, video_duration_seconds: f64, max_frames: u32) -> Result<Vec<SampledFrame>, String> {
+    if !video_path.exists() {
+        return Err(format!("Video file not found: {:?}", video_path));
+    }
+
+    let interval = frame_interval(video_duration_seconds, max_frames);
+    let ffmpeg_path = capture::get_ffmpeg_path()?;
+
+    let work_dir = std::env::temp_dir().join(format!("screenjournal-frames-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&work_dir).map_err(|e| format!("Failed to create frame directory: {}", e))?;
+
+    let result = (|| {
+        let status = Command::new(&ffmpeg_path)
+            .arg("-i")
+            .arg(video_path)
+            .args([
+                "-vf", &format!("fps=1/{:.3},scale='min({},iw)':-2", interval, FRAME_WIDTH),
+                "-frames:v", &max_frames.max(1).to_string(),
+                "-q:v", "4",
+            ])
+            .arg(work_dir.join("frame_%04d.jpg"))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map_err(|e| format!("Failed to run FFmpeg at {:?}: {}", ffmpeg_path, e))?;
+
+        if !status.success() {
+            return Err(format!("FFmpeg exited with error: {:?}", status.code()));
+        }
+
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&work_dir)
+            .map_err(|e| format!("Failed to read frame directory: {}", e))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .collect();
+        paths.sort();
+
+        paths
+            .iter()
+            .enumerate()
+            .map(|(i, path)| {
+                let bytes = std::fs::read(path).map_err(|e| format!("Failed to read frame {:?}: {}", path, e))?;
+                Ok(SampledFrame {
+                    offset_seconds: i as f64 * interval,
+                    jpeg_base64: BASE64.encode(bytes),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()
+    })();
+
+    let _ = std::fs::remove_dir_all(&work_dir);
+
+    let frames = result?;
+    if frames.is_empty() {
+        return Err("No frames could be sampled from the video".to_string());
+    }
+    Ok(frames)
+}
+
+// Frames to sample for a request's sampling factor (at least one)
+pub(crate) fn frame_budget(max_frames: u32, sampling_factor: f64) -> u32 {
+    ((max_frames as f64 / sampling_factor.max(1.0)).ceil() as u32).max(1)
+}
+
+// Seconds between sampled frames so that at most `max_frames` cover the video
+fn frame_interval(video_duration_seconds: f64, max_frames: u32) -> f64 {
+    (video_duration_seconds / max_frames.max(1) as f64).max(1.0)
+}
+
+// Shared HTTP client for backend requests
+pub(crate) fn http_client() -> Result<reqwest::Client, GeminiError> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| GeminiError::Permanent {
+            message: format!("Failed to create HTTP client: {}", e),
+        })
+}
+
+/**
+ * Classify a failed request to a frame-based backend
+ * Unreachable or timed-out servers are retried (local server not started yet)
+ */
+pub(crate) fn classify_request_error(error: reqwest::Error) -> GeminiError {
+    if error.is_connect() || error.is_timeout() {
+        GeminiError::ServiceUnavailable {
+            message: format!("Analyzer request failed: {}", error),
+            retry_after: None,
+        }
+    } else {
+        GeminiError::Permanent {
+            message: format!("Analyzer request failed: {}", error),
+        }
+    }
+}
+
+/**
+ * Classify an HTTP error status for retry handling
+ * 429 = rate limited, 503/500/502/504 = temporarily unavailable, others permanent
+ */
+pub(crate) fn classify_status_error(status_code: u16, message: String, rate_limit_max_wait_seconds: u64) -> GeminiError {
+    match status_code {
+        429 => {
+            let retry_after = super::client::parse_retry_delay_from_error(&message, rate_limit_max_wait_seconds);
+            log::warn!(
+                "[ANALYZER] Rate limited (429). Retry after: {:?}",
+                retry_after.map(|d| format!("{:.1}s", d.as_secs_f64()))
+            );
+            GeminiError::RateLimited { message, retry_after }
+        }
+        503 => {
+            let retry_after = super::client::parse_retry_delay_from_error(&message, rate_limit_max_wait_seconds);
+            log::warn!(
+                "[ANALYZER] Service unavailable (503). Retry after: {:?}",
+                retry_after.map(|d| format!("{:.1}s", d.as_secs_f64()))
+            );
+            GeminiError::ServiceUnavailable { message, retry_after }
+        }
+        500 | 502 | 504 => GeminiError::ServiceUnavailable {
+            message,
+            retry_after: None,
+        },
+        _ => GeminiError::Permanent { message },
+    }
+}
+
+// =============================================================================
+// Test Support
+// =============================================================================
+
+/**
+ * Minimal stand-in HTTP server for backend tests
+ * Accepts one request, replies with `status` and `body`, and returns the
+ * request line and JSON body it received
+ */
+#[cfg(test)]
+pub(crate) fn serve_once(status: u16, body: &'static str) -> (String, std::thread::JoinHandle<(String, serde_json::Value)>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+
+        let mut content_length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            if header.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':')
+                && name.eq_ignore_ascii_case("content-length")
+            {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+
+        let mut request_body = vec![0; content_length];
+        reader.read_exact(&mut request_body).unwrap();
+
+        let mut stream = stream;
+        write!(
+            stream,
+            "HTTP/1.1 {} Stand-in\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+        .unwrap();
+
+        (
+            request_line.trim().to_string(),
+            serde_json::from_slice(&request_body).unwrap_or(serde_json::Value::Null),
+        )
+    });
+
+    (base_url, handle)
+}
+
+#[cfg(test)]
+pub(crate) fn test_frames() -> Vec<SampledFrame> {
+    vec![
+        SampledFrame { offset_seconds: 0.0, jpeg_base64: "AAAA".to_string() },
+        SampledFrame { offset_seconds: 30.0, jpeg_base64: "BBBB".to_string() },
+    ]
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::gemini::types::SpeedupMode;
+
+    #[test]
+    fn test_analysis_windows() {
+        // Short videos and disabled splitting stay whole
+        assert_eq!(analysis_windows(300.0, 1200, 30), vec![(0, 300)]);
+        assert_eq!(analysis_windows(7200.0, 0, 30), vec![(0, 7200)]);
+
+        // 100 minutes in 20-minute windows: 6 windows of 1025s, 30s overlaps
+        let windows = analysis_windows(6000.0, 1200, 30);
+        assert_eq!(windows.len(), 6);
+        assert_eq!(windows[0], (0, 1025));
+        assert_eq!(windows[1], (995, 2020));
+        assert_eq!(windows[5], (4975, 6000));
+        assert!(windows.windows(2).all(|pair| pair[0].1 - pair[1].0 == 30));
+
+        // Overlap is capped at half a window
+        assert_eq!(analysis_windows(100.0, 60, 60), vec![(0, 54), (24, 78), (48, 100)]);
+    }
+
+    #[test]
+    fn test_frame_interval() {
+        assert_eq!(frame_interval(60.0, 12), 5.0);
+        assert_eq!(frame_interval(5.0, 12), 1.0); // Never more than 1 frame per second
+        assert_eq!(frame_interval(60.0, 0), 60.0);
+    }
+
+    #[test]
+    fn test_frame_budget() {
+        assert_eq!(frame_budget(12, 1.0), 12);
+        assert_eq!(frame_budget(12, 5.0), 3);
+        assert_eq!(frame_budget(12, 0.5), 12);
+        assert_eq!(frame_budget(2, 10.0), 1);
+    }
+
+    #[test]
+    fn test_speedup_summary() {
+        let mut config = GeminiConfig::default();
+        assert_eq!(speedup_summary(&config, 600.0), None);
+
+        // 10 minutes at 4x: 154800 tokens at real time, a quarter of that analyzed
+        config.speedup_mode = SpeedupMode::Reencode;
+        let summary = speedup_summary(&config, 600.0).unwrap();
+        assert_eq!(summary.factor, 4.0);
+        assert_eq!(summary.estimated_tokens, 38700);
+        assert_eq!(summary.estimated_tokens_saved, 116100);
+
+        config.speedup_mode = SpeedupMode::ReducedFrameRate;
+        config.speedup_factor = 1.0;
+        assert_eq!(speedup_summary(&config, 600.0), None);
+    }
+
+    #[test]
+    fn test_classify_status_error() {
+        assert!(matches!(
+            classify_status_error(429, "Please retry in 10s".to_string(), 120),
+            GeminiError::RateLimited { retry_after: Some(_), .. }
+        ));
+        assert!(matches!(
+            classify_status_error(502, "bad gateway".to_string(), 120),
+            GeminiError::ServiceUnavailable { .. }
+        ));
+        assert!(matches!(
+            classify_status_error(404, "model not found".to_string(), 120),
+            GeminiError::Permanent { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_validate_with_correction() {
+        let request = AnalysisRequest {
+            video_path: PathBuf::from("segment.mp4"),
+            video_duration_seconds: 60.0,
+            prompt: "Timeline prompt".to_string(),
+            sampling_factor: 1.0,
+        };
+        let valid = r#"{"timeline": [{"startTime": "00:00", "endTime": "01:00", "description": "Editing", "activeApplication": "VS Code", "activeWindowTitle": "main.rs", "productiveScore": 5}]}"#;
+        let invalid = valid.replace("\"productiveScore\": 5", "\"productiveScore\": 9");
+
+        // Rejected reply is sent back once, with the reason
+        let mut corrections = Vec::new();
+        let validated = validate_with_correction(&request, |correction: Option<Correction>| {
+            let reply = if correction.is_none() { invalid.clone() } else { valid.to_string() };
+            corrections.push(correction);
+            async move { Ok(reply) }
+        })
+        .await
+        .unwrap();
+
+        assert!(validated.quality.corrected);
+        assert_eq!(corrections.len(), 2);
+        let correction = corrections[1].as_ref().unwrap();
+        assert_eq!(correction.previous_response, invalid);
+        assert!(correction.prompt.contains("productiveScore 9"));
+
+        // A second rejection fails the job
+        let result = validate_with_correction(&request, |_| async { Ok("not json".to_string()) }).await;
+        assert!(matches!(result, Err(GeminiError::Permanent { .. })));
+    }
+
+    #[test]
+    fn test_create_analyzer_validates_endpoint() {
+        let mut config = GeminiConfig::default();
+        let mut target = ModelTarget {
+            backend: AnalyzerBackend::Local,
+            model: None,
+            base_url: None,
+        };
+        assert!(is_configured(&config, &target, None));
+        assert_eq!(create_analyzer(&config, &target, None).unwrap().backend(), AnalyzerBackend::Local);
+
+        config.local.base_url = "localhost:11434".to_string();
+        assert!(!is_configured(&config, &target, None));
+        assert!(create_analyzer(&config, &target, None).is_err());
+
+        // Target endpoint overrides the configured one
+        target.base_url = Some("http://gpu-box:11434".to_string());
+        target.model = Some("llava:13b".to_string());
+        assert!(is_configured(&config, &target, None));
+        assert_eq!(create_analyzer(&config, &target, None).unwrap().model(), "llava:13b");
+    }
+}
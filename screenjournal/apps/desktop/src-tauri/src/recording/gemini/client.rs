@@ -6,37 +6,46 @@
  * PURPOSE: HTTP client for Google Gemini AI video analysis
  * 
  * API ENDPOINT:
- * POST https://generativelanguage.googleapis.com/v1beta/models/gemini-2.5-flash-lite:generateContent
+ * POST https://generativelanguage.googleapis.com/v1beta/models/{model}:generateContent
+ * (default model: gemini-2.5-flash-lite)
  * 
  * REQUEST FLOW:
- * 1. Read video file as bytes
- * 2. Base64 encode video data
- * 3. Build request with prompt and inline video
- * 4. Send to Gemini API with thinking budget config
- * 5. Parse JSON timeline response
+ * 1. Small videos: read and base64 encode inline
+ *    Videos above files_api_threshold_mb: resumable upload via the Files API
+ *    (files.rs), referenced by file URI
+ * 2. Build request with prompt and video part (reduced frame rate via
+ *    videoMetadata when speedup_mode is reduced_frame_rate)
+ * 3. Send to Gemini API with thinking budget config and the timeline JSON
+ *    response schema
+ * 4. Parse and validate the timeline (one self-correction turn if rejected);
+ *    usageMetadata of every response is summed for the job's token usage
+ * 5. Delete the uploaded file (if any)
  * 
  * ============================================================================
  */
 
 use crate::recording::gemini::{
-    prompt::build_timeline_prompt,
-    types::{GeminiConfig, GeminiError, GeminiTimelineResponse, TimelineAnalysis, TimelineEntry},
+    analyzer::{
+        classify_status_error, http_client, validate_with_correction, AnalysisRequest, Correction, VideoAnalyzer,
+    },
+    files::FileUploader,
+    types::{AnalyzerBackend, GeminiError, GeminiTimelineResponse, TimelineEntry, TokenUsage},
+    validation::ValidatedTimeline,
 };
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use futures_util::future::{BoxFuture, FutureExt};
 use regex::Regex;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use serde_json::json;
 use std::path::Path;
+use std::sync::Mutex;
 use std::time::Duration;
 
 /// Gemini API base URL
-const GEMINI_API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta/models";
+pub const GEMINI_API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta/models";
 
-/// Model to use for video analysis
-const GEMINI_MODEL: &str = "gemini-2.5-flash-lite";
-
-/// Request timeout (video upload can take time)
-const REQUEST_TIMEOUT_SECS: u64 = 300; // 5 minutes
+/// Model used when a model target doesn't name one
+pub const DEFAULT_GEMINI_MODEL: &str = "gemini-2.5-flash-lite";
 
 // =============================================================================
 // API Request/Response Structures
@@ -51,27 +60,52 @@ struct GeminiRequest {
 
 #[derive(Debug, Serialize)]
 struct Content {
+    role: &'static str,
     parts: Vec<Part>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(untagged)]
 enum Part {
     Text { text: String },
-    InlineData { inline_data: InlineData },
+    InlineData {
+        inline_data: InlineData,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        video_metadata: Option<VideoMetadata>,
+    },
+    FileData {
+        file_data: FileData,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        video_metadata: Option<VideoMetadata>,
+    },
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct InlineData {
     mime_type: String,
     data: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FileData {
+    mime_type: String,
+    file_uri: String,
+}
+
+/// Video sampling options (Gemini samples 1 frame per second by default)
+#[derive(Debug, Clone, Serialize)]
+struct VideoMetadata {
+    fps: f64,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct GenerationConfig {
     thinking_config: ThinkingConfig,
+    response_mime_type: &'static str,
+    response_schema: serde_json::Value,
 }
 
 #[derive(Debug, Serialize)]
@@ -81,9 +115,49 @@ struct ThinkingConfig {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct GeminiApiResponse {
     candidates: Option<Vec<Candidate>>,
     error: Option<GeminiApiErrorBody>,
+    usage_metadata: Option<UsageMetadata>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UsageMetadata {
+    #[serde(default)]
+    prompt_token_count: u64,
+    #[serde(default)]
+    candidates_token_count: u64,
+    #[serde(default)]
+    thoughts_token_count: u64,
+    #[serde(default)]
+    prompt_tokens_details: Vec<ModalityTokenCount>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModalityTokenCount {
+    modality: String,
+    #[serde(default)]
+    token_count: u64,
+}
+
+impl From<UsageMetadata> for TokenUsage {
+    // Video (and its audio track) split from the prompt; thinking is billed as output
+    fn from(metadata: UsageMetadata) -> Self {
+        let video_tokens: u64 = metadata
+            .prompt_tokens_details
+            .iter()
+            .filter(|detail| matches!(detail.modality.as_str(), "VIDEO" | "AUDIO"))
+            .map(|detail| detail.token_count)
+            .sum();
+        TokenUsage {
+            prompt_tokens: metadata.prompt_token_count.saturating_sub(video_tokens),
+            video_tokens,
+            output_tokens: metadata.candidates_token_count + metadata.thoughts_token_count,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -109,115 +183,217 @@ struct GeminiApiErrorBody {
 }
 
 // =============================================================================
-// Public API
+// Analyzer
 // =============================================================================
 
 /**
- * Analyze a video file using Gemini AI
- * 
- * # Arguments
- * * `video_path` - Path to the MP4 video file
- * * `segment_id` - Segment identifier for the result
- * * `display_index` - Display index for multi-monitor setups
- * * `video_duration_seconds` - Duration of the video
- * * `segment_start_time` - ISO 8601 timestamp when segment started
- * * `config` - Gemini configuration
- * 
- * # Returns
- * * `Ok(TimelineAnalysis)` on success
- * * `Err(GeminiError)` with classified error for retry handling
+ * Gemini backend: sends the video inline, or uploads it first when large
  */
-pub async fn analyze_video(
-    video_path: &Path,
-    segment_id: &str,
-    display_index: u32,
-    video_duration_seconds: f64,
-    segment_start_time: &str,
-    config: &GeminiConfig,
-    app: Option<&tauri::AppHandle>,
-) -> Result<TimelineAnalysis, GeminiError> {
-    log::info!(
-        "Starting Gemini analysis for segment {} display {} ({:.1}s video)",
-        segment_id,
-        display_index,
-        video_duration_seconds
-    );
-
-    // Get API key (check user-provided key first if AppHandle is available)
-    let api_key = if let Some(app_handle) = app {
-        super::get_api_key_with_app(app_handle).map_err(|e| GeminiError::Permanent {
-            message: e,
-        })?
-    } else {
-        super::get_api_key().map_err(|e| GeminiError::Permanent {
+pub struct GeminiAnalyzer {
+    api_key: String,
+    api_base: String,
+    model: String,
+    thinking_budget: u32,
+    rate_limit_max_wait_seconds: u64,
+    files_api_threshold_bytes: u64,
+    usage: Mutex<TokenUsage>,
+}
+
+impl GeminiAnalyzer {
+    pub fn new(
+        api_key: String,
+        api_base: String,
+        model: String,
+        thinking_budget: u32,
+        rate_limit_max_wait_seconds: u64,
+        files_api_threshold_bytes: u64,
+    ) -> Self {
+        Self {
+            api_key,
+            api_base,
+            model,
+            thinking_budget,
+            rate_limit_max_wait_seconds,
+            files_api_threshold_bytes,
+            usage: Mutex::new(TokenUsage::default()),
+        }
+    }
+
+    async fn analyze_video(&self, request: &AnalysisRequest) -> Result<ValidatedTimeline, GeminiError> {
+        let size = std::fs::metadata(&request.video_path)
+            .map_err(|_| GeminiError::Permanent {
+                message: format!("Video file not found: {:?}", request.video_path),
+            })?
+            .len();
+
+        if size > self.files_api_threshold_bytes {
+            self.analyze_uploaded(request).await
+        } else {
+            self.analyze_inline(request).await
+        }
+    }
+
+    // Upload via the Files API, analyze by file URI, then delete the upload
+    async fn analyze_uploaded(&self, request: &AnalysisRequest) -> Result<ValidatedTimeline, GeminiError> {
+        let uploader = FileUploader::new(&self.api_key, &self.api_base, self.rate_limit_max_wait_seconds);
+        let file = uploader.upload(&request.video_path, "video/mp4").await?;
+
+        let video_part = Part::FileData {
+            file_data: FileData {
+                mime_type: "video/mp4".to_string(),
+                file_uri: file.uri.clone(),
+            },
+            video_metadata: video_metadata(request),
+        };
+        let result = self.generate_validated(request, &video_part).await;
+
+        // Uploads expire after 48 hours anyway, so a failed delete isn't fatal
+        if let Err(e) = uploader.delete(&file.name).await {
+            log::warn!("[GEMINI] Failed to delete uploaded file {}: {:?}", file.name, e);
+        }
+
+        result
+    }
+
+    async fn analyze_inline(&self, request: &AnalysisRequest) -> Result<ValidatedTimeline, GeminiError> {
+        // Read and encode video file
+        let video_data = read_and_encode_video(&request.video_path).map_err(|e| GeminiError::Permanent {
             message: e,
-        })?
-    };
+        })?;
+        log::info!(
+            "Video encoded: {} bytes base64 (from {:?})",
+            video_data.len(),
+            request.video_path
+        );
 
-    // Read and encode video file
-    let video_data = read_and_encode_video(video_path).map_err(|e| GeminiError::Permanent {
-        message: e,
-    })?;
-    log::info!(
-        "Video encoded: {} bytes base64 (from {:?})",
-        video_data.len(),
-        video_path
-    );
-
-    // Build the prompt
-    let prompt = build_timeline_prompt(1.0, video_duration_seconds);
-
-    // Build request
-    let request = GeminiRequest {
-        contents: vec![Content {
-            parts: vec![
-                Part::Text { text: prompt },
-                Part::InlineData {
-                    inline_data: InlineData {
-                        mime_type: "video/mp4".to_string(),
-                        data: video_data,
-                    },
+        let video_part = Part::InlineData {
+            inline_data: InlineData {
+                mime_type: "video/mp4".to_string(),
+                data: video_data,
+            },
+            video_metadata: video_metadata(request),
+        };
+        self.generate_validated(request, &video_part).await
+    }
+
+    // Generate and validate the timeline, with one self-correction turn
+    async fn generate_validated(&self, request: &AnalysisRequest, video_part: &Part) -> Result<ValidatedTimeline, GeminiError> {
+        validate_with_correction(request, move |correction| self.generate(request, video_part, correction)).await
+    }
+
+    // Send the timeline prompt with the video part to generateContent
+    // A correction continues the conversation after the rejected reply
+    async fn generate(
+        &self,
+        request: &AnalysisRequest,
+        video_part: &Part,
+        correction: Option<Correction>,
+    ) -> Result<String, GeminiError> {
+        let mut contents = vec![Content {
+            role: "user",
+            parts: vec![Part::Text { text: request.prompt.clone() }, video_part.clone()],
+        }];
+        if let Some(correction) = correction {
+            contents.push(Content {
+                role: "model",
+                parts: vec![Part::Text { text: correction.previous_response }],
+            });
+            contents.push(Content {
+                role: "user",
+                parts: vec![Part::Text { text: correction.prompt }],
+            });
+        }
+
+        // Build request
+        let gemini_request = GeminiRequest {
+            contents,
+            generation_config: GenerationConfig {
+                thinking_config: ThinkingConfig {
+                    thinking_budget: self.thinking_budget,
                 },
-            ],
-        }],
-        generation_config: GenerationConfig {
-            thinking_config: ThinkingConfig {
-                thinking_budget: config.thinking_budget,
+                response_mime_type: "application/json",
+                response_schema: timeline_response_schema(),
             },
-        },
-    };
+        };
 
-    // Send request to Gemini
-    let timeline_entries = send_gemini_request(
-        &api_key, 
-        request, 
-        config.rate_limit_max_wait_seconds
-    ).await?;
-
-    // Build analysis result
-    let analysis = TimelineAnalysis {
-        segment_id: segment_id.to_string(),
-        display_index,
-        analyzed_at: chrono::Utc::now().to_rfc3339(),
-        video_duration_seconds,
-        segment_start_time: segment_start_time.to_string(),
-        timeline: timeline_entries,
-    };
+        // Send request to Gemini
+        let url = format!(
+            "{}/{}:generateContent?key={}",
+            self.api_base.trim_end_matches('/'), self.model, self.api_key
+        );
+        let (text, usage) = send_gemini_request(&url, &self.model, gemini_request, self.rate_limit_max_wait_seconds).await?;
+        self.usage.lock().unwrap().add(&usage);
+        Ok(text)
+    }
+}
 
-    log::info!(
-        "Gemini analysis complete for segment {} display {}: {} timeline entries",
-        segment_id,
-        display_index,
-        analysis.timeline.len()
-    );
+impl VideoAnalyzer for GeminiAnalyzer {
+    fn backend(&self) -> AnalyzerBackend {
+        AnalyzerBackend::Gemini
+    }
 
-    Ok(analysis)
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn analyze<'a>(&'a self, request: &'a AnalysisRequest) -> BoxFuture<'a, Result<ValidatedTimeline, GeminiError>> {
+        self.analyze_video(request).boxed()
+    }
+
+    fn usage(&self) -> TokenUsage {
+        *self.usage.lock().unwrap()
+    }
 }
 
 // =============================================================================
 // Internal Functions
 // =============================================================================
 
+// Reduced sampling rate for the request's sampling factor (None = default 1 fps)
+fn video_metadata(request: &AnalysisRequest) -> Option<VideoMetadata> {
+    (request.sampling_factor > 1.0).then(|| VideoMetadata {
+        fps: 1.0 / request.sampling_factor,
+    })
+}
+
+/**
+ * Response schema for the timeline JSON (Gemini's OpenAPI schema subset)
+ * Constrains decoding so replies are well-formed JSON with every field
+ */
+fn timeline_response_schema() -> serde_json::Value {
+    let fields = [
+        "startTime",
+        "endTime",
+        "description",
+        "activeApplication",
+        "activeWindowTitle",
+        "productiveScore",
+    ];
+
+    json!({
+        "type": "OBJECT",
+        "properties": {
+            "timeline": {
+                "type": "ARRAY",
+                "items": {
+                    "type": "OBJECT",
+                    "properties": {
+                        "startTime": {"type": "STRING", "description": "MM:SS, or HH:MM:SS from one hour"},
+                        "endTime": {"type": "STRING", "description": "MM:SS, or HH:MM:SS from one hour"},
+                        "description": {"type": "STRING"},
+                        "activeApplication": {"type": "STRING"},
+                        "activeWindowTitle": {"type": "STRING"},
+                        "productiveScore": {"type": "INTEGER", "minimum": 1, "maximum": 5},
+                    },
+                    "required": fields,
+                    "propertyOrdering": fields,
+                },
+            },
+        },
+        "required": ["timeline"],
+    })
+}
+
 /**
  * Parse retry delay from Gemini error message
  * Extracts duration from "Please retry in X.XXXs" pattern
@@ -273,30 +449,21 @@ fn read_and_encode_video(video_path: &Path) -> Result<String, String> {
 }
 
 /**
- * Send request to Gemini API and parse response
+ * Send request to Gemini API and return the reply text and its token usage
  * Returns classified GeminiError for intelligent retry handling
  */
 async fn send_gemini_request(
-    api_key: &str,
+    url: &str,
+    model: &str,
     request: GeminiRequest,
     rate_limit_max_wait_seconds: u64,
-) -> Result<Vec<TimelineEntry>, GeminiError> {
-    let url = format!(
-        "{}/{}:generateContent?key={}",
-        GEMINI_API_BASE, GEMINI_MODEL, api_key
-    );
-
-    let client = Client::builder()
-        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
-        .build()
-        .map_err(|e| GeminiError::Permanent {
-            message: format!("Failed to create HTTP client: {}", e),
-        })?;
+) -> Result<(String, TokenUsage), GeminiError> {
+    let client = http_client()?;
 
-    log::info!("[GEMINI] Sending request to Gemini API (model: {})...", GEMINI_MODEL);
+    log::info!("[GEMINI] Sending request to Gemini API (model: {})...", model);
 
     let response = client
-        .post(&url)
+        .post(url)
         .header("Content-Type", "application/json")
         .json(&request)
         .send()
@@ -331,45 +498,7 @@ async fn send_gemini_request(
         };
 
         // Classify error based on HTTP status code
-        return match status_code {
-            429 => {
-                // Rate limited - parse retry delay from message
-                let retry_after = parse_retry_delay_from_error(&error_message, rate_limit_max_wait_seconds);
-                log::warn!(
-                    "[GEMINI] Rate limited (429). Retry after: {:?}",
-                    retry_after.map(|d| format!("{:.1}s", d.as_secs_f64()))
-                );
-                Err(GeminiError::RateLimited {
-                    message: error_message,
-                    retry_after,
-                })
-            }
-            503 => {
-                // Service unavailable - model overloaded
-                let retry_after = parse_retry_delay_from_error(&error_message, rate_limit_max_wait_seconds);
-                log::warn!(
-                    "[GEMINI] Service unavailable (503). Retry after: {:?}",
-                    retry_after.map(|d| format!("{:.1}s", d.as_secs_f64()))
-                );
-                Err(GeminiError::ServiceUnavailable {
-                    message: error_message,
-                    retry_after,
-                })
-            }
-            500 | 502 | 504 => {
-                // Server errors - may be temporary
-                Err(GeminiError::ServiceUnavailable {
-                    message: error_message,
-                    retry_after: None,
-                })
-            }
-            _ => {
-                // All other errors are permanent (4xx client errors, etc.)
-                Err(GeminiError::Permanent {
-                    message: error_message,
-                })
-            }
-        };
+        return Err(classify_status_error(status_code, error_message, rate_limit_max_wait_seconds));
     }
 
     // Parse response
@@ -378,6 +507,9 @@ async fn send_gemini_request(
             message: format!("Failed to parse Gemini response: {}", e),
         })?;
 
+    let usage = TokenUsage::from(gemini_response.usage_metadata.unwrap_or_default());
+    log::info!("[GEMINI] Token usage: {:?}", usage);
+
     // Extract text from response
     let text = gemini_response
         .candidates
@@ -387,17 +519,14 @@ async fn send_gemini_request(
         .ok_or_else(|| GeminiError::Permanent {
             message: "No text content in Gemini response".to_string(),
         })?;
-
-    // Parse timeline JSON from text
-    log::info!("[GEMINI] Parsing timeline from response text ({} chars)", text.len());
-    parse_timeline_json(&text).map_err(|e| GeminiError::Permanent { message: e })
+    Ok((text, usage))
 }
 
 /**
  * Parse timeline JSON from Gemini response text
  * Handles both clean JSON and JSON with markdown code blocks
  */
-fn parse_timeline_json(text: &str) -> Result<Vec<TimelineEntry>, String> {
+pub(crate) fn parse_timeline_json(text: &str) -> Result<Vec<TimelineEntry>, String> {
     // Try direct JSON parse first
     if let Ok(response) = serde_json::from_str::<GeminiTimelineResponse>(text) {
         log_timeline_entries(&response.timeline);
@@ -506,6 +635,65 @@ mod tests {
         assert_eq!(entries[0].productive_score, 3);
     }
 
+    #[test]
+    fn test_video_part_sampling_rate() {
+        let mut request = AnalysisRequest {
+            video_path: std::path::PathBuf::from("segment.mp4"),
+            video_duration_seconds: 60.0,
+            prompt: String::new(),
+            sampling_factor: 1.0,
+        };
+        let part = |request: &AnalysisRequest| {
+            serde_json::to_value(Part::FileData {
+                file_data: FileData {
+                    mime_type: "video/mp4".to_string(),
+                    file_uri: "files/abc".to_string(),
+                },
+                video_metadata: video_metadata(request),
+            })
+            .unwrap()
+        };
+
+        assert!(part(&request).get("video_metadata").is_none());
+
+        request.sampling_factor = 4.0;
+        assert_eq!(part(&request)["video_metadata"]["fps"], 0.25);
+    }
+
+    #[test]
+    fn test_parse_usage_metadata() {
+        let response: GeminiApiResponse = serde_json::from_str(
+            r#"{
+                "candidates": [],
+                "usageMetadata": {
+                    "promptTokenCount": 78000,
+                    "candidatesTokenCount": 900,
+                    "thoughtsTokenCount": 600,
+                    "totalTokenCount": 79500,
+                    "promptTokensDetails": [
+                        {"modality": "TEXT", "tokenCount": 600},
+                        {"modality": "VIDEO", "tokenCount": 77400}
+                    ]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let usage = TokenUsage::from(response.usage_metadata.unwrap());
+        assert_eq!(
+            usage,
+            TokenUsage {
+                prompt_tokens: 600,
+                video_tokens: 77400,
+                output_tokens: 1500,
+            }
+        );
+
+        // Responses without usage count as none
+        let response: GeminiApiResponse = serde_json::from_str(r#"{"candidates": []}"#).unwrap();
+        assert!(TokenUsage::from(response.usage_metadata.unwrap_or_default()).is_empty());
+    }
+
     #[test]
     fn test_parse_retry_delay_with_decimal() {
         let message = "You exceeded your current quota. Please retry in 58.731525401s.";
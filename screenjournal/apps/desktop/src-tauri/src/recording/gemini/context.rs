@@ -0,0 +1,288 @@
+/**
+ * ============================================================================
+ * WINDOW CONTEXT MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Give the model the exact app and window title switches of a segment
+ *
+ * SWITCHES:
+ * ActivityWatch window events overlapping the segment become a list of
+ * (offset, app, title) switches relative to the segment start, rendered into
+ * the prompt by the {{window_context}} template variable. Models read small
+ * window titles poorly; the switch list lets them focus on describing what
+ * was done.
+ *
+ * COMPACTING:
+ * - Windows focused for less than MIN_SWITCH_SECONDS are dropped (alt-tab
+ *   flicker), consecutive events of the same window are merged
+ * - Titles are truncated to MAX_TITLE_CHARS
+ * - At most MAX_SWITCHES switches are kept per segment
+ *
+ * PRIVACY:
+ * Excluded apps/titles (PrivacyConfig) appear as [REDACTED], redaction
+ * patterns are applied to app names and titles.
+ *
+ * The same focused-window spans feed timeline fusion (fusion.rs).
+ *
+ * ============================================================================
+ */
+
+use crate::activitywatch::types::EventInfo;
+use crate::recording::gemini::{idle::segment_range, types::GeminiJob};
+use crate::recording::privacy::{PrivacyRules, REDACTED};
+use chrono::{DateTime, Utc};
+
+/// Windows focused for a shorter time are left out
+const MIN_SWITCH_SECONDS: f64 = 2.0;
+
+/// Longer window titles are truncated
+const MAX_TITLE_CHARS: usize = 80;
+
+/// Switches kept per segment; later ones are left out
+const MAX_SWITCHES: usize = 60;
+
+/// Time a window was focused, seconds from the segment start
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowSpan {
+    pub start_seconds: f64,
+    pub end_seconds: f64,
+    /// App name ([REDACTED] for private windows)
+    pub app: String,
+    /// Redacted window title ([REDACTED] for private windows)
+    pub title: String,
+    /// Excluded by the privacy rules
+    pub private: bool,
+}
+
+/// Focus change to a window, `offset_seconds` after the segment start
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowSwitch {
+    pub offset_seconds: f64,
+    pub app: String,
+    pub title: String,
+}
+
+/**
+ * Fetch the focused windows within a job's segment
+ * Empty when ActivityWatch isn't running or the privacy rules don't compile
+ */
+pub async fn fetch_window_spans(job: &GeminiJob) -> Vec<WindowSpan> {
+    let Some(base_url) = crate::activitywatch::manager::current_base_url() else {
+        return Vec::new();
+    };
+    let Some((start, end)) = segment_range(job) else {
+        return Vec::new();
+    };
+
+    // Privacy rules can't be applied, so no window data may be used
+    let rules = match PrivacyRules::from_config(&crate::recording::manager::current_config().privacy) {
+        Ok(rules) => rules,
+        Err(e) => {
+            log::warn!("[GEMINI-CONTEXT] Leaving out window data: {}", e);
+            return Vec::new();
+        }
+    };
+
+    match crate::activitywatch::client::fetch_events_by_range(&base_url, start, end).await {
+        Ok(events) => window_spans(&events.window_events, start, end, &rules),
+        Err(e) => {
+            log::warn!("[GEMINI-CONTEXT] Could not fetch window events: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/**
+ * Clip window events to [start, end] and merge consecutive events of the
+ * same window
+ *
+ * # Arguments
+ * * `events` - ActivityWatch window events (any order)
+ * * `start`, `end` - Segment range
+ * * `rules` - Privacy rules applied to app names and titles
+ */
+pub fn window_spans(
+    events: &[EventInfo],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    rules: &PrivacyRules,
+) -> Vec<WindowSpan> {
+    let seconds_from_start = |at: DateTime<Utc>| (at - start).num_milliseconds() as f64 / 1000.0;
+
+    let mut clipped: Vec<(f64, f64, &str, &str)> = events
+        .iter()
+        .filter_map(|event| {
+            let event_start = DateTime::parse_from_rfc3339(&event.timestamp).ok()?.with_timezone(&Utc);
+            let event_end = event_start + chrono::Duration::milliseconds((event.duration.max(0.0) * 1000.0) as i64);
+            let (from, to) = (seconds_from_start(event_start.max(start)), seconds_from_start(event_end.min(end)));
+            if to - from < MIN_SWITCH_SECONDS {
+                return None;
+            }
+
+            let app = event.data.get("app").and_then(|v| v.as_str()).unwrap_or("");
+            let title = event.data.get("title").and_then(|v| v.as_str()).unwrap_or("");
+            Some((from, to, app, title))
+        })
+        .collect();
+    clipped.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut spans: Vec<WindowSpan> = Vec::new();
+    for (from, to, app, title) in clipped {
+        let private = rules.is_excluded(app, title);
+        let (app, title) = if private {
+            (REDACTED.to_string(), REDACTED.to_string())
+        } else {
+            (rules.redact(app), rules.redact(title))
+        };
+
+        match spans.last_mut() {
+            Some(last) if last.app == app && last.title == title => last.end_seconds = last.end_seconds.max(to),
+            _ => spans.push(WindowSpan {
+                start_seconds: from,
+                end_seconds: to,
+                app,
+                title,
+                private,
+            }),
+        }
+    }
+    spans
+}
+
+/**
+ * Compact focused windows into prompt switches: titles truncated, at most
+ * MAX_SWITCHES kept
+ */
+pub fn window_switches(spans: &[WindowSpan]) -> Vec<WindowSwitch> {
+    if spans.len() > MAX_SWITCHES {
+        log::debug!("[GEMINI-CONTEXT] Keeping {} of {} window switches", MAX_SWITCHES, spans.len());
+    }
+
+    spans
+        .iter()
+        .take(MAX_SWITCHES)
+        .map(|span| WindowSwitch {
+            offset_seconds: span.start_seconds,
+            app: span.app.clone(),
+            title: truncate(&span.title, MAX_TITLE_CHARS),
+        })
+        .collect()
+}
+
+/**
+ * Switches within the window [start, end) of a video, offsets relative to
+ * the window start
+ * The window focused when the window starts is included at offset 0
+ */
+pub fn window_slice(switches: &[WindowSwitch], start_seconds: f64, end_seconds: f64) -> Vec<WindowSwitch> {
+    let first = switches
+        .iter()
+        .rposition(|s| s.offset_seconds <= start_seconds)
+        .unwrap_or(0);
+
+    switches[first..]
+        .iter()
+        .take_while(|s| s.offset_seconds < end_seconds)
+        .map(|s| WindowSwitch {
+            offset_seconds: (s.offset_seconds - start_seconds).max(0.0),
+            ..s.clone()
+        })
+        .collect()
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((index, _)) => format!("{}…", &text[..index]),
+        None => text.to_string(),
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::types::PrivacyConfig;
+    use serde_json::json;
+
+    fn window_event(timestamp: &str, duration: f64, app: &str, title: &str) -> EventInfo {
+        EventInfo {
+            id: None,
+            timestamp: timestamp.to_string(),
+            duration,
+            data: json!({ "app": app, "title": title }),
+        }
+    }
+
+    fn switch(offset_seconds: f64, app: &str, title: &str) -> WindowSwitch {
+        WindowSwitch {
+            offset_seconds,
+            app: app.to_string(),
+            title: title.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_window_switches() {
+        let rules = PrivacyRules::from_config(&PrivacyConfig {
+            excluded_apps: vec!["1Password".to_string()],
+            excluded_title_patterns: Vec::new(),
+            redact_patterns: vec![r"TICKET-\d+".to_string()],
+        })
+        .unwrap();
+        let start = "2025-01-15T10:00:00Z".parse().unwrap();
+        let end = "2025-01-15T10:05:00Z".parse().unwrap();
+
+        let events = vec![
+            window_event("2025-01-15T10:01:00Z", 1.0, "Slack", "general"),
+            // Started before the segment
+            window_event("2025-01-15T09:58:00Z", 180.0, "Code", "main.rs"),
+            window_event("2025-01-15T10:01:01Z", 30.0, "Code", "main.rs"),
+            window_event("2025-01-15T10:02:00Z", 20.0, "Firefox", "TICKET-42 - Jira"),
+            window_event("2025-01-15T10:03:00Z", 60.0, "1Password", "Vault"),
+            // Ends past the segment, only 1s inside
+            window_event("2025-01-15T10:04:59Z", 60.0, "Mail", "Inbox"),
+        ];
+
+        let spans = window_spans(&events, start, end, &rules);
+        assert_eq!(
+            spans.iter().map(|s| (s.end_seconds, s.private)).collect::<Vec<_>>(),
+            vec![(91.0, false), (140.0, false), (240.0, true)]
+        );
+        assert_eq!(
+            window_switches(&spans),
+            vec![
+                switch(0.0, "Code", "main.rs"),
+                switch(120.0, "Firefox", "[REDACTED] - Jira"),
+                switch(180.0, REDACTED, REDACTED),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_window_slice() {
+        let switches = vec![
+            switch(0.0, "Code", "main.rs"),
+            switch(100.0, "Firefox", "Docs"),
+            switch(250.0, "Slack", "general"),
+        ];
+
+        assert_eq!(
+            window_slice(&switches, 120.0, 240.0),
+            vec![switch(0.0, "Firefox", "Docs")]
+        );
+        assert_eq!(
+            window_slice(&switches, 0.0, 300.0),
+            switches
+        );
+        assert!(window_slice(&[], 0.0, 300.0).is_empty());
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("main.rs", 10), "main.rs");
+        assert_eq!(truncate("äöüäöü", 3), "äöü…");
+    }
+}
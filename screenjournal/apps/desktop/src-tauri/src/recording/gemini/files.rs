@@ -0,0 +1,613 @@
+/**
+ * ============================================================================
+ * GEMINI FILES API MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Resumable upload of large videos to the Gemini Files API
+ *
+ * API ENDPOINTS:
+ * POST   {host}/upload/v1beta/files          (start resumable session)
+ * POST   {session_url}                       (upload / query / finalize)
+ * GET    {host}/v1beta/files/{id}            (poll processing state)
+ * DELETE {host}/v1beta/files/{id}            (cleanup after analysis)
+ *
+ * UPLOAD FLOW:
+ * 1. Start a session announcing size and MIME type -> session URL
+ * 2. Send the file in chunks (multiples of 256 KiB) read straight from disk
+ * 3. On a dropped connection, ask the session how many bytes it has and
+ *    continue from there
+ * 4. The finalizing chunk returns the file resource; videos are processed
+ *    before use, so poll until the state is ACTIVE
+ *
+ * The analyzer references the file by URI in generateContent and deletes
+ * it afterwards (uploads would otherwise linger for 48 hours).
+ *
+ * ============================================================================
+ */
+
+use crate::recording::gemini::{
+    analyzer::{classify_status_error, http_client, REQUEST_TIMEOUT_SECS},
+    types::GeminiError,
+};
+use serde::Deserialize;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::time::Duration;
+
+/// Bytes per upload request (the API requires multiples of 256 KiB)
+const UPLOAD_CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Consecutive failed attempts at one offset before giving up
+const MAX_RESUME_ATTEMPTS: u32 = 5;
+
+/// First delay before resuming a failed chunk, doubles per attempt
+const RESUME_BASE_DELAY_SECONDS: u64 = 1;
+
+/// Seconds between processing state polls
+const PROCESSING_POLL_SECONDS: u64 = 2;
+
+/// File resource returned by the Files API
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadedFile {
+    /// Resource name ("files/abc123"), used for polling and deletion
+    pub name: String,
+
+    /// URI to reference the file in generateContent
+    pub uri: String,
+
+    #[serde(default)]
+    pub mime_type: String,
+
+    /// PROCESSING, ACTIVE or FAILED
+    #[serde(default)]
+    pub state: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileEnvelope {
+    file: UploadedFile,
+}
+
+/**
+ * Client for the Gemini Files API
+ */
+pub struct FileUploader {
+    api_key: String,
+    version_root: String,
+    upload_url: String,
+    chunk_size: u64,
+    resume_delay: Duration,
+    poll_interval: Duration,
+    rate_limit_max_wait_seconds: u64,
+}
+
+impl FileUploader {
+    /**
+     * Create an uploader for the API behind a generateContent base URL
+     *
+     * # Arguments
+     * * `api_key` - Gemini API key
+     * * `api_base` - Models base URL (".../v1beta/models"), as used by the analyzer
+     */
+    pub fn new(api_key: &str, api_base: &str, rate_limit_max_wait_seconds: u64) -> Self {
+        let (version_root, upload_url) = files_endpoints(api_base);
+        Self {
+            api_key: api_key.to_string(),
+            version_root,
+            upload_url,
+            chunk_size: UPLOAD_CHUNK_SIZE,
+            resume_delay: Duration::from_secs(RESUME_BASE_DELAY_SECONDS),
+            poll_interval: Duration::from_secs(PROCESSING_POLL_SECONDS),
+            rate_limit_max_wait_seconds,
+        }
+    }
+
+    /**
+     * Upload a file and wait until it can be used in requests
+     *
+     * # Returns
+     * * The ACTIVE file resource
+     */
+    pub async fn upload(&self, path: &Path, mime_type: &str) -> Result<UploadedFile, GeminiError> {
+        let size = std::fs::metadata(path)
+            .map_err(|e| GeminiError::Permanent {
+                message: format!("Failed to read video file {:?}: {}", path, e),
+            })?
+            .len();
+
+        let display_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "video".to_string());
+
+        let session_url = self.start_session(size, mime_type, &display_name).await?;
+        log::info!(
+            "[GEMINI-FILES] Uploading {} ({:.1} MB) in {} KiB chunks",
+            display_name,
+            size as f64 / 1_000_000.0,
+            self.chunk_size / 1024
+        );
+
+        let file = self.upload_chunks(&session_url, path, size).await?;
+        log::info!("[GEMINI-FILES] Uploaded {} as {}", display_name, file.name);
+
+        self.wait_until_active(file).await
+    }
+
+    /**
+     * Delete an uploaded file
+     */
+    pub async fn delete(&self, name: &str) -> Result<(), GeminiError> {
+        let url = format!("{}/{}?key={}", self.version_root, name, self.api_key);
+        let response = http_client()?
+            .delete(&url)
+            .send()
+            .await
+            .map_err(|e| upload_network_error("delete", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(classify_status_error(
+                status.as_u16(),
+                format!("Failed to delete {} ({}): {}", name, status, text),
+                self.rate_limit_max_wait_seconds,
+            ));
+        }
+
+        log::info!("[GEMINI-FILES] Deleted {}", name);
+        Ok(())
+    }
+
+    // Open a resumable upload session, returns the session URL
+    async fn start_session(&self, size: u64, mime_type: &str, display_name: &str) -> Result<String, GeminiError> {
+        let url = format!("{}?key={}", self.upload_url, self.api_key);
+        let response = http_client()?
+            .post(&url)
+            .header("X-Goog-Upload-Protocol", "resumable")
+            .header("X-Goog-Upload-Command", "start")
+            .header("X-Goog-Upload-Header-Content-Length", size.to_string())
+            .header("X-Goog-Upload-Header-Content-Type", mime_type)
+            .json(&serde_json::json!({ "file": { "display_name": display_name } }))
+            .send()
+            .await
+            .map_err(|e| upload_network_error("start", e))?;
+
+        let status = response.status();
+        let session_url = response
+            .headers()
+            .get("x-goog-upload-url")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(classify_status_error(
+                status.as_u16(),
+                format!("Failed to start upload ({}): {}", status, text),
+                self.rate_limit_max_wait_seconds,
+            ));
+        }
+
+        session_url.ok_or_else(|| GeminiError::Permanent {
+            message: "Upload session response has no upload URL".to_string(),
+        })
+    }
+
+    // Send the file from its first unconfirmed byte, resuming after dropped connections
+    async fn upload_chunks(&self, session_url: &str, path: &Path, size: u64) -> Result<UploadedFile, GeminiError> {
+        let mut file = std::fs::File::open(path).map_err(|e| GeminiError::Permanent {
+            message: format!("Failed to open video file {:?}: {}", path, e),
+        })?;
+
+        let mut offset = 0;
+        let mut failed_attempts = 0;
+
+        loop {
+            let length = self.chunk_size.min(size - offset);
+            let last = offset + length >= size;
+            let chunk = read_chunk(&mut file, offset, length).map_err(|e| GeminiError::Permanent {
+                message: format!("Failed to read video file {:?}: {}", path, e),
+            })?;
+
+            let result = http_client()?
+                .post(session_url)
+                .header("X-Goog-Upload-Command", if last { "upload, finalize" } else { "upload" })
+                .header("X-Goog-Upload-Offset", offset.to_string())
+                .body(chunk)
+                .send()
+                .await;
+
+            let error = match result {
+                Ok(response) if response.status().is_success() => {
+                    failed_attempts = 0;
+                    if last {
+                        return parse_file_response(&response.text().await.unwrap_or_default());
+                    }
+                    offset += length;
+                    continue;
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    let text = response.text().await.unwrap_or_default();
+                    match classify_status_error(
+                        status.as_u16(),
+                        format!("Upload failed at byte {} ({}): {}", offset, status, text),
+                        self.rate_limit_max_wait_seconds,
+                    ) {
+                        error @ GeminiError::Permanent { .. } => return Err(error),
+                        retryable => retryable,
+                    }
+                }
+                Err(e) => upload_network_error("upload", e),
+            };
+
+            failed_attempts += 1;
+            if failed_attempts >= MAX_RESUME_ATTEMPTS {
+                return Err(error);
+            }
+
+            let delay = self.resume_delay * 2u32.pow(failed_attempts - 1);
+            log::warn!(
+                "[GEMINI-FILES] Chunk at byte {} failed ({:?}), resuming in {:.1}s",
+                offset,
+                error,
+                delay.as_secs_f64()
+            );
+            tokio::time::sleep(delay).await;
+
+            match self.query_session(session_url).await? {
+                SessionState::Active { received } => offset = received.min(size),
+                SessionState::Final(file) => return Ok(file),
+            }
+        }
+    }
+
+    // Ask the session how far the upload got
+    async fn query_session(&self, session_url: &str) -> Result<SessionState, GeminiError> {
+        let response = http_client()?
+            .post(session_url)
+            .header("X-Goog-Upload-Command", "query")
+            .body(Vec::new())
+            .send()
+            .await
+            .map_err(|e| upload_network_error("query", e))?;
+
+        let status = response.status();
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let upload_status = header("x-goog-upload-status");
+        let received = header("x-goog-upload-size-received").and_then(|v| v.parse().ok());
+        let text = response.text().await.unwrap_or_default();
+
+        if !status.is_success() {
+            return Err(classify_status_error(
+                status.as_u16(),
+                format!("Upload session query failed ({}): {}", status, text),
+                self.rate_limit_max_wait_seconds,
+            ));
+        }
+
+        match (upload_status.as_deref(), received) {
+            (Some("final"), _) => parse_file_response(&text).map(SessionState::Final),
+            (Some("active"), Some(received)) => Ok(SessionState::Active { received }),
+            _ => Err(GeminiError::Permanent {
+                message: format!("Upload session is no longer active: {:?}", upload_status),
+            }),
+        }
+    }
+
+    // Poll the file until processing finishes
+    async fn wait_until_active(&self, mut file: UploadedFile) -> Result<UploadedFile, GeminiError> {
+        let url = format!("{}/{}?key={}", self.version_root, file.name, self.api_key);
+        let deadline = std::time::Instant::now() + Duration::from_secs(REQUEST_TIMEOUT_SECS);
+
+        loop {
+            match file.state.as_str() {
+                "ACTIVE" => return Ok(file),
+                "FAILED" => {
+                    return Err(GeminiError::Permanent {
+                        message: format!("Gemini could not process uploaded file {}", file.name),
+                    })
+                }
+                _ => {}
+            }
+
+            if std::time::Instant::now() >= deadline {
+                return Err(GeminiError::ServiceUnavailable {
+                    message: format!("Uploaded file {} still processing after {}s", file.name, REQUEST_TIMEOUT_SECS),
+                    retry_after: None,
+                });
+            }
+
+            tokio::time::sleep(self.poll_interval).await;
+
+            let response = http_client()?
+                .get(&url)
+                .send()
+                .await
+                .map_err(|e| upload_network_error("poll", e))?;
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+
+            if !status.is_success() {
+                return Err(classify_status_error(
+                    status.as_u16(),
+                    format!("Failed to get file {} ({}): {}", file.name, status, text),
+                    self.rate_limit_max_wait_seconds,
+                ));
+            }
+
+            file = serde_json::from_str(&text).map_err(|e| GeminiError::Permanent {
+                message: format!("Failed to parse file resource: {}", e),
+            })?;
+        }
+    }
+}
+
+/// Upload session progress reported by a query
+#[derive(Debug)]
+enum SessionState {
+    Active { received: u64 },
+    Final(UploadedFile),
+}
+
+// =============================================================================
+// Internal Functions
+// =============================================================================
+
+/**
+ * Derive Files API endpoints from the models base URL
+ * "https://host/v1beta/models" -> ("https://host/v1beta", "https://host/upload/v1beta/files")
+ */
+fn files_endpoints(api_base: &str) -> (String, String) {
+    let version_root = api_base.trim_end_matches('/').trim_end_matches("/models").to_string();
+
+    let path_start = version_root
+        .find("://")
+        .and_then(|scheme_end| version_root[scheme_end + 3..].find('/').map(|i| scheme_end + 3 + i))
+        .unwrap_or(version_root.len());
+    let (origin, path) = version_root.split_at(path_start);
+
+    let upload_url = format!("{}/upload{}/files", origin, path);
+    (version_root, upload_url)
+}
+
+fn read_chunk(file: &mut std::fs::File, offset: u64, length: u64) -> std::io::Result<Vec<u8>> {
+    file.seek(SeekFrom::Start(offset))?;
+    let mut chunk = vec![0; length as usize];
+    file.read_exact(&mut chunk)?;
+    Ok(chunk)
+}
+
+fn parse_file_response(text: &str) -> Result<UploadedFile, GeminiError> {
+    serde_json::from_str::<FileEnvelope>(text)
+        .map(|envelope| envelope.file)
+        .map_err(|e| GeminiError::Permanent {
+            message: format!("Failed to parse uploaded file response: {}", e),
+        })
+}
+
+// Network failures during upload are transient (resume or retry the job later)
+fn upload_network_error(step: &str, error: reqwest::Error) -> GeminiError {
+    GeminiError::ServiceUnavailable {
+        message: format!("Files API {} request failed: {}", step, error),
+        retry_after: None,
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Write};
+    use std::sync::{Arc, Mutex};
+
+    /// Request seen by the stand-in Files API
+    struct StubRequest {
+        method: String,
+        path: String,
+        headers: HashMap<String, String>,
+        body: Vec<u8>,
+    }
+
+    /// Stand-in reply; None drops the connection without answering
+    type StubReply = Option<(u16, Vec<(&'static str, String)>, String)>;
+
+    // Serve requests one connection at a time until the test ends
+    fn serve(mut handler: impl FnMut(&StubRequest) -> StubReply + Send + 'static) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+                    continue;
+                }
+                let mut parts = request_line.split_whitespace();
+                let method = parts.next().unwrap_or_default().to_string();
+                let path = parts.next().unwrap_or_default().to_string();
+
+                let mut headers = HashMap::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+                    }
+                }
+
+                let length = headers.get("content-length").and_then(|v| v.parse().ok()).unwrap_or(0);
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+
+                let request = StubRequest { method, path, headers, body };
+                if let Some((status, reply_headers, reply_body)) = handler(&request) {
+                    let extra: String = reply_headers.iter().map(|(k, v)| format!("{}: {}\r\n", k, v)).collect();
+                    let _ = write!(
+                        stream,
+                        "HTTP/1.1 {} Stand-in\r\n{}Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        extra,
+                        reply_body.len(),
+                        reply_body
+                    );
+                }
+            }
+        });
+
+        base_url
+    }
+
+    fn uploader(base_url: &str) -> FileUploader {
+        let mut uploader = FileUploader::new("test-key", &format!("{}/v1beta/models", base_url), 120);
+        uploader.chunk_size = 256 * 1024;
+        uploader.resume_delay = Duration::from_millis(10);
+        uploader.poll_interval = Duration::from_millis(10);
+        uploader
+    }
+
+    fn test_video(name: &str, size: usize) -> (std::path::PathBuf, Vec<u8>) {
+        let bytes: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+        let path = std::env::temp_dir().join(format!("{}-{}.mp4", name, uuid::Uuid::new_v4()));
+        std::fs::write(&path, &bytes).unwrap();
+        (path, bytes)
+    }
+
+    #[test]
+    fn test_files_endpoints() {
+        assert_eq!(
+            files_endpoints("https://generativelanguage.googleapis.com/v1beta/models"),
+            (
+                "https://generativelanguage.googleapis.com/v1beta".to_string(),
+                "https://generativelanguage.googleapis.com/upload/v1beta/files".to_string()
+            )
+        );
+        assert_eq!(
+            files_endpoints("http://127.0.0.1:8080/v1beta/models/").1,
+            "http://127.0.0.1:8080/upload/v1beta/files"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_resumes_after_dropped_connection() {
+        let (path, original) = test_video("files-upload", 600 * 1024);
+        let received = Arc::new(Mutex::new(Vec::<u8>::new()));
+        let commands = Arc::new(Mutex::new(Vec::<String>::new()));
+
+        let (stub_received, stub_commands) = (received.clone(), commands.clone());
+        let mut dropped = false;
+        let mut polls = 0;
+        let base_url = serve(move |request| {
+            let command = request.headers.get("x-goog-upload-command").cloned().unwrap_or_default();
+            stub_commands.lock().unwrap().push(format!("{} {}", request.method, command));
+            let mut data = stub_received.lock().unwrap();
+
+            match (request.method.as_str(), command.as_str()) {
+                ("POST", "start") => {
+                    assert!(request.path.starts_with("/upload/v1beta/files?key=test-key"));
+                    assert_eq!(request.headers["x-goog-upload-header-content-length"], "614400");
+                    let session = format!("http://{}/session/1", request.headers["host"]);
+                    Some((200, vec![("X-Goog-Upload-URL", session)], String::new()))
+                }
+                ("POST", "upload") if data.len() == 256 * 1024 && !dropped => {
+                    // Keep the bytes but lose the reply, like a connection reset
+                    dropped = true;
+                    data.extend_from_slice(&request.body);
+                    None
+                }
+                ("POST", "upload") | ("POST", "upload, finalize") => {
+                    assert_eq!(request.headers["x-goog-upload-offset"], data.len().to_string());
+                    data.extend_from_slice(&request.body);
+                    if command == "upload" {
+                        Some((200, vec![("X-Goog-Upload-Status", "active".to_string())], String::new()))
+                    } else {
+                        Some((
+                            200,
+                            vec![("X-Goog-Upload-Status", "final".to_string())],
+                            r#"{"file": {"name": "files/abc123", "uri": "https://stub/v1beta/files/abc123", "mimeType": "video/mp4", "state": "PROCESSING"}}"#.to_string(),
+                        ))
+                    }
+                }
+                ("POST", "query") => Some((
+                    200,
+                    vec![
+                        ("X-Goog-Upload-Status", "active".to_string()),
+                        ("X-Goog-Upload-Size-Received", data.len().to_string()),
+                    ],
+                    String::new(),
+                )),
+                ("GET", _) => {
+                    assert!(request.path.starts_with("/v1beta/files/abc123?key=test-key"));
+                    polls += 1;
+                    let state = if polls < 2 { "PROCESSING" } else { "ACTIVE" };
+                    Some((
+                        200,
+                        Vec::new(),
+                        format!(
+                            r#"{{"name": "files/abc123", "uri": "https://stub/v1beta/files/abc123", "mimeType": "video/mp4", "state": "{}"}}"#,
+                            state
+                        ),
+                    ))
+                }
+                ("DELETE", _) => {
+                    assert!(request.path.starts_with("/v1beta/files/abc123?key=test-key"));
+                    Some((200, Vec::new(), "{}".to_string()))
+                }
+                other => panic!("unexpected request {:?}", other),
+            }
+        });
+
+        let uploader = uploader(&base_url);
+        let file = uploader.upload(&path, "video/mp4").await.unwrap();
+        assert_eq!(file.uri, "https://stub/v1beta/files/abc123");
+        assert_eq!(file.state, "ACTIVE");
+        assert_eq!(*received.lock().unwrap(), original);
+
+        uploader.delete(&file.name).await.unwrap();
+
+        assert_eq!(
+            *commands.lock().unwrap(),
+            vec![
+                "POST start",
+                "POST upload",
+                "POST upload",
+                "POST query",
+                "POST upload, finalize",
+                "GET ",
+                "GET ",
+                "DELETE ",
+            ]
+        );
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_upload_rejected_session_is_permanent() {
+        let (path, _) = test_video("files-rejected", 1024);
+        let base_url = serve(|_| Some((400, Vec::new(), r#"{"error": {"message": "Unsupported MIME type"}}"#.to_string())));
+
+        let result = uploader(&base_url).upload(&path, "video/mp4").await;
+        assert!(matches!(result, Err(GeminiError::Permanent { .. })));
+
+        let _ = std::fs::remove_file(path);
+    }
+}
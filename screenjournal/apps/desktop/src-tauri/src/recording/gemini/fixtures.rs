@@ -0,0 +1,81 @@
+/**
+ * ============================================================================
+ * GEMINI TEST FIXTURES
+ * ============================================================================
+ *
+ * PURPOSE: Jobs, analyses and timeline entries shared by the module tests
+ *
+ * Builders return plain values; tests override what they check with struct
+ * update syntax (`GeminiJob { display_index: 1, ..test_job(..) }`).
+ *
+ * ============================================================================
+ */
+
+use crate::recording::gemini::types::{GeminiJob, TimelineAnalysis, TimelineEntry};
+use chrono::{DateTime, Utc};
+
+/// Length of fixture segments (seconds)
+pub const SEGMENT_SECONDS: f64 = 300.0;
+
+/**
+ * Queued job for display 0 of a 5 minute segment
+ * Built from JSON like a job persisted before optional fields existed
+ */
+pub fn test_job(segment_id: &str, start_time: &str) -> GeminiJob {
+    let end_time = DateTime::parse_from_rfc3339(start_time)
+        .map(|start| (start.with_timezone(&Utc) + chrono::Duration::seconds(SEGMENT_SECONDS as i64)).to_rfc3339())
+        .unwrap();
+
+    serde_json::from_value(serde_json::json!({
+        "segment_id": segment_id,
+        "display_index": 0,
+        "video_path": format!("/tmp/{}_d0.mp4", segment_id),
+        "metadata": {
+            "id": segment_id,
+            "framerate": 1,
+            "start_time": start_time,
+            "end_time": end_time,
+            "duration_seconds": SEGMENT_SECONDS,
+            "total_file_size_bytes": 0,
+            "display_count": 1,
+            "displays": []
+        },
+        "retry_count": 0,
+        "created_at": end_time
+    }))
+    .unwrap()
+}
+
+/// Timeline entry described as "Using <app>"
+pub fn entry(start: &str, end: &str, app: &str, score: u8) -> TimelineEntry {
+    TimelineEntry {
+        start_time: start.to_string(),
+        end_time: end.to_string(),
+        description: format!("Using {}", app),
+        active_application: app.to_string(),
+        active_window_title: String::new(),
+        productive_score: score,
+        fusion: None,
+    }
+}
+
+/// First analysis of display 0 of a 5 minute segment (built-in prompt)
+pub fn analysis(timeline: Vec<TimelineEntry>) -> TimelineAnalysis {
+    TimelineAnalysis {
+        segment_id: "segment_1".to_string(),
+        display_index: 0,
+        analyzed_at: "2025-01-15T10:05:00Z".to_string(),
+        video_duration_seconds: SEGMENT_SECONDS,
+        segment_start_time: "2025-01-15T10:00:00Z".to_string(),
+        timeline,
+        backend: Default::default(),
+        model: "gemini-2.5-flash-lite".to_string(),
+        prompt_version: "builtin@2".to_string(),
+        quality: None,
+        speedup: None,
+        usage: None,
+        synthetic: false,
+        fusion: None,
+        analysis_version: 1,
+    }
+}
@@ -8,7 +8,18 @@
  * MEASUREMENT: screen_timeline
  * 
  * LINE PROTOCOL FORMAT:
- * screen_timeline,display=0,app=VSCode,hostname=laptop1 description="...",productive_score=5i,app_title="file.rs",duration_seconds=30i 1234567890000000000
+ * screen_timeline,display=0,app=VSCode,hostname=laptop1,prompt_version=builtin@2 description="...",productive_score=5i,app_title="file.rs",duration_seconds=30i 1234567890000000000
+ * 
+ * prompt_version and quality_score (0-1, see validation.rs) are omitted for
+ * analyses made before prompt templates and validation existed; speed_factor
+ * is only written for analyses with speed-up preprocessing
+ * 
+ * Entries fused with ActivityWatch window events (fusion.rs) also carry
+ * confidence (0-1) and disagreement (ActivityWatch reports another app)
+ * 
+ * Reanalyses are tagged analysis_version=N (N >= 2), so their points form
+ * new series next to the first analysis instead of overwriting it;
+ * dashboards switch over by filtering on the tag (absent = first analysis)
  * 
  * ============================================================================
  */
@@ -50,21 +61,51 @@ pub fn format_timeline_entry(
     let display_tag = analysis.display_index.to_string();
     let app_tag = escape_tag_value(&entry.active_application);
     let hostname_tag = escape_tag_value(hostname);
+    let prompt_version_tag = if analysis.prompt_version.is_empty() {
+        String::new()
+    } else {
+        format!(",prompt_version={}", escape_tag_value(&analysis.prompt_version))
+    };
+    let version_tag = if analysis.analysis_version > 1 {
+        format!(",analysis_version={}", analysis.analysis_version)
+    } else {
+        String::new()
+    };
 
     // Format fields
     let description_field = escape_field_string(&entry.description);
     let app_title_field = escape_field_string(&entry.active_window_title);
+    let quality_field = analysis
+        .quality
+        .as_ref()
+        .map(|quality| format!(",quality_score={}", quality.score))
+        .unwrap_or_default();
+    let speed_field = analysis
+        .speedup
+        .as_ref()
+        .map(|speedup| format!(",speed_factor={}", speedup.factor))
+        .unwrap_or_default();
+    let fusion_field = entry
+        .fusion
+        .as_ref()
+        .map(|fusion| format!(",confidence={},disagreement={}", fusion.confidence, fusion.disagreement))
+        .unwrap_or_default();
 
     Ok(format!(
-        "{},display={},app={},hostname={} description={},productive_score={}i,app_title={},duration_seconds={}i {}",
+        "{},display={},app={},hostname={}{}{} description={},productive_score={}i,app_title={},duration_seconds={}i{}{}{} {}",
         MEASUREMENT,
         display_tag,
         app_tag,
         hostname_tag,
+        prompt_version_tag,
+        version_tag,
         description_field,
         entry.productive_score,
         app_title_field,
         duration_seconds,
+        quality_field,
+        speed_field,
+        fusion_field,
         timestamp_nanos
     ))
 }
@@ -72,7 +113,7 @@ pub fn format_timeline_entry(
 /**
  * Calculate absolute timestamp for a timeline entry
  * 
- * Combines segment start time (ISO 8601) with entry start time (MM:SS)
+ * Combines segment start time (ISO 8601) with entry start time (MM:SS or HH:MM:SS)
  */
 fn calculate_entry_timestamp(
     segment_start_time: &str,
@@ -83,12 +124,11 @@ fn calculate_entry_timestamp(
         .map_err(|e| format!("Invalid segment start time '{}': {}", segment_start_time, e))?
         .with_timezone(&Utc);
 
-    // Parse entry start time (MM:SS)
-    let (minutes, seconds) = parse_mm_ss(entry_start_time)?;
-    let offset_seconds = (minutes * 60 + seconds) as i64;
+    // Parse entry start time (MM:SS or HH:MM:SS, seconds may be fractional)
+    let offset_seconds = parse_timestamp(entry_start_time)?;
 
     // Calculate absolute timestamp
-    let entry_timestamp = segment_start + Duration::seconds(offset_seconds);
+    let entry_timestamp = segment_start + Duration::milliseconds((offset_seconds * 1000.0).round() as i64);
 
     // Convert to nanoseconds
     entry_timestamp.timestamp_nanos_opt()
@@ -96,45 +136,59 @@ fn calculate_entry_timestamp(
 }
 
 /**
- * Parse MM:SS format to minutes and seconds
+ * Parse a video timestamp to seconds
+ * 
+ * Accepts "HH:MM:SS" and "MM:SS" (minutes may exceed 59, as older analyses
+ * of long videos wrote them), with optional fractional seconds ("01:05.5")
  */
-fn parse_mm_ss(time_str: &str) -> Result<(u32, u32), String> {
-    let parts: Vec<&str> = time_str.split(':').collect();
+pub(crate) fn parse_timestamp(time_str: &str) -> Result<f64, String> {
+    let parts: Vec<&str> = time_str.trim().split(':').collect();
     
-    if parts.len() != 2 {
-        return Err(format!("Invalid time format '{}', expected MM:SS", time_str));
-    }
-
-    let minutes: u32 = parts[0]
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [minutes, seconds] => ("0", *minutes, *seconds),
+        [hours, minutes, seconds] => (*hours, *minutes, *seconds),
+        _ => return Err(format!("Invalid time format '{}', expected MM:SS or HH:MM:SS", time_str)),
+    };
+    
+    let hours: u32 = hours
         .parse()
-        .map_err(|_| format!("Invalid minutes in '{}'", time_str))?;
+        .map_err(|_| format!("Invalid hours in '{}'", time_str))?;
     
-    let seconds: u32 = parts[1]
+    let minutes: u32 = minutes
         .parse()
-        .map_err(|_| format!("Invalid seconds in '{}'", time_str))?;
-
-    Ok((minutes, seconds))
+        .map_err(|_| format!("Invalid minutes in '{}'", time_str))?;
+    if parts.len() == 3 && minutes >= 60 {
+        return Err(format!("Invalid minutes in '{}'", time_str));
+    }
+    
+    // Digits with an optional fraction only (no sign, exponent or "inf")
+    let seconds: f64 = seconds
+        .chars()
+        .all(|c| c.is_ascii_digit() || c == '.')
+        .then(|| seconds.parse().ok())
+        .flatten()
+        .filter(|s: &f64| *s < 60.0)
+        .ok_or_else(|| format!("Invalid seconds in '{}'", time_str))?;
+    
+    Ok(hours as f64 * 3600.0 + minutes as f64 * 60.0 + seconds)
 }
 
 /**
- * Calculate duration in seconds between two MM:SS timestamps
+ * Calculate duration in seconds between two timestamps
  * 
  * # Arguments
- * * `start_time` - Start time in "MM:SS" format
- * * `end_time` - End time in "MM:SS" format
+ * * `start_time` - Start time ("MM:SS" or "HH:MM:SS")
+ * * `end_time` - End time ("MM:SS" or "HH:MM:SS")
  * 
  * # Returns
- * * `Ok(i64)` - Duration in seconds
+ * * `Ok(i64)` - Duration in whole seconds (rounded)
  * * `Err(String)` - Error message if parsing fails
  */
 fn calculate_duration_seconds(start_time: &str, end_time: &str) -> Result<i64, String> {
-    let (start_min, start_sec) = parse_mm_ss(start_time)?;
-    let (end_min, end_sec) = parse_mm_ss(end_time)?;
+    let start_total = parse_timestamp(start_time)?;
+    let end_total = parse_timestamp(end_time)?;
     
-    let start_total = (start_min * 60 + start_sec) as i64;
-    let end_total = (end_min * 60 + end_sec) as i64;
-    
-    Ok(end_total - start_total)
+    Ok((end_total - start_total).round() as i64)
 }
 
 // =============================================================================
@@ -145,21 +199,31 @@ fn calculate_duration_seconds(start_time: &str, end_time: &str) -> Result<i64, S
 mod tests {
     use super::*;
     use crate::collector::formatter::timestamp_to_nanos;
+    use crate::recording::gemini::types::{EntryFusion, SpeedupMode, SpeedupSummary, TimelineQuality};
 
     #[test]
-    fn test_parse_mm_ss() {
-        assert_eq!(parse_mm_ss("00:00").unwrap(), (0, 0));
-        assert_eq!(parse_mm_ss("00:30").unwrap(), (0, 30));
-        assert_eq!(parse_mm_ss("01:00").unwrap(), (1, 0));
-        assert_eq!(parse_mm_ss("05:45").unwrap(), (5, 45));
-        assert_eq!(parse_mm_ss("60:00").unwrap(), (60, 0));
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("00:00").unwrap(), 0.0);
+        assert_eq!(parse_timestamp("00:30").unwrap(), 30.0);
+        assert_eq!(parse_timestamp("01:00").unwrap(), 60.0);
+        assert_eq!(parse_timestamp("05:45").unwrap(), 345.0);
+        assert_eq!(parse_timestamp("60:00").unwrap(), 3600.0);
+        assert_eq!(parse_timestamp("125:10").unwrap(), 7510.0);
+        assert_eq!(parse_timestamp("1:2:3").unwrap(), 3723.0);
+        assert_eq!(parse_timestamp("02:05:00").unwrap(), 7500.0);
+        assert_eq!(parse_timestamp("01:05.5").unwrap(), 65.5);
+        assert_eq!(parse_timestamp("01:00:00.250").unwrap(), 3600.25);
     }
 
     #[test]
-    fn test_parse_mm_ss_invalid() {
-        assert!(parse_mm_ss("invalid").is_err());
-        assert!(parse_mm_ss("1:2:3").is_err());
-        assert!(parse_mm_ss("ab:cd").is_err());
+    fn test_parse_timestamp_invalid() {
+        assert!(parse_timestamp("invalid").is_err());
+        assert!(parse_timestamp("ab:cd").is_err());
+        assert!(parse_timestamp("1:2:3:4").is_err());
+        assert!(parse_timestamp("01:75:00").is_err());
+        assert!(parse_timestamp("00:60").is_err());
+        assert!(parse_timestamp("00:-5").is_err());
+        assert!(parse_timestamp("00:1e1").is_err());
     }
 
     #[test]
@@ -174,6 +238,9 @@ mod tests {
         assert_eq!(calculate_duration_seconds("01:00", "06:00").unwrap(), 300);
         // Same start and end (0 duration)
         assert_eq!(calculate_duration_seconds("01:30", "01:30").unwrap(), 0);
+        // Across the hour, mixed formats and fractions
+        assert_eq!(calculate_duration_seconds("59:30", "01:00:30").unwrap(), 60);
+        assert_eq!(calculate_duration_seconds("00:10.25", "00:20.75").unwrap(), 11);
     }
 
     #[test]
@@ -189,6 +256,11 @@ mod tests {
         let ts_90 = calculate_entry_timestamp(segment_start, "01:30").unwrap();
         let expected_90 = timestamp_to_nanos("2025-01-15T10:01:30Z").unwrap();
         assert_eq!(ts_90, expected_90);
+        
+        // Past 99 minutes, with fractional seconds
+        let ts_long = calculate_entry_timestamp(segment_start, "01:45:00.5").unwrap();
+        let expected_long = timestamp_to_nanos("2025-01-15T11:45:00.500Z").unwrap();
+        assert_eq!(ts_long, expected_long);
     }
 
     #[test]
@@ -200,6 +272,25 @@ mod tests {
             video_duration_seconds: 300.0,
             segment_start_time: "2025-01-15T10:00:00Z".to_string(),
             timeline: vec![],
+            backend: Default::default(),
+            model: "gemini-2.5-flash-lite".to_string(),
+            prompt_version: "support@2".to_string(),
+            quality: Some(TimelineQuality {
+                coverage: 1.0,
+                repairs: 1,
+                corrected: false,
+                score: 0.95,
+            }),
+            speedup: Some(SpeedupSummary {
+                mode: SpeedupMode::Reencode,
+                factor: 2.0,
+                estimated_tokens: 38700,
+                estimated_tokens_saved: 38700,
+            }),
+            usage: None,
+            synthetic: false,
+            fusion: None,
+            analysis_version: 1,
         };
 
         let entry = TimelineEntry {
@@ -209,6 +300,7 @@ mod tests {
             active_application: "VS Code".to_string(),
             active_window_title: "main.rs - project".to_string(),
             productive_score: 5,
+            fusion: None,
         };
 
         let result = format_timeline_entry(&analysis, &entry, "laptop1");
@@ -218,16 +310,38 @@ mod tests {
         assert!(line.starts_with("screen_timeline,"));
         assert!(line.contains("display=0"));
         assert!(line.contains("app=VS\\ Code"));
-        assert!(line.contains("hostname=laptop1"));
+        assert!(line.contains("hostname=laptop1,prompt_version=support@2 "));
         assert!(line.contains("productive_score=5i"));
         assert!(line.contains("description=\"User editing code\""));
         assert!(line.contains("app_title=\"main.rs - project\""));
-        assert!(line.contains("duration_seconds=30i"));
+        assert!(line.contains("duration_seconds=30i,quality_score=0.95,speed_factor=2 "));
         // Verify removed fields/tags are NOT present
         assert!(!line.contains("window="));
         assert!(!line.contains("segment_id="));
         assert!(!line.contains("start_time="));
         assert!(!line.contains("end_time="));
+        assert!(!line.contains("confidence="));
+
+        // Fused entries carry their ActivityWatch agreement
+        let fused = TimelineEntry {
+            fusion: Some(EntryFusion {
+                confidence: 0.75,
+                disagreement: false,
+                model_application: None,
+                snapped: true,
+            }),
+            ..entry
+        };
+        let line = format_timeline_entry(&analysis, &fused, "laptop1").unwrap();
+        assert!(line.contains("speed_factor=2,confidence=0.75,disagreement=false "));
+
+        // Reanalyses get a version tag
+        let reanalysis = TimelineAnalysis {
+            analysis_version: 3,
+            ..analysis
+        };
+        let line = format_timeline_entry(&reanalysis, &fused, "laptop1").unwrap();
+        assert!(line.contains("prompt_version=support@2,analysis_version=3 "));
     }
 }
 
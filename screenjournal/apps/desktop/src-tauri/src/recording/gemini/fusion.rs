@@ -0,0 +1,299 @@
+/**
+ * ============================================================================
+ * TIMELINE FUSION MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Combine model timelines with ActivityWatch window events
+ *
+ * Model timelines describe what was done but their boundaries are
+ * approximate; ActivityWatch window events are exact but carry no meaning.
+ * Fusion runs between analysis and the collector:
+ *
+ * SNAPPING:
+ * A boundary shared by two entries moves to the nearest window switch within
+ * fusion_snap_seconds, as long as both entries keep a positive length.
+ *
+ * APP NAMES:
+ * An entry's application is matched against the apps ActivityWatch reports
+ * focused during it (case, punctuation and ".exe" ignored, one name may
+ * contain the other: "VS Code" ~ "Code"). A match is replaced by the
+ * ActivityWatch name so the same app is always tagged the same way.
+ *
+ * CONFIDENCE:
+ * Share of the entry ActivityWatch reports the entry's app focused. An entry
+ * whose app is not the one focused for most of it is flagged as a
+ * disagreement (kept as the model described it). Entries without (public)
+ * window data are not fused.
+ *
+ * Private windows (PrivacyConfig) are switch points but never name an app.
+ *
+ * ============================================================================
+ */
+
+use crate::recording::gemini::{
+    context::{fetch_window_spans, WindowSpan},
+    formatter::parse_timestamp,
+    prompt::format_duration,
+    types::{EntryFusion, FusionSummary, GeminiJob, TimelineAnalysis},
+};
+use std::collections::HashMap;
+
+/// Boundaries closer than this (seconds) count as shared by two entries
+const SHARED_BOUNDARY_SECONDS: f64 = 0.5;
+
+/// Shortest app name matched by containment (avoids "a" matching everything)
+const MIN_CONTAINED_NAME_LEN: usize = 3;
+
+/**
+ * Fuse a job's analysis with the window events of its segment
+ * Leaves the analysis unchanged without window data or for away timelines
+ */
+pub async fn fuse_with_activitywatch(job: &GeminiJob, analysis: &mut TimelineAnalysis, snap_seconds: f64) {
+    if analysis.synthetic {
+        return;
+    }
+
+    let spans = fetch_window_spans(job).await;
+    if spans.is_empty() {
+        return;
+    }
+
+    match fuse(analysis, &spans, snap_seconds) {
+        Ok(summary) => {
+            log::info!(
+                "[GEMINI-FUSION] Segment {} display {}: {} boundaries snapped, {} apps corrected, {} disagreements, confidence {:.2}",
+                analysis.segment_id,
+                analysis.display_index,
+                summary.boundaries_snapped,
+                summary.apps_corrected,
+                summary.disagreements,
+                summary.confidence
+            );
+            analysis.fusion = Some(summary);
+        }
+        Err(e) => log::warn!("[GEMINI-FUSION] Timeline left unfused: {}", e),
+    }
+}
+
+/**
+ * Snap boundaries, correct app names and score entries against window spans
+ *
+ * # Arguments
+ * * `analysis` - Validated analysis; entries are updated in place
+ * * `spans` - Focused windows, seconds from the segment start
+ * * `snap_seconds` - Largest boundary move
+ *
+ * # Returns
+ * * `Err(String)` if an entry time can't be parsed (analysis untouched)
+ */
+pub fn fuse(analysis: &mut TimelineAnalysis, spans: &[WindowSpan], snap_seconds: f64) -> Result<FusionSummary, String> {
+    let mut ranges = analysis
+        .timeline
+        .iter()
+        .map(|entry| Ok((parse_timestamp(&entry.start_time)?, parse_timestamp(&entry.end_time)?)))
+        .collect::<Result<Vec<(f64, f64)>, String>>()?;
+
+    let mut summary = FusionSummary::default();
+    let mut snapped = vec![false; ranges.len()];
+
+    // Window switches (the first span may just be the window focused at the start)
+    let switches: Vec<f64> = spans.iter().map(|s| s.start_seconds).filter(|&s| s > 0.0).collect();
+    for i in 1..ranges.len() {
+        let boundary = ranges[i].0;
+        if (ranges[i - 1].1 - boundary).abs() > SHARED_BOUNDARY_SECONDS {
+            continue;
+        }
+
+        let nearest = switches
+            .iter()
+            .copied()
+            .filter(|&s| (s - boundary).abs() <= snap_seconds && s > ranges[i - 1].0 && s < ranges[i].1)
+            .min_by(|a, b| (a - boundary).abs().total_cmp(&(b - boundary).abs()));
+        if let Some(switch) = nearest
+            && format_duration(switch) != format_duration(boundary)
+        {
+            ranges[i - 1].1 = switch;
+            ranges[i].0 = switch;
+            snapped[i - 1] = true;
+            snapped[i] = true;
+            summary.boundaries_snapped += 1;
+        }
+    }
+
+    let mut weighted_confidence = 0.0;
+    let mut fused_seconds = 0.0;
+    for (i, entry) in analysis.timeline.iter_mut().enumerate() {
+        let (start, end) = ranges[i];
+        if snapped[i] {
+            entry.start_time = format_duration(start);
+            entry.end_time = format_duration(end);
+        }
+
+        let length = end - start;
+        let apps = focused_apps(spans, start, end);
+        if length <= 0.0 || apps.is_empty() {
+            entry.fusion = None;
+            continue;
+        }
+
+        // The matching app focused longest is the entry's app
+        let matched = apps
+            .iter()
+            .filter(|(app, _)| same_app(&entry.active_application, app))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        let dominant = apps.iter().max_by(|a, b| a.1.total_cmp(&b.1));
+
+        let mut model_application = None;
+        let matched_seconds = match matched {
+            Some((app, seconds)) => {
+                if entry.active_application != *app {
+                    model_application = Some(std::mem::replace(&mut entry.active_application, app.to_string()));
+                    summary.apps_corrected += 1;
+                }
+                *seconds
+            }
+            None => 0.0,
+        };
+        let disagreement = dominant
+            .is_some_and(|(app, seconds)| *app != entry.active_application && *seconds >= length / 2.0);
+        if disagreement {
+            summary.disagreements += 1;
+        }
+
+        let confidence = (matched_seconds / length).min(1.0);
+        weighted_confidence += confidence * length;
+        fused_seconds += length;
+        entry.fusion = Some(EntryFusion {
+            confidence,
+            disagreement,
+            model_application,
+            snapped: snapped[i],
+        });
+    }
+
+    summary.confidence = if fused_seconds > 0.0 {
+        weighted_confidence / fused_seconds
+    } else {
+        0.0
+    };
+    Ok(summary)
+}
+
+// Seconds each public app is focused within [start, end]
+fn focused_apps(spans: &[WindowSpan], start: f64, end: f64) -> Vec<(&str, f64)> {
+    let mut seconds: HashMap<&str, f64> = HashMap::new();
+    for span in spans.iter().filter(|s| !s.private && !s.app.is_empty()) {
+        let overlap = span.end_seconds.min(end) - span.start_seconds.max(start);
+        if overlap > 0.0 {
+            *seconds.entry(span.app.as_str()).or_default() += overlap;
+        }
+    }
+    seconds.into_iter().collect()
+}
+
+/**
+ * Whether a model app name and an ActivityWatch app name mean the same app
+ * ("VS Code" ~ "Code", "Chrome" ~ "Google-chrome", "Teams" ~ "Teams.exe")
+ */
+fn same_app(model_app: &str, aw_app: &str) -> bool {
+    let normalize = |name: &str| -> String {
+        let name = name.trim().to_lowercase();
+        let name = name.strip_suffix(".exe").or_else(|| name.strip_suffix(".app")).unwrap_or(&name);
+        name.chars().filter(|c| c.is_alphanumeric()).collect()
+    };
+    let (a, b) = (normalize(model_app), normalize(aw_app));
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+
+    let (shorter, longer) = if a.len() <= b.len() { (&a, &b) } else { (&b, &a) };
+    a == b || (shorter.len() >= MIN_CONTAINED_NAME_LEN && longer.contains(shorter.as_str()))
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::gemini::fixtures::{analysis, entry};
+
+    fn span(start_seconds: f64, end_seconds: f64, app: &str) -> WindowSpan {
+        WindowSpan {
+            start_seconds,
+            end_seconds,
+            app: app.to_string(),
+            title: String::new(),
+            private: false,
+        }
+    }
+
+    #[test]
+    fn test_same_app() {
+        assert!(same_app("VS Code", "Code"));
+        assert!(same_app("Chrome", "Google-chrome"));
+        assert!(same_app("Microsoft Teams", "Teams.exe"));
+        assert!(same_app("firefox", "Firefox"));
+        assert!(!same_app("Slack", "Code"));
+        assert!(!same_app("X", "Xcode"));
+        assert!(!same_app("", "Code"));
+    }
+
+    #[test]
+    fn test_fuse_snaps_and_corrects() {
+        let mut analysis = analysis(vec![
+            entry("00:00", "02:00", "VS Code", 4),
+            entry("02:00", "04:00", "Chrome", 4),
+            entry("04:00", "05:00", "Slack", 4),
+        ]);
+        let spans = vec![
+            span(0.0, 126.0, "Code"),
+            span(126.0, 240.0, "Google-chrome"),
+            // Model thought Slack, window events say terminal
+            span(240.0, 300.0, "Terminal"),
+        ];
+
+        let summary = fuse(&mut analysis, &spans, 10.0).unwrap();
+        assert_eq!(
+            (summary.boundaries_snapped, summary.apps_corrected, summary.disagreements),
+            (1, 2, 1)
+        );
+
+        let timeline = &analysis.timeline;
+        assert_eq!((timeline[0].end_time.as_str(), timeline[1].start_time.as_str()), ("02:06", "02:06"));
+        assert_eq!(timeline[0].active_application, "Code");
+        assert_eq!(timeline[1].active_application, "Google-chrome");
+        assert_eq!(timeline[2].active_application, "Slack");
+
+        let first = timeline[0].fusion.as_ref().unwrap();
+        assert_eq!((first.confidence, first.snapped), (1.0, true));
+        assert_eq!(first.model_application.as_deref(), Some("VS Code"));
+
+        let last = timeline[2].fusion.as_ref().unwrap();
+        assert_eq!((last.confidence, last.disagreement, last.snapped), (0.0, true, false));
+        assert!((summary.confidence - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_fuse_leaves_far_switches_and_private_windows() {
+        let mut analysis = analysis(vec![entry("00:00", "02:00", "Code", 4), entry("02:00", "05:00", "Firefox", 4)]);
+        let spans = vec![
+            span(0.0, 150.0, "Code"),
+            WindowSpan {
+                private: true,
+                app: "[REDACTED]".to_string(),
+                ..span(150.0, 300.0, "")
+            },
+        ];
+
+        let summary = fuse(&mut analysis, &spans, 10.0).unwrap();
+        assert_eq!(summary.boundaries_snapped, 0);
+        assert_eq!(analysis.timeline[0].end_time, "02:00");
+
+        // Half of the second entry is Code, the rest private: no disagreement
+        let second = analysis.timeline[1].fusion.as_ref().unwrap();
+        assert_eq!((second.confidence, second.disagreement), (0.0, false));
+        assert_eq!(analysis.timeline[1].active_application, "Firefox");
+    }
+}
@@ -0,0 +1,222 @@
+/**
+ * ============================================================================
+ * IDLE SEGMENT MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Spot segments the user was away for before paying for analysis
+ *
+ * IDLE:
+ * ActivityWatch AFK events covering the segment are summed per status. A
+ * segment is idle when its not-afk time is below idle_threshold_seconds and
+ * afk events account for the rest; time without AFK data never counts as
+ * away, so a stopped watcher doesn't make segments look idle.
+ *
+ * AWAY TIMELINE:
+ * Idle segments that are skipped get a synthetic timeline with a single
+ * "Away" entry, sent to the collector like a model timeline so dashboards
+ * still show the period.
+ *
+ * ============================================================================
+ */
+
+use crate::activitywatch::types::EventInfo;
+use crate::recording::gemini::{
+    prompt::format_duration,
+    types::{GeminiJob, TimelineAnalysis, TimelineEntry},
+};
+use chrono::{DateTime, Utc};
+
+/// Application name of away entries
+pub const AWAY_APPLICATION: &str = "Away";
+
+/// Model name recorded for synthetic away timelines
+const AWAY_MODEL: &str = "activitywatch-afk";
+
+/// Neutral productivity score, so away time doesn't skew averages
+const AWAY_SCORE: u8 = 3;
+
+/// ActivityWatch AFK time within a segment
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct AfkSummary {
+    /// Seconds reported not-afk
+    pub active_seconds: f64,
+
+    /// Seconds reported afk
+    pub away_seconds: f64,
+}
+
+impl AfkSummary {
+    /// Whether a segment of `segment_seconds` counts as idle (threshold 0 = never)
+    pub fn is_idle(&self, segment_seconds: f64, threshold_seconds: u64) -> bool {
+        let threshold = threshold_seconds as f64;
+        threshold > 0.0 && self.active_seconds < threshold && self.away_seconds >= segment_seconds - threshold
+    }
+}
+
+/**
+ * Fetch the AFK summary of a job's segment
+ * None when ActivityWatch isn't running or has no AFK data for the segment
+ */
+pub async fn fetch_afk_summary(job: &GeminiJob) -> Option<AfkSummary> {
+    let base_url = crate::activitywatch::manager::current_base_url()?;
+    let (start, end) = segment_range(job)?;
+
+    match crate::activitywatch::client::fetch_events_by_range(&base_url, start, end).await {
+        Ok(events) if !events.afk_events.is_empty() => Some(summarize_afk(&events.afk_events, start, end)),
+        Ok(_) => None,
+        Err(e) => {
+            log::warn!("[GEMINI-IDLE] Could not fetch AFK events: {}", e);
+            None
+        }
+    }
+}
+
+/// Segment start and end of a job (None if unparseable)
+pub fn segment_range(job: &GeminiJob) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    let start = DateTime::parse_from_rfc3339(&job.metadata.start_time).ok()?;
+    let end = DateTime::parse_from_rfc3339(&job.metadata.end_time).ok()?;
+    Some((start.with_timezone(&Utc), end.with_timezone(&Utc)))
+}
+
+/**
+ * Sum afk and not-afk time of AFK events within [start, end]
+ * Each total is capped at the segment length (events of several AFK
+ * buckets may overlap)
+ */
+pub fn summarize_afk(events: &[EventInfo], start: DateTime<Utc>, end: DateTime<Utc>) -> AfkSummary {
+    let segment_seconds = ((end - start).num_milliseconds() as f64 / 1000.0).max(0.0);
+    let mut summary = AfkSummary::default();
+
+    for event in events {
+        let Ok(timestamp) = DateTime::parse_from_rfc3339(&event.timestamp) else {
+            continue;
+        };
+        let event_start = timestamp.with_timezone(&Utc);
+        let event_end = event_start + chrono::Duration::milliseconds((event.duration.max(0.0) * 1000.0) as i64);
+        let overlap = ((event_end.min(end) - event_start.max(start)).num_milliseconds() as f64 / 1000.0).max(0.0);
+
+        match event.data.get("status").and_then(|s| s.as_str()) {
+            Some("not-afk") => summary.active_seconds += overlap,
+            Some("afk") => summary.away_seconds += overlap,
+            _ => {}
+        }
+    }
+
+    summary.active_seconds = summary.active_seconds.min(segment_seconds);
+    summary.away_seconds = summary.away_seconds.min(segment_seconds);
+    summary
+}
+
+/**
+ * Synthetic timeline for a skipped idle segment: one "Away" entry
+ * covering the whole video
+ */
+pub fn away_analysis(job: &GeminiJob, summary: &AfkSummary) -> TimelineAnalysis {
+    let duration = job.metadata.duration_seconds.max(0.0);
+
+    TimelineAnalysis {
+        segment_id: job.segment_id.clone(),
+        display_index: job.display_index,
+        analyzed_at: Utc::now().to_rfc3339(),
+        video_duration_seconds: duration,
+        segment_start_time: job.metadata.start_time.clone(),
+        timeline: vec![TimelineEntry {
+            start_time: format_duration(0.0),
+            end_time: format_duration(duration),
+            description: format!(
+                "Away from the computer ({:.0}s of activity reported by ActivityWatch)",
+                summary.active_seconds
+            ),
+            active_application: AWAY_APPLICATION.to_string(),
+            active_window_title: String::new(),
+            productive_score: AWAY_SCORE,
+            fusion: None,
+        }],
+        backend: Default::default(),
+        model: AWAY_MODEL.to_string(),
+        prompt_version: String::new(),
+        quality: None,
+        speedup: None,
+        usage: None,
+        synthetic: true,
+        fusion: None,
+        analysis_version: 1,
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::gemini::fixtures::test_job;
+    use serde_json::json;
+
+    fn afk_event(timestamp: &str, duration: f64, status: &str) -> EventInfo {
+        EventInfo {
+            id: None,
+            timestamp: timestamp.to_string(),
+            duration,
+            data: json!({ "status": status }),
+        }
+    }
+
+    fn range() -> (DateTime<Utc>, DateTime<Utc>) {
+        (
+            "2025-01-15T10:00:00Z".parse().unwrap(),
+            "2025-01-15T10:05:00Z".parse().unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_summarize_afk_clips_to_segment() {
+        let (start, end) = range();
+        let events = vec![
+            // Started before the segment, 4 minutes of it away
+            afk_event("2025-01-15T09:50:00Z", 840.0, "afk"),
+            afk_event("2025-01-15T10:04:00Z", 10.0, "not-afk"),
+            afk_event("2025-01-15T10:04:10Z", 600.0, "afk"),
+        ];
+
+        let summary = summarize_afk(&events, start, end);
+        assert_eq!(summary.active_seconds, 10.0);
+        assert_eq!(summary.away_seconds, 290.0);
+        assert!(summary.is_idle(300.0, 15));
+    }
+
+    #[test]
+    fn test_is_idle() {
+        let summary = |active_seconds: f64, away_seconds: f64| AfkSummary {
+            active_seconds,
+            away_seconds,
+        };
+
+        assert!(summary(0.0, 300.0).is_idle(300.0, 15));
+        // Enough activity
+        assert!(!summary(20.0, 280.0).is_idle(300.0, 15));
+        // Missing AFK data is not away time
+        assert!(!summary(0.0, 120.0).is_idle(300.0, 15));
+        // Disabled
+        assert!(!summary(0.0, 300.0).is_idle(300.0, 0));
+    }
+
+    #[test]
+    fn test_away_analysis() {
+        let job = GeminiJob {
+            display_index: 1,
+            ..test_job("segment_1", "2025-01-15T10:00:00Z")
+        };
+
+        let analysis = away_analysis(&job, &AfkSummary::default());
+        assert!(analysis.synthetic);
+        assert_eq!(analysis.display_index, 1);
+        assert_eq!(analysis.timeline.len(), 1);
+        assert_eq!(analysis.timeline[0].active_application, AWAY_APPLICATION);
+        assert_eq!(
+            (analysis.timeline[0].start_time.as_str(), analysis.timeline[0].end_time.as_str()),
+            ("00:00", "05:00")
+        );
+    }
+}
@@ -0,0 +1,190 @@
+/**
+ * ============================================================================
+ * GEMINI RATE LIMITER MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Request pacing shared by all queue workers
+ *
+ * TOKEN BUCKET:
+ * - Refills at rate_limit_per_minute / 60 tokens per second
+ * - Holds up to BURST_SECONDS worth of tokens (at least one), so an idle
+ *   queue can start a few jobs at once without exceeding the minute budget
+ * - 0 requests per minute = unlimited
+ *
+ * SHARED BACKOFF:
+ * A rate-limited response pauses the whole limiter until the provider's
+ * retry delay has passed, so every worker holds off instead of each one
+ * running into the limit again.
+ *
+ * ============================================================================
+ */
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Seconds of requests the bucket can hold for bursts
+const BURST_SECONDS: f64 = 10.0;
+
+/**
+ * Token bucket with a shared pause
+ */
+pub struct RateLimiter {
+    state: Mutex<BucketState>,
+}
+
+struct BucketState {
+    requests_per_minute: u32,
+    tokens: f64,
+    last_refill: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl BucketState {
+    fn capacity(&self) -> f64 {
+        (self.requests_per_minute as f64 / 60.0 * BURST_SECONDS).max(1.0)
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.requests_per_minute as f64 / 60.0).min(self.capacity());
+        self.last_refill = now;
+    }
+}
+
+impl RateLimiter {
+    pub fn new(requests_per_minute: u32) -> Self {
+        let mut state = BucketState {
+            requests_per_minute,
+            tokens: 0.0,
+            last_refill: Instant::now(),
+            paused_until: None,
+        };
+        state.tokens = state.capacity();
+        Self {
+            state: Mutex::new(state),
+        }
+    }
+
+    /**
+     * Change the rate (settings update); tokens above the new burst size are dropped
+     */
+    pub fn set_rate(&self, requests_per_minute: u32) {
+        let mut state = self.state.lock().unwrap();
+        state.refill(Instant::now());
+        state.requests_per_minute = requests_per_minute;
+        state.tokens = state.tokens.min(state.capacity());
+    }
+
+    /**
+     * Wait until a request may be sent, then consume a token
+     */
+    pub async fn acquire(&self) {
+        while let Some(wait) = self.try_acquire(Instant::now()) {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /**
+     * Hold off all workers for `delay` (extends, never shortens, a running pause)
+     */
+    pub fn pause_for(&self, delay: Duration) {
+        self.pause_until(Instant::now() + delay);
+    }
+
+    /**
+     * Remaining shared backoff, None when not paused
+     */
+    pub fn paused_remaining(&self) -> Option<Duration> {
+        let state = self.state.lock().unwrap();
+        state
+            .paused_until
+            .map(|until| until.saturating_duration_since(Instant::now()))
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    fn pause_until(&self, until: Instant) {
+        let mut state = self.state.lock().unwrap();
+        state.paused_until = Some(state.paused_until.map_or(until, |current| current.max(until)));
+    }
+
+    // Take a token at `now`, or return how long to wait before trying again
+    fn try_acquire(&self, now: Instant) -> Option<Duration> {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(until) = state.paused_until {
+            if now < until {
+                return Some(until - now);
+            }
+            state.paused_until = None;
+        }
+
+        if state.requests_per_minute == 0 {
+            return None;
+        }
+
+        state.refill(now);
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            None
+        } else {
+            let per_second = state.requests_per_minute as f64 / 60.0;
+            Some(Duration::from_secs_f64((1.0 - state.tokens) / per_second))
+        }
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_paces_requests() {
+        let limiter = RateLimiter::new(60);
+        let start = limiter.state.lock().unwrap().last_refill;
+
+        // 60 RPM bursts 10 requests, then one per second
+        for _ in 0..10 {
+            assert_eq!(limiter.try_acquire(start), None);
+        }
+        let wait = limiter.try_acquire(start).unwrap();
+        assert!((wait.as_secs_f64() - 1.0).abs() < 1e-6);
+
+        assert_eq!(limiter.try_acquire(start + Duration::from_secs(1)), None);
+        assert!(limiter.try_acquire(start + Duration::from_secs(1)).is_some());
+    }
+
+    #[test]
+    fn test_unlimited_and_rate_change() {
+        let limiter = RateLimiter::new(0);
+        let start = limiter.state.lock().unwrap().last_refill;
+        for _ in 0..100 {
+            assert_eq!(limiter.try_acquire(start), None);
+        }
+
+        // Slow rates still allow one request at a time
+        limiter.set_rate(6);
+        let now = limiter.state.lock().unwrap().last_refill;
+        assert_eq!(limiter.state.lock().unwrap().capacity(), 1.0);
+        assert_eq!(limiter.try_acquire(now), None);
+        let wait = limiter.try_acquire(now).unwrap();
+        assert!((wait.as_secs_f64() - 10.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_pause_blocks_every_caller() {
+        let limiter = RateLimiter::new(0);
+        let start = Instant::now();
+        limiter.pause_until(start + Duration::from_secs(30));
+        // A shorter pause from another worker doesn't cut it short
+        limiter.pause_until(start + Duration::from_secs(5));
+
+        assert_eq!(limiter.try_acquire(start), Some(Duration::from_secs(30)));
+        assert_eq!(limiter.try_acquire(start + Duration::from_secs(10)), Some(Duration::from_secs(20)));
+        assert_eq!(limiter.try_acquire(start + Duration::from_secs(30)), None);
+        assert!(limiter.paused_remaining().is_none());
+    }
+}
@@ -0,0 +1,248 @@
+/**
+ * ============================================================================
+ * LOCAL MODEL ANALYZER MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Timeline analysis on a local model server - nothing leaves the machine
+ *
+ * API ENDPOINT (Ollama):
+ * POST {base_url}/api/chat
+ *
+ * Sampled frames are attached as base64 images to a single user message and
+ * JSON output is requested via `format: "json"`. llama.cpp's server speaks
+ * the OpenAI API instead - use the openai_compatible backend for it.
+ *
+ * An unreachable server is reported as temporarily unavailable so queued
+ * jobs wait for it to start instead of failing.
+ *
+ * ============================================================================
+ */
+
+use crate::recording::gemini::{
+    analyzer::{
+        classify_request_error, classify_status_error, frame_budget, http_client, sample_frames, validate_with_correction,
+        AnalysisRequest, Correction, SampledFrame, VideoAnalyzer,
+    },
+    prompt::build_frames_prompt,
+    types::{AnalyzerBackend, GeminiError, HttpAnalyzerConfig},
+    validation::ValidatedTimeline,
+};
+use futures_util::future::{BoxFuture, FutureExt};
+use serde::Deserialize;
+use serde_json::json;
+
+/// Local servers have no quota; wait at most this long on 429/503 hints
+const LOCAL_MAX_WAIT_SECONDS: u64 = 60;
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    message: Option<ChatMessage>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessage {
+    content: String,
+}
+
+/**
+ * Local model server backend (Ollama API)
+ */
+pub struct LocalAnalyzer {
+    config: HttpAnalyzerConfig,
+}
+
+impl LocalAnalyzer {
+    pub fn new(config: HttpAnalyzerConfig) -> Self {
+        Self { config }
+    }
+
+    async fn analyze_frames(&self, request: &AnalysisRequest) -> Result<ValidatedTimeline, GeminiError> {
+        let video_path = request.video_path.clone();
+        let duration = request.video_duration_seconds;
+        let max_frames = frame_budget(self.config.max_frames, request.sampling_factor);
+
+        let frames = tokio::task::spawn_blocking(move || sample_frames(&video_path, duration, max_frames))
+            .await
+            .map_err(|e| GeminiError::Permanent {
+                message: format!("Frame sampling task failed: {}", e),
+            })?
+            .map_err(|e| GeminiError::Permanent { message: e })?;
+
+        let frames = &frames;
+        validate_with_correction(request, move |correction| {
+            self.request_timeline(&request.prompt, frames, correction)
+        })
+        .await
+    }
+
+    // Send the frame prompt and frames, return the reply text
+    // A correction continues the conversation after the rejected reply
+    async fn request_timeline(
+        &self,
+        timeline_prompt: &str,
+        frames: &[SampledFrame],
+        correction: Option<Correction>,
+    ) -> Result<String, GeminiError> {
+        let offsets: Vec<f64> = frames.iter().map(|f| f.offset_seconds).collect();
+        let prompt = build_frames_prompt(timeline_prompt, &offsets);
+
+        let mut messages = vec![json!({
+            "role": "user",
+            "content": prompt,
+            "images": frames.iter().map(|f| f.jpeg_base64.as_str()).collect::<Vec<_>>(),
+        })];
+        if let Some(correction) = correction {
+            messages.push(json!({"role": "assistant", "content": correction.previous_response}));
+            messages.push(json!({"role": "user", "content": correction.prompt}));
+        }
+
+        let body = json!({
+            "model": self.config.model,
+            "stream": false,
+            "format": "json",
+            "messages": messages,
+        });
+
+        let url = format!("{}/api/chat", self.config.base_url.trim_end_matches('/'));
+        log::info!(
+            "[LOCAL] Sending {} frames to {} (model: {})...",
+            frames.len(),
+            url,
+            self.config.model
+        );
+
+        let response = http_client()?
+            .post(&url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(classify_request_error)?;
+
+        let status = response.status();
+        log::info!("[LOCAL] Response status: {}", status);
+
+        let response_text = response.text().await.map_err(|e| GeminiError::Permanent {
+            message: format!("Failed to read response: {}", e),
+        })?;
+
+        let parsed = serde_json::from_str::<ChatResponse>(&response_text);
+
+        if !status.is_success() {
+            let error_message = match parsed.ok().and_then(|r| r.error) {
+                Some(error) => format!("Local model error ({}): {}", status.as_u16(), error),
+                None => format!("Local model request failed with status {}: {}", status, response_text),
+            };
+            return Err(classify_status_error(status.as_u16(), error_message, LOCAL_MAX_WAIT_SECONDS));
+        }
+
+        let text = parsed
+            .map_err(|e| GeminiError::Permanent {
+                message: format!("Failed to parse local model response: {}", e),
+            })?
+            .message
+            .map(|m| m.content)
+            .ok_or_else(|| GeminiError::Permanent {
+                message: "No message in local model response".to_string(),
+            })?;
+
+        Ok(text)
+    }
+}
+
+impl VideoAnalyzer for LocalAnalyzer {
+    fn backend(&self) -> AnalyzerBackend {
+        AnalyzerBackend::Local
+    }
+
+    fn model(&self) -> &str {
+        &self.config.model
+    }
+
+    fn analyze<'a>(&'a self, request: &'a AnalysisRequest) -> BoxFuture<'a, Result<ValidatedTimeline, GeminiError>> {
+        self.analyze_frames(request).boxed()
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::gemini::analyzer::{serve_once, test_frames};
+    use crate::recording::gemini::client::parse_timeline_json;
+
+    fn analyzer(base_url: String) -> LocalAnalyzer {
+        LocalAnalyzer::new(HttpAnalyzerConfig {
+            base_url,
+            model: "qwen2.5vl".to_string(),
+            max_frames: 12,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_request_timeline_against_stand_in_server() {
+        let (base_url, server) = serve_once(
+            200,
+            r##"{"model": "qwen2.5vl", "message": {"role": "assistant", "content": "{\"timeline\": [{\"startTime\": \"00:00\", \"endTime\": \"00:30\", \"description\": \"Reading docs\", \"activeApplication\": \"Firefox\", \"activeWindowTitle\": \"Rust Book\", \"productiveScore\": 4}, {\"startTime\": \"00:30\", \"endTime\": \"01:00\", \"description\": \"Chat\", \"activeApplication\": \"Slack\", \"activeWindowTitle\": \"#general\", \"productiveScore\": 3}]}"}, "done": true}"##,
+        );
+
+        let text = analyzer(base_url).request_timeline("Timeline prompt", &test_frames(), None).await.unwrap();
+        let timeline = parse_timeline_json(&text).unwrap();
+        assert_eq!(timeline.len(), 2);
+        assert_eq!(timeline[1].active_application, "Slack");
+
+        let (request_line, body) = server.join().unwrap();
+        assert_eq!(request_line, "POST /api/chat HTTP/1.1");
+        assert_eq!(body["stream"], false);
+        assert_eq!(body["format"], "json");
+        assert_eq!(body["messages"][0]["images"], json!(["AAAA", "BBBB"]));
+    }
+
+    #[tokio::test]
+    async fn test_correction_continues_conversation() {
+        let (base_url, server) = serve_once(200, r#"{"message": {"role": "assistant", "content": "{}"}, "done": true}"#);
+
+        let correction = Correction {
+            previous_response: "{\"timeline\": []}".to_string(),
+            prompt: "Your previous response could not be used".to_string(),
+        };
+        analyzer(base_url)
+            .request_timeline("Timeline prompt", &test_frames(), Some(correction))
+            .await
+            .unwrap();
+
+        let (_, body) = server.join().unwrap();
+        let messages = body["messages"].as_array().unwrap();
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[1]["role"], "assistant");
+        assert_eq!(messages[1]["content"], "{\"timeline\": []}");
+        assert_eq!(messages[2]["content"], "Your previous response could not be used");
+    }
+
+    #[tokio::test]
+    async fn test_request_timeline_unknown_model_is_permanent() {
+        let (base_url, server) = serve_once(404, r#"{"error": "model \"qwen2.5vl\" not found, try pulling it first"}"#);
+
+        let result = analyzer(base_url).request_timeline("Timeline prompt", &test_frames(), None).await;
+        server.join().unwrap();
+
+        match result {
+            Err(GeminiError::Permanent { message }) => assert!(message.contains("not found")),
+            other => panic!("expected permanent error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_server_is_retryable() {
+        // Bind and drop to get a port nothing listens on
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+
+        let result = analyzer(format!("http://127.0.0.1:{}", port))
+            .request_timeline("Timeline prompt", &test_frames(), None)
+            .await;
+        assert!(matches!(result, Err(GeminiError::ServiceUnavailable { .. })));
+    }
+}
@@ -3,22 +3,39 @@
  * GEMINI AI INTEGRATION MODULE
  * ============================================================================
  * 
- * PURPOSE: Integrate Google Gemini AI for screen recording timeline analysis
+ * PURPOSE: AI timeline analysis of screen recordings (Gemini by default,
+ * OpenAI-compatible or local model servers per install)
  * 
  * SUBMODULES:
  * - types: Data structures for timeline analysis
  * - embedded_key: Build-time embedded API key (generated by build.rs)
- * - prompt: Timeline analysis prompt builder
+ * - prompt: Versioned prompt templates and prompt builder
+ * - analyzer: VideoAnalyzer trait and backend selection
  * - client: Gemini API client for video analysis
+ * - files: Gemini Files API resumable upload for large videos
+ * - openai: OpenAI-compatible chat completions backend (sampled frames)
+ * - local: Local model server backend, Ollama API (sampled frames)
+ * - validation: Timeline validation, repair and quality score
+ * - idle: AFK check before analysis and synthetic away timelines
+ * - context: ActivityWatch window switches for the analysis prompt
+ * - fusion: Snap timelines to ActivityWatch window events, confidence
+ * - results: Analyses saved as segment sidecars, re-sending to the collector
+ * - reanalysis: Analyze past segments again with a new prompt or model
+ * - limiter: Token bucket pacing shared by queue workers
+ * - store: Durable job state journal (pending, in flight, failed)
+ * - usage: Token usage ledger, cost estimates and budget caps
  * - queue: Async job processing with retry logic
  * - formatter: Convert timeline to InfluxDB line protocol
+ * - fixtures: Jobs and analyses shared by the module tests (tests only)
  * 
  * FLOW:
  * 1. Recording segment completes -> job queued
- * 2. Queue processor uploads video to Gemini
- * 3. Gemini returns timeline JSON
- * 4. Timeline converted to line protocol
- * 5. Events sent to collector
+ * 2. Queue processor sends video (or sampled frames) to the configured backend
+ * 3. Backend returns timeline JSON, validated and repaired
+ * 4. Timeline fused with ActivityWatch window events
+ * 5. Analysis saved as a segment sidecar
+ * 6. Timeline converted to line protocol
+ * 7. Events sent to collector
  * 
  * API KEY PRIORITY:
  * 1. GEMINI_API_KEY environment variable (runtime override)
@@ -30,13 +47,28 @@
 pub mod types;
 pub mod embedded_key;
 pub mod prompt;
+pub mod analyzer;
 pub mod client;
+pub mod files;
+pub mod openai;
+pub mod local;
+pub mod validation;
+pub mod idle;
+pub mod context;
+pub mod fusion;
+pub mod results;
+pub mod reanalysis;
+pub mod limiter;
+pub mod store;
+pub mod usage;
 pub mod queue;
 pub mod formatter;
+#[cfg(test)]
+pub(crate) mod fixtures;
 
 // Re-export commonly used types
-pub use types::{TimelineEntry, TimelineAnalysis, GeminiJob, GeminiJobStatus, GeminiConfig};
-pub use queue::{submit_job, init_queue, shutdown_queue, get_queue_status, QueueStatus};
+pub use types::{TimelineEntry, TimelineAnalysis, GeminiJob, GeminiJobStatus, GeminiConfig, DeadLetterJob};
+pub use queue::{submit_job, init_queue, shutdown_queue, get_queue_status, QueueStatus, QueueListing};
 
 // =============================================================================
 // API Key Functions
@@ -132,3 +164,21 @@ pub fn has_api_key_with_app(app: &tauri::AppHandle) -> bool {
     // Fall back to standard has_api_key() which checks env var and embedded key
     has_api_key()
 }
+
+/**
+ * Get the API key for the OpenAI-compatible backend, if any
+ * Self-hosted endpoints often need none
+ * 
+ * # Priority
+ * 1. User-provided key from secure storage (settings)
+ * 2. OPENAI_API_KEY environment variable
+ */
+pub fn get_openai_api_key(app: Option<&tauri::AppHandle>) -> Option<String> {
+    if let Some(app_handle) = app {
+        if let Ok(Some(key)) = crate::recording::config::load_openai_api_key(app_handle) {
+            return Some(key);
+        }
+    }
+    
+    std::env::var("OPENAI_API_KEY").ok().filter(|key| !key.is_empty())
+}
@@ -0,0 +1,239 @@
+/**
+ * ============================================================================
+ * OPENAI-COMPATIBLE ANALYZER MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Timeline analysis via any OpenAI-compatible multimodal endpoint
+ *
+ * API ENDPOINT:
+ * POST {base_url}/chat/completions
+ *
+ * Works with OpenAI, Azure OpenAI proxies, vLLM, LM Studio and llama.cpp's
+ * server. Chat completions don't accept video, so sampled frames are sent
+ * as base64 JPEG image_url parts alongside the frame prompt.
+ *
+ * ============================================================================
+ */
+
+use crate::recording::gemini::{
+    analyzer::{
+        classify_request_error, classify_status_error, frame_budget, http_client, sample_frames, validate_with_correction,
+        AnalysisRequest, Correction, SampledFrame, VideoAnalyzer,
+    },
+    prompt::build_frames_prompt,
+    types::{AnalyzerBackend, GeminiError, HttpAnalyzerConfig},
+    validation::ValidatedTimeline,
+};
+use futures_util::future::{BoxFuture, FutureExt};
+use serde::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    choices: Option<Vec<Choice>>,
+    error: Option<ApiErrorBody>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Choice {
+    message: ChoiceMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChoiceMessage {
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiErrorBody {
+    message: String,
+}
+
+/**
+ * OpenAI-compatible backend
+ */
+pub struct OpenAiCompatibleAnalyzer {
+    config: HttpAnalyzerConfig,
+    api_key: Option<String>,
+    rate_limit_max_wait_seconds: u64,
+}
+
+impl OpenAiCompatibleAnalyzer {
+    pub fn new(config: HttpAnalyzerConfig, api_key: Option<String>, rate_limit_max_wait_seconds: u64) -> Self {
+        Self {
+            config,
+            api_key,
+            rate_limit_max_wait_seconds,
+        }
+    }
+
+    async fn analyze_frames(&self, request: &AnalysisRequest) -> Result<ValidatedTimeline, GeminiError> {
+        let video_path = request.video_path.clone();
+        let duration = request.video_duration_seconds;
+        let max_frames = frame_budget(self.config.max_frames, request.sampling_factor);
+
+        let frames = tokio::task::spawn_blocking(move || sample_frames(&video_path, duration, max_frames))
+            .await
+            .map_err(|e| GeminiError::Permanent {
+                message: format!("Frame sampling task failed: {}", e),
+            })?
+            .map_err(|e| GeminiError::Permanent { message: e })?;
+
+        let frames = &frames;
+        validate_with_correction(request, move |correction| {
+            self.request_timeline(&request.prompt, frames, correction)
+        })
+        .await
+    }
+
+    // Send the frame prompt and frames, return the reply text
+    // A correction continues the conversation after the rejected reply
+    async fn request_timeline(
+        &self,
+        timeline_prompt: &str,
+        frames: &[SampledFrame],
+        correction: Option<Correction>,
+    ) -> Result<String, GeminiError> {
+        let offsets: Vec<f64> = frames.iter().map(|f| f.offset_seconds).collect();
+        let prompt = build_frames_prompt(timeline_prompt, &offsets);
+
+        let mut content = vec![json!({"type": "text", "text": prompt})];
+        content.extend(frames.iter().map(|frame| {
+            json!({
+                "type": "image_url",
+                "image_url": {"url": format!("data:image/jpeg;base64,{}", frame.jpeg_base64)},
+            })
+        }));
+
+        let mut messages = vec![json!({"role": "user", "content": content})];
+        if let Some(correction) = correction {
+            messages.push(json!({"role": "assistant", "content": correction.previous_response}));
+            messages.push(json!({"role": "user", "content": correction.prompt}));
+        }
+
+        let body = json!({
+            "model": self.config.model,
+            "messages": messages,
+            "temperature": 0.2,
+        });
+
+        let url = format!("{}/chat/completions", self.config.base_url.trim_end_matches('/'));
+        log::info!(
+            "[OPENAI] Sending {} frames to {} (model: {})...",
+            frames.len(),
+            url,
+            self.config.model
+        );
+
+        let mut http_request = http_client()?.post(&url).json(&body);
+        if let Some(key) = &self.api_key {
+            http_request = http_request.bearer_auth(key);
+        }
+
+        let response = http_request.send().await.map_err(classify_request_error)?;
+
+        let status = response.status();
+        log::info!("[OPENAI] Response status: {}", status);
+
+        let response_text = response.text().await.map_err(|e| GeminiError::Permanent {
+            message: format!("Failed to read response: {}", e),
+        })?;
+
+        let parsed = serde_json::from_str::<ChatCompletionResponse>(&response_text);
+
+        if !status.is_success() {
+            let error_message = match parsed.ok().and_then(|r| r.error) {
+                Some(error) => format!("Analyzer API error ({}): {}", status.as_u16(), error.message),
+                None => format!("Analyzer request failed with status {}: {}", status, response_text),
+            };
+            return Err(classify_status_error(status.as_u16(), error_message, self.rate_limit_max_wait_seconds));
+        }
+
+        let text = parsed
+            .map_err(|e| GeminiError::Permanent {
+                message: format!("Failed to parse chat completion response: {}", e),
+            })?
+            .choices
+            .and_then(|c| c.into_iter().next())
+            .and_then(|c| c.message.content)
+            .ok_or_else(|| GeminiError::Permanent {
+                message: "No text content in chat completion response".to_string(),
+            })?;
+
+        Ok(text)
+    }
+}
+
+impl VideoAnalyzer for OpenAiCompatibleAnalyzer {
+    fn backend(&self) -> AnalyzerBackend {
+        AnalyzerBackend::OpenaiCompatible
+    }
+
+    fn model(&self) -> &str {
+        &self.config.model
+    }
+
+    fn analyze<'a>(&'a self, request: &'a AnalysisRequest) -> BoxFuture<'a, Result<ValidatedTimeline, GeminiError>> {
+        self.analyze_frames(request).boxed()
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::gemini::analyzer::{serve_once, test_frames};
+    use crate::recording::gemini::client::parse_timeline_json;
+
+    fn analyzer(base_url: String, api_key: Option<&str>) -> OpenAiCompatibleAnalyzer {
+        OpenAiCompatibleAnalyzer::new(
+            HttpAnalyzerConfig {
+                base_url: format!("{}/v1", base_url),
+                model: "llava".to_string(),
+                max_frames: 12,
+            },
+            api_key.map(str::to_string),
+            120,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_request_timeline_against_stand_in_server() {
+        let (base_url, server) = serve_once(
+            200,
+            r#"{"choices": [{"message": {"role": "assistant", "content": "```json\n{\"timeline\": [{\"startTime\": \"00:00\", \"endTime\": \"01:00\", \"description\": \"Editing code\", \"activeApplication\": \"VS Code\", \"activeWindowTitle\": \"main.rs\", \"productiveScore\": 5}]}\n```"}}]}"#,
+        );
+
+        let text = analyzer(base_url, Some("sk-test"))
+            .request_timeline("Timeline prompt", &test_frames(), None)
+            .await
+            .unwrap();
+        let timeline = parse_timeline_json(&text).unwrap();
+        assert_eq!(timeline.len(), 1);
+        assert_eq!(timeline[0].active_application, "VS Code");
+
+        let (request_line, body) = server.join().unwrap();
+        assert_eq!(request_line, "POST /v1/chat/completions HTTP/1.1");
+        assert_eq!(body["model"], "llava");
+        let content = body["messages"][0]["content"].as_array().unwrap();
+        assert_eq!(content.len(), 3);
+        assert!(content[0]["text"].as_str().unwrap().contains("- Image 2: 00:30"));
+        assert_eq!(content[2]["image_url"]["url"], "data:image/jpeg;base64,BBBB");
+    }
+
+    #[tokio::test]
+    async fn test_request_timeline_rate_limited() {
+        let (base_url, server) = serve_once(429, r#"{"error": {"message": "Rate limit reached. Please retry in 20s."}}"#);
+
+        let result = analyzer(base_url, None).request_timeline("Timeline prompt", &test_frames(), None).await;
+        server.join().unwrap();
+
+        match result {
+            Err(GeminiError::RateLimited { retry_after, .. }) => assert_eq!(retry_after.unwrap().as_secs(), 21),
+            other => panic!("expected rate limit, got {:?}", other),
+        }
+    }
+}
@@ -2,94 +2,529 @@
  * ============================================================================
  * GEMINI PROMPT MODULE
  * ============================================================================
- * 
- * PURPOSE: Build prompts for Gemini video analysis
- * 
+ *
+ * PURPOSE: Build prompts for Gemini video analysis from versioned templates
+ *
  * PROMPT STRATEGY:
  * - Instruct Gemini to analyze entire video duration
  * - Extract timeline of activities with productivity scores
  * - Return structured JSON for parsing
- * 
+ * - Frame-based backends get the same prompt plus a frame/timestamp legend
+ *
+ * TEMPLATES:
+ * - Built-in template ships with the app (prompts/timeline_v<N>.txt); every
+ *   version stays available, "builtin" selects the newest
+ * - User templates live in app_data_dir/prompt_templates/<name>/v<N>.txt;
+ *   saving a template always writes a new version, old ones are kept
+ * - GeminiConfig.prompt_template selects "name" (latest) or "name@N"
+ * - Every analysis records the template used as prompt_version ("name@N")
+ *
+ * VARIABLES ({{name}} in a template):
+ * - duration, duration_seconds: Length of the video sent ("MM:SS" / whole
+ *   seconds; the sped-up length when re-encoded)
+ * - speed: Playback speed of the video (1 = real time)
+ * - speed_note, duration_note: Standard instructions for the above
+ * - window_context: App and window title switches from ActivityWatch, in
+ *   video time (empty without ActivityWatch data)
+ * - hostname, local_time: Machine and local start time of the recording
+ * - rubric: Productivity rubric (GeminiConfig.prompt_rubric or built-in)
+ *
  * ============================================================================
  */
 
+use crate::recording::gemini::{context::WindowSwitch, types::GeminiConfig};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+/// Name of the template that ships with the app
+pub const BUILTIN_TEMPLATE: &str = "builtin";
+
+/// Built-in template bodies by version, oldest first (2: window context)
+const BUILTIN_TEMPLATES: &[(u32, &str)] = &[
+    (1, include_str!("prompts/timeline_v1.txt")),
+    (2, include_str!("prompts/timeline_v2.txt")),
+];
+
+/// Productivity rubric used when the install doesn't set its own
+pub const DEFAULT_RUBRIC: &str = "Guidelines for productiveScore (1-5 levels):
+- 5: Highly Productive - Coding, writing documents, focused work, learning, deep concentration tasks
+- 4: Productive - Email for work, work meetings, documentation, communication tools for work tasks
+- 3: Neutral - General browsing for research, reading articles, light administrative tasks
+- 2: Low Productivity - Casual browsing, social media for short breaks, off-topic reading
+- 1: Distraction - Entertainment, gaming, extended social media use, watching videos unrelated to work";
+
+/// Variables a template may use
+const TEMPLATE_VARIABLES: &[&str] = &[
+    "duration",
+    "duration_seconds",
+    "speed",
+    "speed_note",
+    "duration_note",
+    "window_context",
+    "hostname",
+    "local_time",
+    "rubric",
+];
+
+/// Output fields the timeline parser needs; every template must ask for them
+const REQUIRED_OUTPUT_FIELDS: &[&str] = &[
+    "timeline",
+    "startTime",
+    "endTime",
+    "description",
+    "activeApplication",
+    "activeWindowTitle",
+    "productiveScore",
+];
+
+/// Directory under app_data_dir holding user templates
+const TEMPLATES_DIR: &str = "prompt_templates";
+
+// =============================================================================
+// Templates
+// =============================================================================
+
 /**
- * Format duration in seconds to "MM:SS" string
- * 
- * # Arguments
- * * `seconds` - Duration in seconds
- * 
- * # Returns
- * String in "MM:SS" format
+ * A versioned prompt template
  */
-pub fn format_duration(seconds: f64) -> String {
-    let total_seconds = seconds.round() as u64;
-    let minutes = total_seconds / 60;
-    let secs = total_seconds % 60;
-    format!("{:02}:{:02}", minutes, secs)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PromptTemplate {
+    pub name: String,
+    pub version: u32,
+    pub body: String,
 }
 
 /**
- * Build the timeline analysis prompt for Gemini
- * 
- * # Arguments
- * * `playback_speed` - Video playback speed (1.0 = normal)
- * * `video_duration_seconds` - Total video duration in seconds
- * 
- * # Returns
- * Complete prompt string for Gemini API
+ * Values substituted into a template for one video
  */
-pub fn build_timeline_prompt(playback_speed: f64, video_duration_seconds: f64) -> String {
-    let speed_note = if playback_speed > 1.0 {
-        "\n\nIMPORTANT: The timestamps you provide should be based on the video's actual playback time (as if played at normal 1x speed).".to_string()
+#[derive(Debug, Clone)]
+pub struct PromptContext {
+    pub playback_speed: f64,
+    pub video_duration_seconds: f64,
+    pub hostname: String,
+    pub local_time: String,
+    pub rubric: Option<String>,
+    /// Window switches, offsets in real seconds from the video start
+    pub window_switches: Vec<WindowSwitch>,
+}
+
+impl PromptTemplate {
+    /// Newest built-in template
+    pub fn builtin() -> Self {
+        let (version, _) = BUILTIN_TEMPLATES[BUILTIN_TEMPLATES.len() - 1];
+        Self::builtin_version(version).expect("built-in template")
+    }
+
+    /// A built-in template version (None if it never shipped)
+    pub fn builtin_version(version: u32) -> Option<Self> {
+        BUILTIN_TEMPLATES
+            .iter()
+            .find(|(v, _)| *v == version)
+            .map(|(version, body)| Self {
+                name: BUILTIN_TEMPLATE.to_string(),
+                version: *version,
+                body: body.to_string(),
+            })
+    }
+
+    /// Identifier recorded on analyses, e.g. "builtin@2"
+    pub fn prompt_version(&self) -> String {
+        format!("{}@{}", self.name, self.version)
+    }
+
+    /**
+     * Render the prompt for one video
+     */
+    pub fn render(&self, context: &PromptContext) -> String {
+        let duration_str = format_duration(context.video_duration_seconds);
+        let mut output = String::with_capacity(self.body.len() + DEFAULT_RUBRIC.len());
+        let mut rest = self.body.as_str();
+
+        while let Some((before, name, after)) = next_variable(rest) {
+            output.push_str(before);
+            match name {
+                "duration" => output.push_str(&duration_str),
+                "duration_seconds" => output.push_str(&(context.video_duration_seconds.round() as u64).to_string()),
+                "speed" => output.push_str(&context.playback_speed.to_string()),
+                "speed_note" => output.push_str(&speed_note(context.playback_speed)),
+                "duration_note" => output.push_str(&duration_note(context.video_duration_seconds)),
+                "window_context" => output.push_str(&window_context(&context.window_switches, context.playback_speed)),
+                "hostname" => output.push_str(&context.hostname),
+                "local_time" => output.push_str(&context.local_time),
+                "rubric" => output.push_str(context.rubric.as_deref().unwrap_or(DEFAULT_RUBRIC)),
+                // Unknown names are rejected by validate(); keep them verbatim
+                _ => {
+                    output.push_str("{{");
+                    output.push_str(name);
+                    output.push_str("}}");
+                }
+            }
+            rest = after;
+        }
+        output.push_str(rest);
+        output
+    }
+
+    /**
+     * Check that the template only uses known variables and asks for every
+     * field the timeline parser needs
+     */
+    pub fn validate(&self) -> Result<(), String> {
+        if self.body.trim().is_empty() {
+            return Err("Prompt template is empty".to_string());
+        }
+
+        let mut rest = self.body.as_str();
+        while let Some((_, name, after)) = next_variable(rest) {
+            if !TEMPLATE_VARIABLES.contains(&name) {
+                return Err(format!(
+                    "Unknown template variable '{{{{{}}}}}' (available: {})",
+                    name,
+                    TEMPLATE_VARIABLES.join(", ")
+                ));
+            }
+            rest = after;
+        }
+
+        let missing: Vec<&str> = REQUIRED_OUTPUT_FIELDS
+            .iter()
+            .copied()
+            .filter(|field| !self.body.contains(field))
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!("Prompt template doesn't ask for output field(s): {}", missing.join(", ")));
+        }
+
+        Ok(())
+    }
+}
+
+// Split at the next {{variable}}: (text before, variable name, text after)
+// Braces not enclosing an identifier (e.g. JSON examples) are plain text
+fn next_variable(text: &str) -> Option<(&str, &str, &str)> {
+    let mut search_from = 0;
+    while let Some(start) = text[search_from..].find("{{").map(|i| i + search_from) {
+        let name_start = start + 2;
+        if let Some(len) = text[name_start..].find("}}") {
+            let name = &text[name_start..name_start + len];
+            if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Some((&text[..start], name, &text[name_start + len + 2..]));
+            }
+        }
+        search_from = start + 1;
+    }
+    None
+}
+
+fn speed_note(playback_speed: f64) -> String {
+    if playback_speed > 1.0 {
+        format!(
+            "\n\nIMPORTANT: This video plays at {}x speed, so every activity appears {} times shorter than it really was. Give timestamps as they appear in this video; they are converted to real time afterwards.",
+            playback_speed, playback_speed
+        )
     } else {
         String::new()
-    };
+    }
+}
 
-    let duration_str = format_duration(video_duration_seconds);
-    let duration_note = if video_duration_seconds > 0.0 {
+fn duration_note(video_duration_seconds: f64) -> String {
+    if video_duration_seconds > 0.0 {
+        let duration_str = format_duration(video_duration_seconds);
+        let long_note = if video_duration_seconds.round() >= 3600.0 {
+            " Write times from one hour on as \"HH:MM:SS\"."
+        } else {
+            ""
+        };
         format!(
-            "\n\nVIDEO DURATION: This video is exactly {} ({} seconds) long. Your timeline MUST cover from 00:00 to {}. The last entry's endTime must be {} or very close to it.",
+            "\n\nVIDEO DURATION: This video is exactly {} ({} seconds) long. Your timeline MUST cover from 00:00 to {}. The last entry's endTime must be {} or very close to it.{}",
             duration_str,
             video_duration_seconds.round() as u64,
             duration_str,
-            duration_str
+            duration_str,
+            long_note
         )
     } else {
         String::new()
+    }
+}
+
+// =============================================================================
+// Template Storage
+// =============================================================================
+
+/// Directory holding user templates
+pub fn templates_dir(app: &tauri::AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .expect("app_data_dir available")
+        .join(TEMPLATES_DIR)
+}
+
+/**
+ * List the built-in template and every saved version of the user templates
+ */
+pub fn list_templates(dir: &Path) -> Vec<PromptTemplate> {
+    let mut templates: Vec<PromptTemplate> = BUILTIN_TEMPLATES
+        .iter()
+        .filter_map(|(version, _)| PromptTemplate::builtin_version(*version))
+        .collect();
+
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| validate_name(name).is_ok())
+        .collect();
+    names.sort();
+
+    for name in names {
+        for version in template_versions(dir, &name) {
+            match read_template(dir, &name, version) {
+                Ok(template) => templates.push(template),
+                Err(e) => log::warn!("[PROMPT] Skipping template {}@{}: {}", name, version, e),
+            }
+        }
+    }
+
+    templates
+}
+
+/**
+ * Load the template selected by GeminiConfig.prompt_template
+ *
+ * # Arguments
+ * * `dir` - User template directory
+ * * `selection` - None = built-in, "name" = latest version, "name@N" = version N
+ */
+pub fn load_template(dir: &Path, selection: Option<&str>) -> Result<PromptTemplate, String> {
+    let Some(selection) = selection.map(str::trim).filter(|s| !s.is_empty()) else {
+        return Ok(PromptTemplate::builtin());
     };
 
-    format!(
-        r#"Analyze this screen recording video and extract a timeline of activities.{}{}
+    let (name, version) = match selection.split_once('@') {
+        Some((name, version)) => {
+            let version: u32 = version
+                .parse()
+                .map_err(|_| format!("Invalid prompt template version in '{}'", selection))?;
+            (name, Some(version))
+        }
+        None => (selection, None),
+    };
 
-CRITICAL: You MUST analyze and provide timeline entries for the ENTIRE video from start to finish. Do NOT truncate, cut off, or skip any portion of the video.
+    if name == BUILTIN_TEMPLATE {
+        return match version {
+            Some(v) => PromptTemplate::builtin_version(v)
+                .ok_or_else(|| format!("Prompt template {}@{} does not exist", BUILTIN_TEMPLATE, v)),
+            None => Ok(PromptTemplate::builtin()),
+        };
+    }
 
-VALIDATION: Before responding, verify your timeline:
-1. Does your first entry start at "00:00"?
-2. Does your last entry end at or near "{}"?
-3. Are there any gaps between entries?
-If any of these checks fail, re-analyze the video and fix your response.
+    validate_name(name)?;
+    let version = match version {
+        Some(version) => version,
+        None => *template_versions(dir, name)
+            .last()
+            .ok_or_else(|| format!("Prompt template '{}' does not exist", name))?,
+    };
 
-For each distinct activity or context switch you observe, create a timeline entry with:
-- startTime: When this activity started in the video (format: "MM:SS")
-- endTime: When this activity ended in the video (format: "MM:SS")
-- description: Short concise context of what the user is doing that you have seen in the screen. Highlight the content that can cause negative sentiment or damaging company reputation.
-- activeApplication: The application being used (e.g., "VS Code", "Chrome", "Terminal", "Slack")
-- activeWindowTitle: The window title or tab name if visible
-- productiveScore: A productivity level from 1-5 (integer only). Note that if the distraction is coming from a productive task for example error in a software is considered productive.
+    let template = read_template(dir, name, version)?;
+    template.validate()?;
+    Ok(template)
+}
 
-Guidelines for productiveScore (1-5 levels):
-- 5: Highly Productive - Coding, writing documents, focused work, learning, deep concentration tasks
-- 4: Productive - Email for work, work meetings, documentation, communication tools for work tasks
-- 3: Neutral - General browsing for research, reading articles, light administrative tasks
-- 2: Low Productivity - Casual browsing, social media for short breaks, off-topic reading
-- 1: Distraction - Entertainment, gaming, extended social media use, watching videos unrelated to work
+/**
+ * Save a template body as the next version of `name`
+ * Earlier versions stay on disk so past analyses remain reproducible
+ */
+pub fn save_template(dir: &Path, name: &str, body: &str) -> Result<PromptTemplate, String> {
+    validate_name(name)?;
+    if name == BUILTIN_TEMPLATE {
+        return Err(format!("'{}' is reserved for the built-in template", BUILTIN_TEMPLATE));
+    }
 
-Return ONLY a valid JSON object with this exact structure (no markdown, no code blocks):
-{{"timeline": [{{"startTime": "00:00", "endTime": "00:30", "description": "...", "activeApplication": "...", "activeWindowTitle": "...", "productiveScore": 5}}]}}"#,
-        speed_note,
-        duration_note,
-        duration_str
+    let mut template = PromptTemplate {
+        name: name.to_string(),
+        version: 0,
+        body: body.to_string(),
+    };
+    template.validate()?;
+
+    let template_dir = dir.join(name);
+    std::fs::create_dir_all(&template_dir)
+        .map_err(|e| format!("Failed to create template directory: {}", e))?;
+
+    template.version = template_versions(dir, name).last().copied().unwrap_or(0) + 1;
+    let path = template_path(dir, name, template.version);
+
+    // create_new: never overwrite a version that analyses may refer to
+    use std::io::Write;
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to create {:?}: {}", path, e))?;
+    file.write_all(body.as_bytes())
+        .map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+
+    log::info!("[PROMPT] Saved prompt template {}", template.prompt_version());
+    Ok(template)
+}
+
+/**
+ * Template to use for the next analysis
+ * Fails if the selected one was removed or edited into an invalid state on
+ * disk; analyses are never tagged with a prompt that wasn't selected
+ */
+pub fn resolve_template(app: Option<&tauri::AppHandle>, config: &GeminiConfig) -> Result<PromptTemplate, String> {
+    let Some(selection) = config.prompt_template.as_deref() else {
+        return Ok(PromptTemplate::builtin());
+    };
+
+    let dir = app.map(templates_dir).unwrap_or_default();
+    load_template(&dir, Some(selection))
+        .map_err(|e| format!("Prompt template '{}' unusable: {}", selection, e))
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty()
+        || name.len() > 64
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "Invalid prompt template name '{}' (use letters, digits, '-' and '_')",
+            name
+        ));
+    }
+    Ok(())
+}
+
+fn template_path(dir: &Path, name: &str, version: u32) -> PathBuf {
+    dir.join(name).join(format!("v{}.txt", version))
+}
+
+// Saved versions of a template, ascending
+fn template_versions(dir: &Path, name: &str) -> Vec<u32> {
+    let mut versions: Vec<u32> = std::fs::read_dir(dir.join(name))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let file_name = entry.file_name().into_string().ok()?;
+            file_name.strip_prefix('v')?.strip_suffix(".txt")?.parse().ok()
+        })
+        .collect();
+    versions.sort_unstable();
+    versions
+}
+
+fn read_template(dir: &Path, name: &str, version: u32) -> Result<PromptTemplate, String> {
+    let path = template_path(dir, name, version);
+    let body = std::fs::read_to_string(&path).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            format!("Prompt template {}@{} does not exist", name, version)
+        } else {
+            format!("Failed to read {:?}: {}", path, e)
+        }
+    })?;
+
+    Ok(PromptTemplate {
+        name: name.to_string(),
+        version,
+        body,
+    })
+}
+
+// =============================================================================
+// Prompt Builders
+// =============================================================================
+
+/**
+ * Format duration in seconds to "MM:SS" string ("HH:MM:SS" from one hour)
+ *
+ * # Arguments
+ * * `seconds` - Duration in seconds
+ *
+ * # Returns
+ * String in "MM:SS" or "HH:MM:SS" format
+ */
+pub fn format_duration(seconds: f64) -> String {
+    let total_seconds = seconds.round() as u64;
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let secs = total_seconds % 60;
+    if hours > 0 {
+        format!("{:02}:{:02}:{:02}", hours, minutes, secs)
+    } else {
+        format!("{:02}:{:02}", minutes, secs)
+    }
+}
+
+/**
+ * Build the timeline prompt for backends that receive sampled still frames
+ * instead of the video itself
+ *
+ * # Arguments
+ * * `timeline_prompt` - Rendered timeline prompt
+ * * `frame_offsets` - Video position of each attached frame, in order
+ */
+pub fn build_frames_prompt(timeline_prompt: &str, frame_offsets: &[f64]) -> String {
+    let legend: Vec<String> = frame_offsets
+        .iter()
+        .enumerate()
+        .map(|(i, offset)| format!("- Image {}: {}", i + 1, format_duration(*offset)))
+        .collect();
+
+    format!(
+        "The screen recording is provided as {} still images sampled in order from the video. Each image shows the screen at the video time listed below and represents the period until the next image:\n{}\n\n{}",
+        frame_offsets.len(),
+        legend.join("\n"),
+        timeline_prompt
+    )
+}
+
+/**
+ * Build the follow-up asking the model to fix a response that failed
+ * validation (sent once, after the model's previous reply)
+ *
+ * # Arguments
+ * * `error` - Why the previous response was rejected
+ * * `video_duration_seconds` - Total video duration in seconds
+ */
+pub fn build_correction_prompt(error: &str, video_duration_seconds: f64) -> String {
+    let coverage = if video_duration_seconds > 0.0 {
+        format!(" covering the whole video from 00:00 to {}", format_duration(video_duration_seconds))
+    } else {
+        String::new()
+    };
+
+    format!(
+        "Your previous response could not be used: {}\n\nReply again with ONLY the corrected JSON object{}. Every entry needs startTime and endTime in \"MM:SS\" format (\"HH:MM:SS\" from one hour on) and an integer productiveScore from 1 to 5.",
+        error, coverage
+    )
+}
+
+fn window_context(switches: &[WindowSwitch], playback_speed: f64) -> String {
+    if switches.is_empty() {
+        return String::new();
+    }
+
+    let lines: Vec<String> = switches
+        .iter()
+        .map(|s| {
+            format!(
+                "- {} {} — {}",
+                format_duration(s.offset_seconds / playback_speed.max(1.0)),
+                s.app,
+                s.title
+            )
+        })
+        .collect();
+    format!(
+        "\n\nWINDOW CONTEXT: The focused application and window title changed at these times in this video (recorded by the operating system, exact):\n{}\nUse these for activeApplication and activeWindowTitle and focus on describing what the user did. [REDACTED] marks private windows; don't describe their content.",
+        lines.join("\n")
     )
 }
 
@@ -101,6 +536,21 @@ Return ONLY a valid JSON object with this exact structure (no markdown, no code
 mod tests {
     use super::*;
 
+    fn context(playback_speed: f64, video_duration_seconds: f64) -> PromptContext {
+        PromptContext {
+            playback_speed,
+            video_duration_seconds,
+            hostname: "support-laptop".to_string(),
+            local_time: "2026-10-18 09:30".to_string(),
+            rubric: None,
+            window_switches: Vec::new(),
+        }
+    }
+
+    fn build_timeline_prompt(playback_speed: f64, video_duration_seconds: f64) -> String {
+        PromptTemplate::builtin().render(&context(playback_speed, video_duration_seconds))
+    }
+
     #[test]
     fn test_format_duration() {
         assert_eq!(format_duration(0.0), "00:00");
@@ -108,14 +558,16 @@ mod tests {
         assert_eq!(format_duration(60.0), "01:00");
         assert_eq!(format_duration(90.0), "01:30");
         assert_eq!(format_duration(300.0), "05:00");
-        assert_eq!(format_duration(3600.0), "60:00");
+        assert_eq!(format_duration(3599.0), "59:59");
+        assert_eq!(format_duration(3600.0), "01:00:00");
+        assert_eq!(format_duration(6330.0), "01:45:30"); // Past 99 minutes
         assert_eq!(format_duration(65.5), "01:06"); // Rounds to 66 seconds
     }
 
     #[test]
     fn test_build_timeline_prompt_basic() {
         let prompt = build_timeline_prompt(1.0, 300.0);
-        
+
         // Check key elements are present
         assert!(prompt.contains("Analyze this screen recording video"));
         assert!(prompt.contains("05:00"));
@@ -123,37 +575,148 @@ mod tests {
         assert!(prompt.contains("productiveScore"));
         assert!(prompt.contains("startTime"));
         assert!(prompt.contains("endTime"));
+        assert!(prompt.contains("5: Highly Productive"));
+        assert!(!prompt.contains("{{"));
     }
 
     #[test]
     fn test_build_timeline_prompt_with_speed() {
         let prompt = build_timeline_prompt(2.0, 300.0);
-        
+
         // Check speed note is included
-        assert!(prompt.contains("actual playback time"));
-        assert!(prompt.contains("normal 1x speed"));
+        assert!(prompt.contains("plays at 2x speed"));
+        assert!(prompt.contains("as they appear in this video"));
     }
 
     #[test]
     fn test_build_timeline_prompt_no_duration() {
         let prompt = build_timeline_prompt(1.0, 0.0);
-        
+
         // Duration note should not be present
         assert!(!prompt.contains("VIDEO DURATION"));
     }
 
+    #[test]
+    fn test_build_timeline_prompt_long_video() {
+        let prompt = build_timeline_prompt(1.0, 6000.0);
+
+        assert!(prompt.contains("exactly 01:40:00 (6000 seconds)"));
+        assert!(prompt.contains("\"HH:MM:SS\""));
+        assert!(!build_timeline_prompt(1.0, 300.0).contains("\"HH:MM:SS\""));
+    }
+
+    #[test]
+    fn test_build_frames_prompt() {
+        let prompt = build_frames_prompt(&build_timeline_prompt(1.0, 60.0), &[0.0, 30.0]);
+
+        assert!(prompt.starts_with("The screen recording is provided as 2 still images"));
+        assert!(prompt.contains("- Image 1: 00:00\n- Image 2: 00:30"));
+        assert!(prompt.contains("VIDEO DURATION: This video is exactly 01:00"));
+    }
+
+    #[test]
+    fn test_build_correction_prompt() {
+        let prompt = build_correction_prompt("Entry 2 has productiveScore 7, which is outside 1-5", 300.0);
+
+        assert!(prompt.contains("Entry 2 has productiveScore 7"));
+        assert!(prompt.contains("from 00:00 to 05:00"));
+        assert!(!build_correction_prompt("Bad JSON", 0.0).contains("00:00 to"));
+    }
+
     #[test]
     fn test_prompt_json_structure() {
         let prompt = build_timeline_prompt(1.0, 60.0);
-        
+
         // Verify JSON example structure
-        assert!(prompt.contains(r#""timeline""#));
-        assert!(prompt.contains(r#""startTime""#));
+        assert!(prompt.contains(r#"{"timeline": [{"startTime": "00:00""#));
         assert!(prompt.contains(r#""endTime""#));
         assert!(prompt.contains(r#""description""#));
         assert!(prompt.contains(r#""activeApplication""#));
         assert!(prompt.contains(r#""activeWindowTitle""#));
         assert!(prompt.contains(r#""productiveScore""#));
     }
-}
 
+    #[test]
+    fn test_render_variables_and_rubric() {
+        let template = PromptTemplate {
+            name: "support".to_string(),
+            version: 2,
+            body: "Host {{hostname}} at {{local_time}}, {{duration}} ({{duration_seconds}}s) at {{speed}}x.\n{{rubric}}\nJSON: {{\"a\": 1}}".to_string(),
+        };
+        let mut context = context(4.0, 90.0);
+        context.rubric = Some("- 5: Answering tickets in Slack".to_string());
+
+        assert_eq!(
+            template.render(&context),
+            "Host support-laptop at 2026-10-18 09:30, 01:30 (90s) at 4x.\n- 5: Answering tickets in Slack\nJSON: {{\"a\": 1}}"
+        );
+        assert_eq!(template.prompt_version(), "support@2");
+    }
+
+    #[test]
+    fn test_window_context() {
+        let mut context = context(2.0, 150.0);
+        assert!(!build_timeline_prompt(2.0, 150.0).contains("WINDOW CONTEXT"));
+
+        context.window_switches = vec![
+            WindowSwitch {
+                offset_seconds: 0.0,
+                app: "Code".to_string(),
+                title: "main.rs".to_string(),
+            },
+            WindowSwitch {
+                offset_seconds: 130.0,
+                app: "Firefox".to_string(),
+                title: "Docs".to_string(),
+            },
+        ];
+        let prompt = PromptTemplate::builtin().render(&context);
+        // Offsets are converted to video time at 2x speed
+        assert!(prompt.contains("- 00:00 Code — main.rs\n- 01:05 Firefox — Docs\n"));
+        assert!(PromptTemplate::builtin().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_template() {
+        for (version, _) in BUILTIN_TEMPLATES {
+            assert!(PromptTemplate::builtin_version(*version).unwrap().validate().is_ok());
+        }
+
+        let mut template = PromptTemplate::builtin();
+        template.body = template.body.replace("{{rubric}}", "{{org_rubric}}");
+        assert!(template.validate().unwrap_err().contains("org_rubric"));
+
+        template.body = "Describe the video. {{rubric}}".to_string();
+        let error = template.validate().unwrap_err();
+        assert!(error.contains("timeline") && error.contains("productiveScore"));
+    }
+
+    #[test]
+    fn test_save_and_load_versions() {
+        let dir = std::env::temp_dir().join(format!("screenjournal-prompts-{}", uuid::Uuid::new_v4()));
+        let body_v1 = PromptTemplate::builtin().body;
+        let body_v2 = body_v1.replace("Analyze", "Carefully analyze");
+
+        assert_eq!(load_template(&dir, Some("builtin@1")).unwrap().prompt_version(), "builtin@1");
+        assert_eq!(load_template(&dir, None).unwrap().prompt_version(), "builtin@2");
+        assert!(load_template(&dir, Some("builtin@3")).is_err());
+        assert!(load_template(&dir, Some("support")).is_err());
+        assert!(save_template(&dir, "builtin", &body_v1).is_err());
+        assert!(save_template(&dir, "../escape", &body_v1).is_err());
+        assert!(save_template(&dir, "support", "no fields").is_err());
+
+        assert_eq!(save_template(&dir, "support", &body_v1).unwrap().version, 1);
+        assert_eq!(save_template(&dir, "support", &body_v2).unwrap().version, 2);
+
+        let latest = load_template(&dir, Some("support")).unwrap();
+        assert_eq!(latest.prompt_version(), "support@2");
+        assert!(latest.body.starts_with("Carefully"));
+        assert_eq!(load_template(&dir, Some("support@1")).unwrap().body, body_v1);
+        assert!(load_template(&dir, Some("support@3")).is_err());
+
+        let versions: Vec<String> = list_templates(&dir).iter().map(|t| t.prompt_version()).collect();
+        assert_eq!(versions, vec!["builtin@1", "builtin@2", "support@1", "support@2"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -7,39 +7,70 @@
  * 
  * FEATURES:
  * - Background processing (non-blocking)
+ * - Requests paced by rate_limit_per_minute (token bucket, limiter.rs)
  * - Exponential backoff retry on failures
+ * - Falls through the model chain when a model stays unavailable
  * - Persistent queue for crash recovery
+ * - Segments the user was away for (ActivityWatch AFK) get a synthetic away
+ *   timeline instead of an analysis, or are analyzed sped up (idle.rs)
+ * - Timelines fused with ActivityWatch window events before they are sent
+ *   (fusion.rs), and saved next to the segment (results.rs)
+ * - Reanalysis jobs (reanalysis.rs) use their own prompt and model and are
+ *   saved as new analysis versions
+ * - Token usage ledger; dispatching pauses at the daily/monthly budget cap
+ *   and emits gemini-budget-reached
  * - Graceful shutdown handling
  * 
  * ARCHITECTURE:
- * - MPSC channel for job submission
- * - Single background task processes jobs sequentially
- * - Jobs persisted to disk for recovery
+ * - Ordered pending list; submissions append, prioritization moves jobs
+ *   to the front
+ * - Dispatcher runs up to max_concurrent_jobs jobs in parallel
+ * - Rate-limit backoff pauses the shared limiter, so all workers wait together
+ * - Job state transitions journaled to disk (store.rs) for crash recovery
+ * - Permanently failed jobs kept in the store's dead-letter state for retry
  * 
  * ============================================================================
  */
 
 use crate::collector::{batch, config as collector_config};
 use crate::recording::gemini::{
-    client,
+    analyzer,
     formatter,
-    types::{GeminiConfig, GeminiError, GeminiJob},
+    fusion,
+    idle::{self, AfkSummary},
+    limiter::RateLimiter,
+    results,
+    store::{job_key, JobKey, JobStore},
+    types::{AnalyzerBackend, DeadLetterJob, GeminiConfig, GeminiError, GeminiJob, IdleAction, SpeedupMode, TokenUsage},
+    usage::{self, UsageLedger, UsageRecord, UsageTotals},
 };
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::{HashMap, VecDeque};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Manager};
-use tokio::sync::mpsc;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Notify;
+use tokio::task::{AbortHandle, JoinSet};
 use tokio::time::{sleep, Duration};
 
 // =============================================================================
 // Global State
 // =============================================================================
 
-/// Job submission channel
-static JOB_SENDER: Lazy<Mutex<Option<mpsc::Sender<GeminiJob>>>> = Lazy::new(|| Mutex::new(None));
+/// Jobs waiting for a worker, next job first
+static PENDING: Lazy<Mutex<VecDeque<GeminiJob>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// Jobs being processed, with the handle to cancel them
+static IN_FLIGHT: Lazy<Mutex<HashMap<JobKey, InFlightJob>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Wakes the dispatcher on new jobs and shutdown
+static JOB_AVAILABLE: Lazy<Notify> = Lazy::new(Notify::new);
+
+/// Whether the processor is accepting jobs
+static RUNNING: AtomicBool = AtomicBool::new(false);
 
 /// Shutdown signal
 static SHUTDOWN: Lazy<Arc<AtomicBool>> = Lazy::new(|| Arc::new(AtomicBool::new(false)));
@@ -53,6 +84,24 @@ static QUEUE_STATS: Lazy<Mutex<QueueStats>> = Lazy::new(|| Mutex::new(QueueStats
 /// App handle for storage paths
 static APP_HANDLE: Lazy<Mutex<Option<AppHandle>>> = Lazy::new(|| Mutex::new(None));
 
+/// Request pacing shared by all workers
+static RATE_LIMITER: Lazy<RateLimiter> = Lazy::new(|| RateLimiter::new(0));
+
+/// Durable job state (None if the journal could not be opened)
+static JOB_STORE: Lazy<Mutex<Option<JobStore>>> = Lazy::new(|| Mutex::new(None));
+
+/// Token usage per day (None if the ledger could not be opened)
+static USAGE_LEDGER: Lazy<Mutex<Option<UsageLedger>>> = Lazy::new(|| Mutex::new(None));
+
+/// Why dispatching is paused for the budget (None = not paused)
+static BUDGET_PAUSE: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+/// Event emitted when a budget cap pauses the queue
+const BUDGET_EVENT: &str = "gemini-budget-reached";
+
+/// How often a budget pause re-checks (the day or month may have rolled over)
+const BUDGET_RECHECK_INTERVAL: Duration = Duration::from_secs(60);
+
 // =============================================================================
 // Types
 // =============================================================================
@@ -63,6 +112,10 @@ pub struct QueueStats {
     pub jobs_completed: u64,
     pub jobs_failed: u64,
     pub jobs_pending: u64,
+    #[serde(default)]
+    pub jobs_skipped: u64,
+    #[serde(default)]
+    pub jobs_cancelled: u64,
     pub last_error: Option<String>,
 }
 
@@ -71,14 +124,66 @@ pub struct QueueStatus {
     pub running: bool,
     pub stats: QueueStats,
     pub config: GeminiConfig,
+    /// Jobs being analyzed right now
+    pub active_workers: usize,
+    /// Remaining shared rate-limit backoff (seconds), None when not paused
+    pub rate_limit_pause_seconds: Option<f64>,
+    /// Token usage and estimated cost of the current local day
+    #[serde(default)]
+    pub usage_today: UsageTotals,
+    /// Token usage and estimated cost of the current local month
+    #[serde(default)]
+    pub usage_month: UsageTotals,
+    /// Why dispatching is paused for the budget, None when not paused
+    #[serde(default)]
+    pub budget_pause: Option<String>,
 }
 
-/// Persisted queue for recovery
+/// Payload of the gemini-budget-reached event
+#[derive(Debug, Clone, Serialize)]
+pub struct BudgetReached {
+    pub reason: String,
+    pub usage_today: UsageTotals,
+    pub usage_month: UsageTotals,
+}
+
+/// Jobs by state, for queue management
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueListing {
+    /// Waiting jobs in processing order
+    pub pending: Vec<GeminiJob>,
+    pub in_flight: Vec<GeminiJob>,
+    /// Dead-letter jobs, most recent failure first
+    pub failed: Vec<DeadLetterJob>,
+}
+
+/// Queue file written by earlier versions (imported into the journal once)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct PersistedQueue {
     jobs: Vec<GeminiJob>,
 }
 
+/// Dead-letter file written by earlier versions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct DeadLetterStore {
+    jobs: Vec<DeadLetterJob>,
+}
+
+struct InFlightJob {
+    job: GeminiJob,
+    abort: AbortHandle,
+}
+
+// Removes a job from IN_FLIGHT when its worker ends, including on abort
+struct InFlightGuard(JobKey);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        IN_FLIGHT.lock().unwrap().remove(&self.0);
+    }
+}
+
+
 // =============================================================================
 // Public API
 // =============================================================================
@@ -93,23 +198,35 @@ pub fn init_queue(app: &AppHandle, config: GeminiConfig) {
     // Store app handle and config
     *APP_HANDLE.lock().unwrap() = Some(app.clone());
     *GEMINI_CONFIG.lock().unwrap() = config.clone();
+    RATE_LIMITER.set_rate(config.rate_limit_per_minute);
 
     // Reset shutdown flag
     SHUTDOWN.store(false, Ordering::SeqCst);
+    RUNNING.store(true, Ordering::SeqCst);
 
-    // Create channel
-    let (tx, rx) = mpsc::channel::<GeminiJob>(100);
-    *JOB_SENDER.lock().unwrap() = Some(tx);
-
-    // Load persisted jobs
-    let persisted_jobs = load_persisted_queue(app);
+    // Load persisted jobs (processed before new ones)
+    let store = open_job_store(app);
+    let persisted_jobs = store.as_ref().map(JobStore::pending).unwrap_or_default();
     let job_count = persisted_jobs.len();
+    *JOB_STORE.lock().unwrap() = store;
+    *USAGE_LEDGER.lock().unwrap() = match UsageLedger::open(&get_usage_path(app)) {
+        Ok(ledger) => Some(ledger),
+        Err(e) => {
+            log::error!("Failed to open Gemini usage ledger, usage won't be tracked: {}", e);
+            None
+        }
+    };
+    {
+        let mut pending = PENDING.lock().unwrap();
+        pending.clear();
+        pending.extend(persisted_jobs);
+    }
 
     // Spawn background processor using Tauri's async runtime
     // (required because init_queue is called from synchronous setup)
     let app_clone = app.clone();
     tauri::async_runtime::spawn(async move {
-        queue_processor(rx, app_clone, persisted_jobs).await;
+        queue_processor(app_clone).await;
     });
 
     if job_count > 0 {
@@ -135,39 +252,42 @@ pub fn submit_job(job: GeminiJob) -> Result<(), String> {
         return Ok(());
     }
 
-    // Check if API key exists (check user-provided key first if AppHandle is available)
-    let has_key = if let Some(app_handle) = APP_HANDLE.lock().unwrap().as_ref() {
-        crate::recording::gemini::has_api_key_with_app(app_handle)
-    } else {
-        crate::recording::gemini::has_api_key()
+    // Check some model in the job's chain is usable (Gemini: API key, others: endpoint + model)
+    let chain = config.chain_for(job.metadata.duration_seconds, job.metadata.high_risk);
+    let configured = {
+        let app_handle = APP_HANDLE.lock().unwrap();
+        chain.iter().any(|target| analyzer::is_configured(&config, target, app_handle.as_ref()))
     };
     
-    if !has_key {
+    if !configured {
         log::warn!(
-            "[GEMINI-QUEUE] ⚠ No API key configured, skipping: segment={} display={}",
+            "[GEMINI-QUEUE] ⚠ No configured analyzer in model chain {:?}, skipping: segment={} display={}",
+            chain.iter().map(|t| t.backend).collect::<Vec<_>>(),
             job.segment_id,
             job.display_index
         );
         return Ok(());
     }
 
-    let sender = JOB_SENDER.lock().unwrap();
-    let tx = sender.as_ref().ok_or_else(|| "Gemini queue not initialized".to_string())?;
-
-    tx.try_send(job.clone())
-        .map_err(|e| format!("Failed to queue Gemini job: {}", e))?;
-
-    // Update stats
-    {
-        let mut stats = QUEUE_STATS.lock().unwrap();
-        stats.jobs_submitted += 1;
-        stats.jobs_pending += 1;
+    // Blank and locked screens carry nothing to analyze
+    let classification = job
+        .metadata
+        .displays
+        .iter()
+        .find(|d| d.display_index == job.display_index)
+        .and_then(|d| d.classification);
+    if let Some(classification) = classification.filter(|c| config.skip_classifications.contains(c)) {
+        log::info!(
+            "[GEMINI-QUEUE] ⏭ Skipping job ({:?} screen): segment={} display={}",
+            classification,
+            job.segment_id,
+            job.display_index
+        );
+        QUEUE_STATS.lock().unwrap().jobs_skipped += 1;
+        return Ok(());
     }
 
-    // Persist queue
-    if let Some(app) = APP_HANDLE.lock().unwrap().as_ref() {
-        persist_job(app, &job);
-    }
+    enqueue(job.clone())?;
 
     log::info!(
         "Queued Gemini analysis job for segment {} display {}",
@@ -178,6 +298,65 @@ pub fn submit_job(job: GeminiJob) -> Result<(), String> {
     Ok(())
 }
 
+/**
+ * Queue a reanalysis job (job.reanalysis set) behind the backlog
+ * Unlike submit_job it reports why a job isn't queued
+ *
+ * # Returns
+ * * `Ok(true)` - Queued
+ * * `Ok(false)` - Skipped: blank/locked screen, already queued or failed
+ * * `Err(String)` - Analysis disabled, model not configured or queue not running
+ */
+pub fn submit_reanalysis(job: GeminiJob) -> Result<bool, String> {
+    let config = GEMINI_CONFIG.lock().unwrap().clone();
+    if !config.enabled {
+        return Err("Analysis is disabled".to_string());
+    }
+
+    let chain = match job.reanalysis.as_ref().and_then(|r| r.target.clone()) {
+        Some(target) => vec![target],
+        None => config.chain_for(job.metadata.duration_seconds, job.metadata.high_risk),
+    };
+    let configured = {
+        let app_handle = APP_HANDLE.lock().unwrap();
+        chain.iter().any(|target| analyzer::is_configured(&config, target, app_handle.as_ref()))
+    };
+    if !configured {
+        return Err(format!(
+            "No configured analyzer in model chain {:?}",
+            chain.iter().map(|t| t.backend).collect::<Vec<_>>()
+        ));
+    }
+
+    let skipped = job
+        .metadata
+        .displays
+        .iter()
+        .find(|d| d.display_index == job.display_index)
+        .and_then(|d| d.classification)
+        .is_some_and(|c| config.skip_classifications.contains(&c));
+    if skipped {
+        return Ok(false);
+    }
+
+    // Keep the dead-letter record (and its error) until the job is retried
+    let failed = JOB_STORE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|store| store.is_failed(&job_key(&job)));
+    if failed {
+        log::info!(
+            "[GEMINI-QUEUE] Not reanalyzing failed job: segment={} display={}",
+            job.segment_id,
+            job.display_index
+        );
+        return Ok(false);
+    }
+
+    enqueue(job)
+}
+
 /**
  * Shutdown the queue gracefully
  * Waits for current job to complete, persists remaining jobs
@@ -186,9 +365,10 @@ pub async fn shutdown_queue() {
     log::info!("Shutting down Gemini queue...");
     
     SHUTDOWN.store(true, Ordering::SeqCst);
+    RUNNING.store(false, Ordering::SeqCst);
     
-    // Drop the sender to close the channel
-    *JOB_SENDER.lock().unwrap() = None;
+    // Wake the dispatcher so it stops (pending jobs are already persisted)
+    JOB_AVAILABLE.notify_one();
     
     // Give processor time to finish current job
     sleep(Duration::from_secs(1)).await;
@@ -200,10 +380,16 @@ pub async fn shutdown_queue() {
  * Get current queue status
  */
 pub fn get_queue_status() -> QueueStatus {
+    let (usage_today, usage_month) = usage_totals();
     QueueStatus {
-        running: JOB_SENDER.lock().unwrap().is_some(),
+        running: RUNNING.load(Ordering::SeqCst),
         stats: QUEUE_STATS.lock().unwrap().clone(),
         config: GEMINI_CONFIG.lock().unwrap().clone(),
+        active_workers: IN_FLIGHT.lock().unwrap().len(),
+        rate_limit_pause_seconds: RATE_LIMITER.paused_remaining().map(|d| d.as_secs_f64()),
+        usage_today,
+        usage_month,
+        budget_pause: BUDGET_PAUSE.lock().unwrap().clone(),
     }
 }
 
@@ -212,44 +398,290 @@ pub fn get_queue_status() -> QueueStatus {
  */
 pub fn update_config(config: GeminiConfig) {
     log::info!("Updating Gemini config (enabled: {})", config.enabled);
+    RATE_LIMITER.set_rate(config.rate_limit_per_minute);
     *GEMINI_CONFIG.lock().unwrap() = config;
+
+    // Wake the dispatcher: a raised budget ends a budget pause
+    JOB_AVAILABLE.notify_one();
+}
+
+// =============================================================================
+// Queue Management
+// =============================================================================
+
+/**
+ * List pending, in-flight and dead-letter jobs
+ */
+pub fn list_jobs() -> QueueListing {
+    let mut failed = JOB_STORE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(JobStore::failed)
+        .unwrap_or_default();
+    failed.sort_by_key(|failed| std::cmp::Reverse(failed.failed_at));
+
+    let mut in_flight: Vec<GeminiJob> = IN_FLIGHT.lock().unwrap().values().map(|j| j.job.clone()).collect();
+    in_flight.sort_by_key(|job| job.created_at);
+
+    QueueListing {
+        pending: PENDING.lock().unwrap().iter().cloned().collect(),
+        in_flight,
+        failed,
+    }
+}
+
+/**
+ * Cancel a pending or in-flight job
+ * In-flight requests are aborted; the segment can be submitted again later
+ *
+ * # Returns
+ * * Whether a job was found and cancelled
+ */
+pub fn cancel_job(segment_id: &str, display_index: u32) -> bool {
+    let key = (segment_id.to_string(), display_index);
+
+    let removed = {
+        let mut pending = PENDING.lock().unwrap();
+        let before = pending.len();
+        pending.retain(|j| job_key(j) != key);
+        before != pending.len()
+    };
+
+    let aborted = if removed {
+        None
+    } else {
+        IN_FLIGHT.lock().unwrap().remove(&key)
+    };
+    if let Some(in_flight) = &aborted {
+        in_flight.abort.abort();
+    }
+
+    if !removed && aborted.is_none() {
+        return false;
+    }
+
+    with_store(|store| store.remove(&key));
+    {
+        let mut stats = QUEUE_STATS.lock().unwrap();
+        stats.jobs_cancelled += 1;
+        stats.jobs_pending = stats.jobs_pending.saturating_sub(1);
+    }
+
+    log::info!(
+        "[GEMINI-QUEUE] Cancelled {} job: segment={} display={}",
+        if removed { "pending" } else { "in-flight" },
+        segment_id,
+        display_index
+    );
+    true
+}
+
+/**
+ * Move failed jobs from the dead-letter store back into the queue
+ * with fresh retry counters
+ *
+ * # Arguments
+ * * `job` - Segment and display of one job, or None to retry all
+ *
+ * # Returns
+ * * Number of jobs re-queued
+ */
+pub fn retry_failed_jobs(job: Option<(&str, u32)>) -> Result<usize, String> {
+    if !RUNNING.load(Ordering::SeqCst) {
+        return Err("Gemini queue not running".to_string());
+    }
+
+    let retried: Vec<DeadLetterJob> = JOB_STORE
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(JobStore::failed)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|failed| {
+            job.is_none_or(|(segment_id, display_index)| {
+                failed.job.segment_id == segment_id && failed.job.display_index == display_index
+            })
+        })
+        .collect();
+
+    let count = retried.len();
+    for failed in retried {
+        let mut job = failed.job;
+        job.retry_count = 0;
+        job.rate_limit_waits = 0;
+        job.model_index = 0;
+        enqueue(job)?;
+    }
+
+    if count > 0 {
+        log::info!("[GEMINI-QUEUE] Re-queued {} failed job(s)", count);
+    }
+    Ok(count)
+}
+
+/**
+ * Move pending jobs of segments recorded on `date` ahead of the backlog
+ * Keeps the relative order within both groups
+ *
+ * # Returns
+ * * Number of jobs moved to the front
+ */
+pub fn prioritize_date(date: NaiveDate) -> usize {
+    let mut pending = PENDING.lock().unwrap();
+    let (front, back): (VecDeque<_>, VecDeque<_>) = pending.drain(..).partition(|job| job_date(job) == Some(date));
+    let count = front.len();
+    pending.extend(front);
+    pending.extend(back);
+
+    log::info!("[GEMINI-QUEUE] Prioritized {} pending job(s) from {}", count, date);
+    count
+}
+
+// UTC recording date of a job's segment (storage layout date)
+fn job_date(job: &GeminiJob) -> Option<NaiveDate> {
+    DateTime::parse_from_rfc3339(&job.metadata.start_time)
+        .ok()
+        .map(|start| start.with_timezone(&Utc).date_naive())
+}
+
+// Append a job to the pending list and persist it
+// (a failed job moves out of the dead-letter state)
+// Returns false if the job was already queued or running
+fn enqueue(job: GeminiJob) -> Result<bool, String> {
+    if !RUNNING.load(Ordering::SeqCst) {
+        return Err("Gemini queue not initialized".to_string());
+    }
+
+    let key = job_key(&job);
+
+    // Hold the list until the push so two submits can't both pass the check
+    let mut pending = PENDING.lock().unwrap();
+
+    // Check memory too; without a store it is the only record of queued jobs
+    let mut queued = is_active(&pending, &key);
+    {
+        let mut store = JOB_STORE.lock().unwrap();
+        if let Some(store) = store.as_mut() {
+            queued = queued || store.is_queued(&key);
+            if !queued
+                && let Err(e) = store.enqueue(&job)
+            {
+                log::warn!("Failed to persist Gemini job: {}", e);
+            }
+        }
+    }
+    if queued {
+        log::info!(
+            "[GEMINI-QUEUE] Job already queued: segment={} display={}",
+            job.segment_id,
+            job.display_index
+        );
+        return Ok(false);
+    }
+
+    pending.push_back(job);
+    drop(pending);
+
+    // Update stats
+    {
+        let mut stats = QUEUE_STATS.lock().unwrap();
+        stats.jobs_submitted += 1;
+        stats.jobs_pending += 1;
+    }
+
+    JOB_AVAILABLE.notify_one();
+    Ok(true)
+}
+
+// Whether a job is pending or being processed
+fn is_active(pending: &VecDeque<GeminiJob>, key: &JobKey) -> bool {
+    pending.iter().any(|j| &job_key(j) == key) || IN_FLIGHT.lock().unwrap().contains_key(key)
 }
 
 // =============================================================================
 // Background Processor
 // =============================================================================
 
-async fn queue_processor(
-    mut rx: mpsc::Receiver<GeminiJob>,
-    app: AppHandle,
-    persisted_jobs: Vec<GeminiJob>,
-) {
+async fn queue_processor(app: AppHandle) {
     log::info!("Gemini queue processor started");
 
-    // Process persisted jobs first
-    for job in persisted_jobs {
+    let mut workers = JoinSet::new();
+
+    loop {
+        wait_for_worker_slot(&mut workers).await;
+        wait_for_budget(&app).await;
+
         if SHUTDOWN.load(Ordering::SeqCst) {
             break;
         }
-        process_job(&app, job).await;
-    }
 
-    // Process new jobs from channel
-    while let Some(job) = rx.recv().await {
-        if SHUTDOWN.load(Ordering::SeqCst) {
-            // Persist remaining job for next startup
-            persist_job(&app, &job);
-            break;
+        let next = PENDING.lock().unwrap().pop_front();
+        match next {
+            Some(job) => spawn_worker(&mut workers, &app, job),
+            None => JOB_AVAILABLE.notified().await,
         }
-        
-        process_job(&app, job).await;
     }
 
+    // Let in-flight jobs finish
+    while workers.join_next().await.is_some() {}
+
     log::info!("Gemini queue processor stopped");
 }
 
+/**
+ * Wait until fewer than max_concurrent_jobs workers run
+ * The pool size is read per job so settings changes apply without restart
+ */
+async fn wait_for_worker_slot(workers: &mut JoinSet<()>) {
+    loop {
+        let max_workers = GEMINI_CONFIG.lock().unwrap().max_concurrent_jobs.max(1) as usize;
+        if workers.len() < max_workers {
+            return;
+        }
+        match workers.join_next().await {
+            Some(Err(e)) if !e.is_cancelled() => log::error!("[GEMINI-QUEUE] Worker task failed: {}", e),
+            _ => {}
+        }
+    }
+}
+
+fn spawn_worker(workers: &mut JoinSet<()>, app: &AppHandle, job: GeminiJob) {
+    let key = job_key(&job);
+    let snapshot = job.clone();
+    let app = app.clone();
+
+    // Hold the map while spawning so the guard can't run before the insert
+    let mut in_flight = IN_FLIGHT.lock().unwrap();
+    let guard = InFlightGuard(key.clone());
+    let abort = workers.spawn(async move {
+        let _guard = guard;
+        with_store(|store| store.start(&job_key(&job)));
+        process_job(&app, job).await;
+    });
+    in_flight.insert(key, InFlightJob { job: snapshot, abort });
+}
+
 async fn process_job(app: &AppHandle, mut job: GeminiJob) {
-    let config = GEMINI_CONFIG.lock().unwrap().clone();
+    let mut config = GEMINI_CONFIG.lock().unwrap().clone();
+    
+    // The file may have moved to a new recordings root since the job was queued
+    job.video_path = crate::recording::storage::resolve_recording_path(app, &job.video_path);
+    
+    // Keep the listed and stored copies current across retries
+    let counters = |j: &GeminiJob| (j.retry_count, j.rate_limit_waits, j.model_index);
+    let changed = match IN_FLIGHT.lock().unwrap().get_mut(&job_key(&job)) {
+        Some(in_flight) => {
+            let changed = counters(&in_flight.job) != counters(&job);
+            in_flight.job = job.clone();
+            changed
+        }
+        None => false,
+    };
+    if changed {
+        with_store(|store| store.update(&job));
+    }
     
     log::info!(
         "[GEMINI-QUEUE] ▶ Processing job: segment={} display={} attempt={}/{} (rate_limit_waits={})",
@@ -265,6 +697,50 @@ async fn process_job(app: &AppHandle, mut job: GeminiJob) {
         job.metadata.duration_seconds
     );
 
+    // Pick up a high-risk flag set after the job was queued
+    refresh_high_risk(app, &mut job);
+    
+    // Reanalysis: the chosen prompt, and the chosen model without fallbacks
+    if let Some(prompt_template) = job.reanalysis.as_ref().and_then(|r| r.prompt_template.clone()) {
+        config.prompt_template = Some(prompt_template);
+    }
+    let chain = match job.reanalysis.as_ref().and_then(|r| r.target.clone()) {
+        Some(target) => vec![target],
+        None => config.chain_for(job.metadata.duration_seconds, job.metadata.high_risk),
+    };
+    job.model_index = job.model_index.min(chain.len() - 1);
+    let target = &chain[job.model_index];
+    log::info!(
+        "[GEMINI-QUEUE]   Model: {:?} {} ({}/{} in chain)",
+        target.backend,
+        target.model.as_deref().unwrap_or("(default)"),
+        job.model_index + 1,
+        chain.len()
+    );
+
+    // Segments the user was away for: away timeline, or a cheap sped-up analysis
+    if config.idle_threshold_seconds > 0
+        && let Some((start, end)) = idle::segment_range(&job)
+        && let Some(summary) = idle::fetch_afk_summary(&job).await
+        && summary.is_idle((end - start).num_milliseconds() as f64 / 1000.0, config.idle_threshold_seconds)
+    {
+        match config.idle_action {
+            IdleAction::Skip => {
+                complete_idle_job(app, &job, &summary);
+                return;
+            }
+            IdleAction::Reduced => {
+                log::info!(
+                    "[GEMINI-QUEUE]   Idle segment ({:.0}s active), analyzing at {}x speed",
+                    summary.active_seconds,
+                    config.idle_speedup_factor
+                );
+                config.speedup_factor = config.idle_speedup_factor.max(config.playback_speed());
+                config.speedup_mode = SpeedupMode::Reencode;
+            }
+        }
+    }
+
     // Check if video file still exists
     if !job.video_path.exists() {
         log::warn!(
@@ -273,32 +749,38 @@ async fn process_job(app: &AppHandle, mut job: GeminiJob) {
             job.display_index,
             job.video_path
         );
-        mark_job_failed("Video file not found");
-        remove_persisted_job(app, &job);
+        fail_job(&job, "Video file not found");
         return;
     }
 
-    // Call Gemini API (pass app handle to check user-provided API key)
-    let result = client::analyze_video(
-        &job.video_path,
-        &job.segment_id,
-        job.display_index,
-        job.metadata.duration_seconds,
-        &job.metadata.start_time,
-        &config,
-        Some(app),
-    )
-    .await;
+    // Wait for the shared rate limit (and any rate-limit pause)
+    RATE_LIMITER.acquire().await;
+
+    // Call the configured analyzer backend (app handle for user-provided API keys)
+    let result = analyzer::analyze_video(&job, &config, target, Some(app)).await;
 
     match result {
-        Ok(analysis) => {
+        Ok(mut analysis) => {
             log::info!(
-                "Gemini analysis successful for segment {} display {}: {} entries",
+                "Analysis successful for segment {} display {}: {} entries",
                 job.segment_id,
                 job.display_index,
                 analysis.timeline.len()
             );
 
+            // Exact boundaries and app names from ActivityWatch window events
+            if config.window_fusion {
+                fusion::fuse_with_activitywatch(&job, &mut analysis, config.fusion_snap_seconds).await;
+            }
+
+            // Keep the result locally, whatever happens to the collector batch
+            if job.reanalysis.is_some() {
+                analysis.analysis_version = results::next_version(app, &job);
+            }
+            if let Err(e) = results::save_analysis(app, &analysis) {
+                log::error!("Failed to save analysis: {}", e);
+            }
+
             // Send to collector
             if let Err(e) = send_to_collector(&analysis) {
                 log::error!("Failed to send timeline to collector: {}", e);
@@ -312,7 +794,7 @@ async fn process_job(app: &AppHandle, mut job: GeminiJob) {
             }
 
             // Remove from persisted queue
-            remove_persisted_job(app, &job);
+            with_store(|store| store.complete(&job_key(&job)));
         }
         Err(e) => {
             log::error!(
@@ -329,14 +811,24 @@ async fn process_job(app: &AppHandle, mut job: GeminiJob) {
                     job.rate_limit_waits += 1;
                     
                     if job.rate_limit_waits >= config.rate_limit_max_waits {
+                        if advance_model(&mut job, chain.len()) {
+                            log::warn!(
+                                "[GEMINI-QUEUE] Rate limit waits exhausted, falling back to model {}/{} for segment {} display {}",
+                                job.model_index + 1,
+                                chain.len(),
+                                job.segment_id,
+                                job.display_index
+                            );
+                            Box::pin(process_job(app, job)).await;
+                            return;
+                        }
                         log::error!(
                             "Max rate limit waits ({}) exceeded for segment {} display {}",
                             config.rate_limit_max_waits,
                             job.segment_id,
                             job.display_index
                         );
-                        mark_job_failed(&e.to_string());
-                        remove_persisted_job(app, &job);
+                        fail_job(&job, &e.to_string());
                         return;
                     }
 
@@ -346,13 +838,14 @@ async fn process_job(app: &AppHandle, mut job: GeminiJob) {
                     });
                     
                     log::info!(
-                        "[GEMINI-QUEUE] Rate limited. Waiting {:.1}s (API-specified delay). Rate limit wait {}/{}",
+                        "[GEMINI-QUEUE] Rate limited. Pausing all workers for {:.1}s. Rate limit wait {}/{}",
                         delay.as_secs_f64(),
                         job.rate_limit_waits,
                         config.rate_limit_max_waits
                     );
                     
-                    sleep(delay).await;
+                    // Shared backoff: the retry (and every other worker) waits in acquire()
+                    RATE_LIMITER.pause_for(delay);
                     
                     // Re-process (retry_count NOT incremented)
                     Box::pin(process_job(app, job)).await;
@@ -362,13 +855,24 @@ async fn process_job(app: &AppHandle, mut job: GeminiJob) {
                     job.retry_count += 1;
 
                     if job.retry_count >= config.max_retries {
+                        if advance_model(&mut job, chain.len()) {
+                            log::warn!(
+                                "[GEMINI-QUEUE] Model unavailable after {} attempts, falling back to model {}/{} for segment {} display {}",
+                                config.max_retries,
+                                job.model_index + 1,
+                                chain.len(),
+                                job.segment_id,
+                                job.display_index
+                            );
+                            Box::pin(process_job(app, job)).await;
+                            return;
+                        }
                         log::error!(
                             "Max retries exceeded for segment {} display {}",
                             job.segment_id,
                             job.display_index
                         );
-                        mark_job_failed(&e.to_string());
-                        remove_persisted_job(app, &job);
+                        fail_job(&job, &e.to_string());
                         return;
                     }
 
@@ -397,29 +901,186 @@ async fn process_job(app: &AppHandle, mut job: GeminiJob) {
                         job.display_index,
                         e
                     );
-                    mark_job_failed(&e.to_string());
-                    remove_persisted_job(app, &job);
+                    fail_job(&job, &e.to_string());
                 }
             }
         }
     }
 }
 
-fn mark_job_failed(error: &str) {
-    let mut stats = QUEUE_STATS.lock().unwrap();
-    stats.jobs_failed += 1;
-    stats.jobs_pending = stats.jobs_pending.saturating_sub(1);
-    stats.last_error = Some(error.to_string());
+/**
+ * Move a job to the next model of its chain with fresh retry counters
+ * Returns false when the job already used the last model
+ */
+fn advance_model(job: &mut GeminiJob, chain_len: usize) -> bool {
+    if job.model_index + 1 >= chain_len {
+        return false;
+    }
+    job.model_index += 1;
+    job.retry_count = 0;
+    job.rate_limit_waits = 0;
+    true
+}
+
+/**
+ * Record a job's token usage in the ledger and check the budget
+ * Called by the analyzer after every attempt, failed ones included
+ */
+pub(crate) fn record_usage(job: &GeminiJob, backend: AnalyzerBackend, model: &str, token_usage: &TokenUsage) {
+    let config = GEMINI_CONFIG.lock().unwrap().clone();
+    let record = UsageRecord {
+        segment_id: job.segment_id.clone(),
+        display_index: job.display_index,
+        date: chrono::Local::now().date_naive(),
+        recorded_at: Utc::now(),
+        backend,
+        model: model.to_string(),
+        usage: *token_usage,
+        cost_usd: usage::estimate_cost(token_usage, &config),
+    };
+
+    log::info!(
+        "[GEMINI-QUEUE] Token usage for segment {} display {}: {:?} (~${:.4})",
+        job.segment_id,
+        job.display_index,
+        token_usage,
+        record.cost_usd
+    );
+
+    if let Some(ledger) = USAGE_LEDGER.lock().unwrap().as_mut()
+        && let Err(e) = ledger.record(&record)
+    {
+        log::warn!("Failed to record Gemini usage: {}", e);
+    }
+
+    let app = APP_HANDLE.lock().unwrap().clone();
+    if let Some(app) = app {
+        check_budget(&app);
+    }
+}
+
+// Totals of the current local day and month
+fn usage_totals() -> (UsageTotals, UsageTotals) {
+    let today = chrono::Local::now().date_naive();
+    USAGE_LEDGER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|ledger| (ledger.day(today), ledger.month(today)))
+        .unwrap_or_default()
+}
+
+/**
+ * Update the budget pause from the current spend
+ * Emits gemini-budget-reached when the queue becomes paused
+ *
+ * # Returns
+ * * Whether dispatching is paused
+ */
+fn check_budget(app: &AppHandle) -> bool {
+    let config = GEMINI_CONFIG.lock().unwrap().clone();
+    let (usage_today, usage_month) = usage_totals();
+    let reason = usage::budget_exceeded(&config, &usage_today, &usage_month);
+
+    let mut pause = BUDGET_PAUSE.lock().unwrap();
+    match (&reason, pause.is_some()) {
+        (Some(reason), false) => {
+            log::warn!("[GEMINI-QUEUE] ⏸ {}, pausing analysis", reason);
+            let _ = app.emit(
+                BUDGET_EVENT,
+                BudgetReached {
+                    reason: reason.clone(),
+                    usage_today,
+                    usage_month,
+                },
+            );
+        }
+        (None, true) => log::info!("[GEMINI-QUEUE] ▶ Within budget again, resuming analysis"),
+        _ => {}
+    }
+    *pause = reason;
+    pause.is_some()
+}
+
+// Hold back new jobs while a budget is reached (in-flight jobs finish)
+async fn wait_for_budget(app: &AppHandle) {
+    while check_budget(app) && !SHUTDOWN.load(Ordering::SeqCst) {
+        // Woken early by config updates and shutdown
+        let _ = tokio::time::timeout(BUDGET_RECHECK_INTERVAL, JOB_AVAILABLE.notified()).await;
+    }
+}
+
+/**
+ * Wait for the shared rate limit before an additional request of a job
+ * (analysis windows of long videos, self-correction turns)
+ */
+pub(crate) async fn wait_for_rate_limit() {
+    RATE_LIMITER.acquire().await;
+}
+
+// Re-read the segment's high-risk flag from its sidecar (set after queuing)
+fn refresh_high_risk(app: &AppHandle, job: &mut GeminiJob) {
+    let Ok(start) = chrono::DateTime::parse_from_rfc3339(&job.metadata.start_time) else {
+        return;
+    };
+    let date = start.with_timezone(&chrono::Utc).date_naive();
+    let path = crate::recording::storage::get_metadata_path(app, &date, &job.segment_id);
+    let path = crate::recording::storage::resolve_recording_path(app, &path);
+    if let Ok(metadata) = crate::recording::storage::load_metadata(&path) {
+        job.metadata.high_risk = metadata.high_risk;
+    }
+}
+
+// Emit the away timeline of an idle segment instead of analyzing it
+fn complete_idle_job(app: &AppHandle, job: &GeminiJob, summary: &AfkSummary) {
+    log::info!(
+        "[GEMINI-QUEUE] ⏭ Skipping idle segment ({:.0}s active, {:.0}s away): segment={} display={}",
+        summary.active_seconds,
+        summary.away_seconds,
+        job.segment_id,
+        job.display_index
+    );
+
+    let mut analysis = idle::away_analysis(job, summary);
+    if job.reanalysis.is_some() {
+        analysis.analysis_version = results::next_version(app, job);
+    }
+    if let Err(e) = results::save_analysis(app, &analysis) {
+        log::error!("Failed to save away timeline: {}", e);
+    }
+    if let Err(e) = send_to_collector(&analysis) {
+        log::error!("Failed to send away timeline to collector: {}", e);
+    }
+
+    {
+        let mut stats = QUEUE_STATS.lock().unwrap();
+        stats.jobs_skipped += 1;
+        stats.jobs_pending = stats.jobs_pending.saturating_sub(1);
+    }
+    with_store(|store| store.complete(&job_key(job)));
+}
+
+// Count a permanent failure and move the job to the dead-letter store
+fn fail_job(job: &GeminiJob, error: &str) {
+    {
+        let mut stats = QUEUE_STATS.lock().unwrap();
+        stats.jobs_failed += 1;
+        stats.jobs_pending = stats.jobs_pending.saturating_sub(1);
+        stats.last_error = Some(error.to_string());
+    }
+
+    with_store(|store| store.fail(job, error));
 }
 
 // =============================================================================
 // Collector Integration
 // =============================================================================
 
-fn send_to_collector(analysis: &crate::recording::gemini::types::TimelineAnalysis) -> Result<(), String> {
+// Queue an analysis' entries to the collector; returns the number queued
+pub(crate) fn send_to_collector(analysis: &crate::recording::gemini::types::TimelineAnalysis) -> Result<usize, String> {
     if !collector_config::is_enabled() {
         log::info!("[GEMINI-COLLECTOR] Collector disabled, timeline data NOT sent");
-        return Ok(());
+        return Ok(0);
     }
 
     let hostname = hostname::get()
@@ -435,12 +1096,16 @@ fn send_to_collector(analysis: &crate::recording::gemini::types::TimelineAnalysi
     );
 
     // Convert timeline entries to line protocol and queue
+    // An entry that can't be formatted is skipped, not the whole timeline
+    let mut queued = 0;
     for (i, entry) in analysis.timeline.iter().enumerate() {
-        let line_protocol = formatter::format_timeline_entry(
-            analysis,
-            entry,
-            &hostname,
-        )?;
+        let line_protocol = match formatter::format_timeline_entry(analysis, entry, &hostname) {
+            Ok(line) => line,
+            Err(e) => {
+                log::warn!("[GEMINI-COLLECTOR] Skipping timeline entry {}: {}", i + 1, e);
+                continue;
+            }
+        };
         
         log::info!(
             "[GEMINI-COLLECTOR] LineProtocol[{}]: {}",
@@ -449,77 +1114,89 @@ fn send_to_collector(analysis: &crate::recording::gemini::types::TimelineAnalysi
         );
         
         batch::add_event(line_protocol)?;
+        queued += 1;
     }
 
     log::info!(
-        "[GEMINI-COLLECTOR] ✓ Queued {} timeline events for transmission",
+        "[GEMINI-COLLECTOR] ✓ Queued {}/{} timeline events for transmission",
+        queued,
         analysis.timeline.len()
     );
 
-    Ok(())
+    Ok(queued)
 }
 
 // =============================================================================
 // Persistence
 // =============================================================================
 
-fn get_queue_path(app: &AppHandle) -> PathBuf {
+fn get_store_path(app: &AppHandle) -> PathBuf {
     app.path()
         .app_data_dir()
         .expect("app_data_dir available")
-        .join("gemini_queue.json")
+        .join("gemini_jobs.jsonl")
 }
 
-fn persist_job(app: &AppHandle, job: &GeminiJob) {
-    let path = get_queue_path(app);
-    
-    // Load existing queue
-    let mut queue = load_persisted_queue_raw(app);
-    
-    // Add job if not already present
-    if !queue.jobs.iter().any(|j| j.segment_id == job.segment_id && j.display_index == job.display_index) {
-        queue.jobs.push(job.clone());
-    }
-    
-    // Save
-    if let Ok(json) = serde_json::to_string_pretty(&queue) {
-        if let Err(e) = std::fs::write(&path, json) {
-            log::warn!("Failed to persist Gemini queue: {}", e);
-        }
-    }
+fn get_usage_path(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .expect("app_data_dir available")
+        .join("gemini_usage.jsonl")
 }
 
-fn remove_persisted_job(app: &AppHandle, job: &GeminiJob) {
-    let path = get_queue_path(app);
-    
-    // Load existing queue
-    let mut queue = load_persisted_queue_raw(app);
-    
-    // Remove job
-    queue.jobs.retain(|j| !(j.segment_id == job.segment_id && j.display_index == job.display_index));
-    
-    // Save
-    if let Ok(json) = serde_json::to_string_pretty(&queue) {
-        if let Err(e) = std::fs::write(&path, json) {
-            log::warn!("Failed to update Gemini queue: {}", e);
+// Open the job journal, importing queue files of earlier versions once
+fn open_job_store(app: &AppHandle) -> Option<JobStore> {
+    let mut store = match JobStore::open(&get_store_path(app)) {
+        Ok(store) => store,
+        Err(e) => {
+            log::error!("Failed to open Gemini job store, jobs won't survive a restart: {}", e);
+            return None;
         }
+    };
+
+    let data_dir = app.path().app_data_dir().expect("app_data_dir available");
+    let legacy_queue = data_dir.join("gemini_queue.json");
+    let legacy_dead_letter = data_dir.join("gemini_dead_letter.json");
+
+    let queued = std::fs::read_to_string(&legacy_queue)
+        .ok()
+        .and_then(|json| serde_json::from_str::<PersistedQueue>(&json).ok())
+        .map(|queue| queue.jobs)
+        .unwrap_or_default();
+    let failed = std::fs::read_to_string(&legacy_dead_letter)
+        .ok()
+        .and_then(|json| serde_json::from_str::<DeadLetterStore>(&json).ok())
+        .map(|store| store.jobs)
+        .unwrap_or_default();
+
+    let imported = queued
+        .iter()
+        .try_for_each(|job| store.enqueue(job))
+        .and_then(|_| failed.iter().try_for_each(|f| store.fail(&f.job, &f.error)));
+    match imported {
+        Ok(()) => {
+            let _ = std::fs::remove_file(&legacy_queue);
+            let _ = std::fs::remove_file(&legacy_dead_letter);
+            if !queued.is_empty() || !failed.is_empty() {
+                log::info!(
+                    "Imported {} queued and {} failed Gemini jobs into the job store",
+                    queued.len(),
+                    failed.len()
+                );
+            }
+        }
+        Err(e) => log::warn!("Failed to import legacy Gemini queue: {}", e),
     }
-}
 
-fn load_persisted_queue(app: &AppHandle) -> Vec<GeminiJob> {
-    load_persisted_queue_raw(app).jobs
+    Some(store)
 }
 
-fn load_persisted_queue_raw(app: &AppHandle) -> PersistedQueue {
-    let path = get_queue_path(app);
-    
-    if !path.exists() {
-        return PersistedQueue { jobs: Vec::new() };
-    }
-    
-    match std::fs::read_to_string(&path) {
-        Ok(json) => serde_json::from_str(&json).unwrap_or(PersistedQueue { jobs: Vec::new() }),
-        Err(_) => PersistedQueue { jobs: Vec::new() },
+// Record a job state transition (logged, not fatal, on failure)
+fn with_store(transition: impl FnOnce(&mut JobStore) -> Result<(), String>) {
+    if let Some(store) = JOB_STORE.lock().unwrap().as_mut()
+        && let Err(e) = transition(store)
+    {
+        log::warn!("Failed to update Gemini job store: {}", e);
     }
 }
 
@@ -530,6 +1207,7 @@ fn load_persisted_queue_raw(app: &AppHandle) -> PersistedQueue {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::recording::gemini::fixtures::test_job;
 
     #[test]
     fn test_queue_stats_default() {
@@ -538,7 +1216,53 @@ mod tests {
         assert_eq!(stats.jobs_completed, 0);
         assert_eq!(stats.jobs_failed, 0);
         assert_eq!(stats.jobs_pending, 0);
+        assert_eq!(stats.jobs_skipped, 0);
         assert!(stats.last_error.is_none());
     }
-}
 
+    #[test]
+    fn test_advance_model() {
+        let mut job = GeminiJob {
+            retry_count: 3,
+            rate_limit_waits: 2,
+            ..test_job("segment_1", "2025-01-15T10:00:00Z")
+        };
+
+        // Jobs persisted before model chains start at the first model
+        assert_eq!(job.model_index, 0);
+
+        assert!(advance_model(&mut job, 2));
+        assert_eq!((job.model_index, job.retry_count, job.rate_limit_waits), (1, 0, 0));
+        assert!(!advance_model(&mut job, 2));
+        assert_eq!(job.model_index, 1);
+    }
+
+    #[test]
+    fn test_prioritize_date() {
+        {
+            let mut pending = PENDING.lock().unwrap();
+            pending.clear();
+            pending.extend([
+                test_job("old_1", "2025-01-14T09:00:00Z"),
+                test_job("today_1", "2025-01-15T08:00:00Z"),
+                test_job("old_2", "2025-01-14T10:00:00Z"),
+                test_job("today_2", "2025-01-15T09:00:00Z"),
+            ]);
+        }
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        assert_eq!(prioritize_date(date), 2);
+
+        let order: Vec<String> = PENDING.lock().unwrap().drain(..).map(|j| j.segment_id).collect();
+        assert_eq!(order, vec!["today_1", "today_2", "old_1", "old_2"]);
+    }
+
+    #[test]
+    fn test_is_active() {
+        let pending = VecDeque::from([test_job("queued", "2025-01-15T08:00:00Z")]);
+
+        assert!(is_active(&pending, &("queued".to_string(), 0)));
+        assert!(!is_active(&pending, &("queued".to_string(), 1)));
+        assert!(!is_active(&pending, &("other".to_string(), 0)));
+    }
+}
@@ -0,0 +1,217 @@
+/**
+ * ============================================================================
+ * REANALYSIS MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Analyze already analyzed segments again with a new prompt or model
+ *
+ * SELECTION:
+ * Segments starting within a time range, narrowed by a filter on their
+ * current analysis (prompt version, model). Displays without a stored
+ * analysis are only selected without a filter; displays whose video was
+ * deleted are skipped, as are displays whose last job failed (retry those
+ * first; the dead-letter record is kept).
+ *
+ * JOBS:
+ * Selected displays are queued oldest first behind the regular backlog, with
+ * the chosen prompt (pinned to its current version when none is given) and
+ * model instead of the configured chain. Results are saved as a new analysis
+ * version (results.rs) and sent to the collector tagged
+ * analysis_version=<K>, so earlier analyses and points stay as they were.
+ *
+ * ============================================================================
+ */
+
+use crate::recording::gemini::{
+    prompt::{load_template, templates_dir},
+    queue,
+    results::{load_analysis, segment_date},
+    types::{GeminiJob, ModelTarget, Reanalysis, TimelineAnalysis},
+};
+use crate::recording::storage;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/**
+ * Which analyzed displays to reanalyze
+ * Unset fields match every analysis
+ */
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ReanalysisFilter {
+    /// "name@N" for one version, "name" for every version of a template
+    #[serde(default)]
+    pub prompt_version: Option<String>,
+
+    /// Model name the analysis was made with
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+impl ReanalysisFilter {
+    fn is_empty(&self) -> bool {
+        self.prompt_version.is_none() && self.model.is_none()
+    }
+
+    /**
+     * Whether a display with this current analysis (None = never analyzed)
+     * should be reanalyzed
+     */
+    pub fn matches(&self, analysis: Option<&TimelineAnalysis>) -> bool {
+        let Some(analysis) = analysis else {
+            return self.is_empty();
+        };
+
+        let prompt_matches = self.prompt_version.as_deref().is_none_or(|wanted| {
+            if wanted.contains('@') {
+                analysis.prompt_version == wanted
+            } else {
+                analysis.prompt_version.split('@').next() == Some(wanted)
+            }
+        });
+        let model_matches = self.model.as_deref().is_none_or(|model| analysis.model == model);
+
+        prompt_matches && model_matches
+    }
+}
+
+/// Outcome of queuing a reanalysis
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ReanalysisSummary {
+    /// Display jobs queued
+    pub queued: usize,
+
+    /// Matching displays not queued (video deleted, blank screen, already
+    /// queued, or failed and waiting in the dead-letter store)
+    pub skipped: usize,
+
+    /// Prompt the jobs use ("name@N"; None = configured prompt)
+    pub prompt_version: Option<String>,
+}
+
+/**
+ * Queue reanalysis of the segments starting within [start, end]
+ *
+ * # Arguments
+ * * `filter` - Which analyses to redo
+ * * `prompt_template` - Template to use (None = configured prompt)
+ * * `target` - Model to use (None = configured chain)
+ *
+ * # Returns
+ * * `Err(String)` if the prompt doesn't exist, the model isn't configured or
+ *   analysis is disabled (nothing queued)
+ */
+pub fn enqueue_reanalysis(
+    app: &AppHandle,
+    start: &DateTime<Utc>,
+    end: &DateTime<Utc>,
+    filter: &ReanalysisFilter,
+    prompt_template: Option<&str>,
+    target: Option<ModelTarget>,
+) -> Result<ReanalysisSummary, String> {
+    // Pin the template so later edits don't change jobs already queued
+    let prompt_version = match prompt_template {
+        Some(selection) => Some(load_template(&templates_dir(app), Some(selection))?.prompt_version()),
+        None => None,
+    };
+    let reanalysis = Reanalysis {
+        prompt_template: prompt_version.clone(),
+        target,
+    };
+
+    let mut segments = storage::get_recordings_in_range(app, start, end)?;
+    segments.reverse();
+
+    let mut summary = ReanalysisSummary {
+        prompt_version,
+        ..Default::default()
+    };
+    for metadata in segments {
+        let Ok(date) = segment_date(&metadata.start_time) else {
+            continue;
+        };
+
+        for display in &metadata.displays {
+            let current = load_analysis(app, &date, &metadata.id, display.display_index);
+            if !filter.matches(current.as_ref()) {
+                continue;
+            }
+
+            let video_path = storage::find_video_path(app, &date, &metadata.id, display.display_index);
+            if !video_path.exists() {
+                summary.skipped += 1;
+                continue;
+            }
+
+            let job = GeminiJob {
+                segment_id: metadata.id.clone(),
+                display_index: display.display_index,
+                video_path,
+                metadata: metadata.clone(),
+                retry_count: 0,
+                rate_limit_waits: 0,
+                created_at: Utc::now(),
+                model_index: 0,
+                reanalysis: Some(reanalysis.clone()),
+            };
+            if queue::submit_reanalysis(job)? {
+                summary.queued += 1;
+            } else {
+                summary.skipped += 1;
+            }
+        }
+    }
+
+    log::info!(
+        "[GEMINI-REANALYSIS] Queued {} displays ({} skipped) from {} to {} with prompt {:?}",
+        summary.queued,
+        summary.skipped,
+        start,
+        end,
+        summary.prompt_version
+    );
+    Ok(summary)
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::gemini::fixtures;
+
+    fn analysis(prompt_version: &str, model: &str) -> TimelineAnalysis {
+        TimelineAnalysis {
+            prompt_version: prompt_version.to_string(),
+            model: model.to_string(),
+            ..fixtures::analysis(Vec::new())
+        }
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        let v1 = analysis("builtin@1", "gemini-2.5-flash-lite");
+        let custom = analysis("focus@3", "llava");
+
+        let any = ReanalysisFilter::default();
+        assert!(any.matches(Some(&v1)) && any.matches(None));
+
+        let builtin_v1 = ReanalysisFilter {
+            prompt_version: Some("builtin@1".to_string()),
+            model: None,
+        };
+        assert!(builtin_v1.matches(Some(&v1)));
+        assert!(!builtin_v1.matches(Some(&custom)));
+        assert!(!builtin_v1.matches(None));
+
+        let focus_llava = ReanalysisFilter {
+            prompt_version: Some("focus".to_string()),
+            model: Some("llava".to_string()),
+        };
+        assert!(focus_llava.matches(Some(&custom)));
+        assert!(!focus_llava.matches(Some(&analysis("focus@3", "gemma3"))));
+        assert!(!focus_llava.matches(Some(&analysis("focused@1", "llava"))));
+    }
+}
@@ -0,0 +1,246 @@
+/**
+ * ============================================================================
+ * ANALYSIS RESULTS MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Keep finished analyses next to their recordings
+ *
+ * STORAGE:
+ * Every analysis (model or away timeline) is saved as
+ * <segment_id>_d<N>.timeline in the segment's date directory, before it is
+ * sent to the collector. Reanalyses (reanalysis.rs) are saved as new
+ * versions, <segment_id>_d<N>.v<K>.timeline, keeping the earlier ones; the
+ * highest version is the display's current timeline. The desktop UI reads
+ * timelines from there, so they survive a disabled collector or a lost
+ * batch. Sidecars are removed with their recordings by retention and quota
+ * cleanup; they are derived data and not part of the integrity chain.
+ *
+ * RE-SENDING:
+ * The current analyses of a time range can be queued to the collector again.
+ * Points keep their tags and timestamps, so re-sent entries overwrite the
+ * ones already stored instead of duplicating them.
+ *
+ * ============================================================================
+ */
+
+use crate::recording::gemini::types::{GeminiJob, TimelineAnalysis};
+use crate::recording::storage;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+/// Outcome of re-sending stored analyses
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ResendSummary {
+    pub analyses: usize,
+    pub entries: usize,
+}
+
+/**
+ * Save an analysis as its segment's sidecar for analysis.analysis_version
+ * (replacing an older save of the same version)
+ */
+pub fn save_analysis(app: &AppHandle, analysis: &TimelineAnalysis) -> Result<PathBuf, String> {
+    let date = segment_date(&analysis.segment_start_time)?;
+    storage::ensure_recording_dir(app, &date)?;
+    let path = storage::get_analysis_path(
+        app,
+        &date,
+        &analysis.segment_id,
+        analysis.display_index,
+        analysis.analysis_version,
+    );
+
+    let contents = serde_json::to_string_pretty(analysis)
+        .map_err(|e| format!("Failed to serialize analysis: {}", e))?;
+    std::fs::write(&path, contents).map_err(|e| format!("Failed to write analysis: {}", e))?;
+
+    Ok(path)
+}
+
+/**
+ * Version for a reanalysis of a display
+ * At least 2: the first analysis may have been sent to the collector before
+ * analyses were stored
+ */
+pub fn next_version(app: &AppHandle, job: &GeminiJob) -> u32 {
+    let latest = segment_date(&job.metadata.start_time)
+        .ok()
+        .and_then(|date| {
+            storage::list_analysis_versions(app, &date, &job.segment_id, job.display_index)
+                .last()
+                .copied()
+        })
+        .unwrap_or(1);
+    latest.max(1) + 1
+}
+
+/**
+ * Load a display's current (highest version) analysis (None if never analyzed)
+ */
+pub fn load_analysis(app: &AppHandle, date: &NaiveDate, segment_id: &str, display_index: u32) -> Option<TimelineAnalysis> {
+    storage::list_analysis_versions(app, date, segment_id, display_index)
+        .into_iter()
+        .rev()
+        .find_map(|version| load_analysis_version(app, date, segment_id, display_index, version))
+}
+
+/**
+ * Load every stored analysis version of a display, oldest first
+ */
+pub fn load_analysis_versions(app: &AppHandle, date: &NaiveDate, segment_id: &str, display_index: u32) -> Vec<TimelineAnalysis> {
+    storage::list_analysis_versions(app, date, segment_id, display_index)
+        .into_iter()
+        .filter_map(|version| load_analysis_version(app, date, segment_id, display_index, version))
+        .collect()
+}
+
+fn load_analysis_version(
+    app: &AppHandle,
+    date: &NaiveDate,
+    segment_id: &str,
+    display_index: u32,
+    version: u32,
+) -> Option<TimelineAnalysis> {
+    let path = storage::resolve_recording_path(
+        app,
+        &storage::get_analysis_path(app, date, segment_id, display_index, version),
+    );
+    let contents = std::fs::read_to_string(&path).ok()?;
+
+    match serde_json::from_str(&contents) {
+        Ok(analysis) => Some(analysis),
+        Err(e) => {
+            log::warn!("[GEMINI-RESULTS] Unreadable analysis {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+/**
+ * Stored analyses of every display of a segment: the current one per
+ * display, or every version with `all_versions`
+ */
+pub fn segment_analyses(
+    app: &AppHandle,
+    date: &NaiveDate,
+    segment_id: &str,
+    all_versions: bool,
+) -> Result<Vec<TimelineAnalysis>, String> {
+    let metadata = storage::get_segments_for_date(app, date)
+        .into_iter()
+        .find(|m| m.id == segment_id)
+        .ok_or_else(|| format!("Segment not found: {}", segment_id))?;
+
+    Ok(metadata
+        .displays
+        .iter()
+        .flat_map(|d| {
+            if all_versions {
+                load_analysis_versions(app, date, segment_id, d.display_index)
+            } else {
+                load_analysis(app, date, segment_id, d.display_index).into_iter().collect()
+            }
+        })
+        .collect())
+}
+
+/**
+ * Current analyses of segments starting within [start, end], oldest first
+ */
+pub fn analyses_in_range(app: &AppHandle, start: &DateTime<Utc>, end: &DateTime<Utc>) -> Result<Vec<TimelineAnalysis>, String> {
+    let mut segments = storage::get_recordings_in_range(app, start, end)?;
+    segments.reverse();
+
+    let mut analyses = Vec::new();
+    for metadata in segments {
+        let Ok(date) = segment_date(&metadata.start_time) else {
+            continue;
+        };
+        analyses.extend(
+            metadata
+                .displays
+                .iter()
+                .filter_map(|d| load_analysis(app, &date, &metadata.id, d.display_index)),
+        );
+    }
+    Ok(analyses)
+}
+
+/**
+ * Queue the current analyses of [start, end] to the collector again
+ * Fails without sending anything when the collector is disabled
+ */
+pub fn resend_to_collector(app: &AppHandle, start: &DateTime<Utc>, end: &DateTime<Utc>) -> Result<ResendSummary, String> {
+    if !crate::collector::config::is_enabled() {
+        return Err("Collector is disabled".to_string());
+    }
+
+    let mut summary = ResendSummary::default();
+    for analysis in analyses_in_range(app, start, end)? {
+        summary.entries += super::queue::send_to_collector(&analysis)?;
+        summary.analyses += 1;
+    }
+
+    log::info!(
+        "[GEMINI-RESULTS] Re-sent {} analyses ({} entries) from {} to {}",
+        summary.analyses,
+        summary.entries,
+        start,
+        end
+    );
+    Ok(summary)
+}
+
+// Date directory of a segment (UTC date of its start, as recordings are filed)
+pub(crate) fn segment_date(start_time: &str) -> Result<NaiveDate, String> {
+    DateTime::parse_from_rfc3339(start_time)
+        .map(|start| start.with_timezone(&Utc).date_naive())
+        .map_err(|e| format!("Invalid segment start time '{}': {}", start_time, e))
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_date() {
+        assert_eq!(
+            segment_date("2025-01-15T23:30:00-02:00").unwrap(),
+            "2025-01-16".parse::<NaiveDate>().unwrap()
+        );
+        assert!(segment_date("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_sidecar_round_trip() {
+        // Analyses without quality, usage or fusion (away timelines, older versions) stay readable
+        let stored = r#"{
+            "segment_id": "segment_1736956800_abc123",
+            "display_index": 1,
+            "analyzed_at": "2025-01-15T16:05:00Z",
+            "video_duration_seconds": 300.0,
+            "segment_start_time": "2025-01-15T16:00:00Z",
+            "timeline": [{
+                "startTime": "00:00",
+                "endTime": "05:00",
+                "description": "Editing code",
+                "activeApplication": "Code",
+                "activeWindowTitle": "main.rs",
+                "productiveScore": 5
+            }]
+        }"#;
+        let analysis: TimelineAnalysis = serde_json::from_str(stored).unwrap();
+        assert!(analysis.quality.is_none() && analysis.fusion.is_none() && !analysis.synthetic);
+
+        let reread: TimelineAnalysis = serde_json::from_str(&serde_json::to_string_pretty(&analysis).unwrap()).unwrap();
+        assert_eq!(reread.timeline.len(), 1);
+        assert_eq!(reread.timeline[0].active_application, "Code");
+        assert_eq!(reread.display_index, 1);
+    }
+}
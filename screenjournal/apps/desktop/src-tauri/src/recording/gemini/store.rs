@@ -0,0 +1,433 @@
+/**
+ * ============================================================================
+ * GEMINI JOB STORE MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Durable state of every queued job (append-only journal)
+ *
+ * JOB STATES:
+ * pending -> in_flight -> (done | failed)
+ * Done and cancelled jobs are dropped; failed jobs form the dead-letter store
+ * and go back to pending when retried.
+ *
+ * JOURNAL:
+ * - One JSON record per line in gemini_jobs.jsonl, each a single state
+ *   transition, flushed and synced before the transition counts
+ * - Replayed on startup; a torn last line (crash mid-write) is ignored
+ * - Jobs left in flight by a crash are recovered as pending
+ *
+ * COMPACTION:
+ * When the journal holds many more records than live jobs, it is rewritten
+ * as a snapshot (one record per live job) to a temp file which then
+ * atomically replaces the journal. Also done on every open.
+ *
+ * ============================================================================
+ */
+
+use crate::recording::gemini::types::{DeadLetterJob, GeminiJob};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Never compact journals shorter than this
+const COMPACT_MIN_RECORDS: usize = 1000;
+
+/// Compact once records outnumber live jobs by this factor
+const COMPACT_RATIO: usize = 4;
+
+/// Jobs are identified by segment and display
+pub type JobKey = (String, u32);
+
+pub fn job_key(job: &GeminiJob) -> JobKey {
+    (job.segment_id.clone(), job.display_index)
+}
+
+/// One state transition
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum JournalRecord {
+    Enqueued { job: GeminiJob },
+    Started { segment_id: String, display_index: u32 },
+    Updated { job: GeminiJob },
+    Completed { segment_id: String, display_index: u32 },
+    Failed { job: GeminiJob, error: String, failed_at: DateTime<Utc> },
+    Removed { segment_id: String, display_index: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum JobState {
+    Pending,
+    InFlight,
+    Failed { error: String, failed_at: DateTime<Utc> },
+}
+
+#[derive(Debug, Clone)]
+struct StoredJob {
+    job: GeminiJob,
+    state: JobState,
+    /// Enqueue order
+    seq: u64,
+}
+
+/**
+ * Journal-backed job store
+ */
+pub struct JobStore {
+    path: PathBuf,
+    file: File,
+    records: usize,
+    jobs: HashMap<JobKey, StoredJob>,
+    next_seq: u64,
+}
+
+impl JobStore {
+    /**
+     * Open (or create) the journal, replay it and recover in-flight jobs
+     */
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let mut jobs = HashMap::new();
+        let mut next_seq = 0;
+
+        if path.exists() {
+            let file = File::open(path).map_err(|e| format!("Failed to open job journal: {}", e))?;
+            for (line_number, line) in BufReader::new(file).lines().enumerate() {
+                let line = line.map_err(|e| format!("Failed to read job journal: {}", e))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str::<JournalRecord>(&line) {
+                    Ok(record) => apply(&mut jobs, &mut next_seq, record),
+                    Err(e) => log::warn!("[GEMINI-STORE] Skipping unreadable journal line {}: {}", line_number + 1, e),
+                }
+            }
+        }
+
+        let mut recovered = 0;
+        for stored in jobs.values_mut() {
+            if stored.state == JobState::InFlight {
+                stored.state = JobState::Pending;
+                recovered += 1;
+            }
+        }
+        if recovered > 0 {
+            log::info!("[GEMINI-STORE] Recovered {} job(s) interrupted mid-analysis", recovered);
+        }
+
+        let file = open_append(path)?;
+        let mut store = Self {
+            path: path.to_path_buf(),
+            file,
+            records: 0,
+            jobs,
+            next_seq,
+        };
+        store.compact()?;
+        Ok(store)
+    }
+
+    /// Pending jobs in enqueue order
+    pub fn pending(&self) -> Vec<GeminiJob> {
+        let mut pending: Vec<&StoredJob> = self.jobs.values().filter(|s| s.state == JobState::Pending).collect();
+        pending.sort_by_key(|s| s.seq);
+        pending.into_iter().map(|s| s.job.clone()).collect()
+    }
+
+    /// Dead-letter jobs
+    pub fn failed(&self) -> Vec<DeadLetterJob> {
+        self.jobs
+            .values()
+            .filter_map(|s| match &s.state {
+                JobState::Failed { error, failed_at } => Some(DeadLetterJob {
+                    job: s.job.clone(),
+                    error: error.clone(),
+                    failed_at: *failed_at,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether the job is pending or in flight
+    pub fn is_queued(&self, key: &JobKey) -> bool {
+        self.jobs.get(key).is_some_and(|s| !matches!(s.state, JobState::Failed { .. }))
+    }
+
+    /// Whether the job is in the dead-letter store
+    pub fn is_failed(&self, key: &JobKey) -> bool {
+        self.jobs.get(key).is_some_and(|s| matches!(s.state, JobState::Failed { .. }))
+    }
+
+    /// Add a job as pending (also re-queues a failed one)
+    pub fn enqueue(&mut self, job: &GeminiJob) -> Result<(), String> {
+        self.append(JournalRecord::Enqueued { job: job.clone() })
+    }
+
+    pub fn start(&mut self, key: &JobKey) -> Result<(), String> {
+        self.append(JournalRecord::Started {
+            segment_id: key.0.clone(),
+            display_index: key.1,
+        })
+    }
+
+    /// Record changed retry counters or model position
+    pub fn update(&mut self, job: &GeminiJob) -> Result<(), String> {
+        self.append(JournalRecord::Updated { job: job.clone() })
+    }
+
+    pub fn complete(&mut self, key: &JobKey) -> Result<(), String> {
+        self.append(JournalRecord::Completed {
+            segment_id: key.0.clone(),
+            display_index: key.1,
+        })
+    }
+
+    /// Move a job to the dead-letter store
+    pub fn fail(&mut self, job: &GeminiJob, error: &str) -> Result<(), String> {
+        self.append(JournalRecord::Failed {
+            job: job.clone(),
+            error: error.to_string(),
+            failed_at: Utc::now(),
+        })
+    }
+
+    /// Drop a job in any state (cancel)
+    pub fn remove(&mut self, key: &JobKey) -> Result<(), String> {
+        if !self.jobs.contains_key(key) {
+            return Ok(());
+        }
+        self.append(JournalRecord::Removed {
+            segment_id: key.0.clone(),
+            display_index: key.1,
+        })
+    }
+
+    // Write and sync one record, then apply it in memory
+    fn append(&mut self, record: JournalRecord) -> Result<(), String> {
+        let mut line = serde_json::to_string(&record).map_err(|e| format!("Failed to serialize job record: {}", e))?;
+        line.push('\n');
+
+        self.file
+            .write_all(line.as_bytes())
+            .and_then(|_| self.file.sync_data())
+            .map_err(|e| format!("Failed to write job journal: {}", e))?;
+
+        self.records += 1;
+        apply(&mut self.jobs, &mut self.next_seq, record);
+
+        if self.records >= COMPACT_MIN_RECORDS && self.records > self.jobs.len() * COMPACT_RATIO {
+            self.compact()?;
+        }
+        Ok(())
+    }
+
+    /**
+     * Rewrite the journal as one record per live job
+     * The snapshot replaces the journal by rename, so a crash keeps either one
+     */
+    pub fn compact(&mut self) -> Result<(), String> {
+        let mut live: Vec<&StoredJob> = self.jobs.values().collect();
+        live.sort_by_key(|s| s.seq);
+
+        let mut snapshot = String::new();
+        for stored in &live {
+            let record = match &stored.state {
+                JobState::Failed { error, failed_at } => JournalRecord::Failed {
+                    job: stored.job.clone(),
+                    error: error.clone(),
+                    failed_at: *failed_at,
+                },
+                _ => JournalRecord::Enqueued { job: stored.job.clone() },
+            };
+            snapshot.push_str(&serde_json::to_string(&record).map_err(|e| format!("Failed to serialize job record: {}", e))?);
+            snapshot.push('\n');
+            if stored.state == JobState::InFlight {
+                let started = JournalRecord::Started {
+                    segment_id: stored.job.segment_id.clone(),
+                    display_index: stored.job.display_index,
+                };
+                snapshot.push_str(&serde_json::to_string(&started).map_err(|e| format!("Failed to serialize job record: {}", e))?);
+                snapshot.push('\n');
+            }
+        }
+
+        let temp_path = self.path.with_extension("jsonl.tmp");
+        let mut temp = File::create(&temp_path).map_err(|e| format!("Failed to create job journal snapshot: {}", e))?;
+        temp.write_all(snapshot.as_bytes())
+            .and_then(|_| temp.sync_all())
+            .map_err(|e| format!("Failed to write job journal snapshot: {}", e))?;
+        std::fs::rename(&temp_path, &self.path).map_err(|e| format!("Failed to replace job journal: {}", e))?;
+
+        self.file = open_append(&self.path)?;
+        self.records = snapshot.lines().count();
+        log::debug!("[GEMINI-STORE] Compacted job journal to {} record(s)", self.records);
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> Result<File, String> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open job journal: {}", e))
+}
+
+// Apply one transition to the in-memory state
+fn apply(jobs: &mut HashMap<JobKey, StoredJob>, next_seq: &mut u64, record: JournalRecord) {
+    match record {
+        JournalRecord::Enqueued { job } => {
+            // Re-submitting a queued job keeps its place
+            let key = job_key(&job);
+            if jobs.get(&key).is_some_and(|s| !matches!(s.state, JobState::Failed { .. })) {
+                return;
+            }
+            jobs.insert(
+                key,
+                StoredJob {
+                    job,
+                    state: JobState::Pending,
+                    seq: *next_seq,
+                },
+            );
+            *next_seq += 1;
+        }
+        JournalRecord::Started { segment_id, display_index } => {
+            if let Some(stored) = jobs.get_mut(&(segment_id, display_index)) {
+                stored.state = JobState::InFlight;
+            }
+        }
+        JournalRecord::Updated { job } => {
+            if let Some(stored) = jobs.get_mut(&job_key(&job)) {
+                stored.job = job;
+            }
+        }
+        JournalRecord::Failed { job, error, failed_at } => {
+            let key = job_key(&job);
+            let seq = jobs.get(&key).map(|s| s.seq).unwrap_or_else(|| {
+                *next_seq += 1;
+                *next_seq - 1
+            });
+            jobs.insert(
+                key,
+                StoredJob {
+                    job,
+                    state: JobState::Failed { error, failed_at },
+                    seq,
+                },
+            );
+        }
+        JournalRecord::Completed { segment_id, display_index } | JournalRecord::Removed { segment_id, display_index } => {
+            jobs.remove(&(segment_id, display_index));
+        }
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::gemini::fixtures;
+
+    fn test_job(segment_id: &str) -> GeminiJob {
+        fixtures::test_job(segment_id, "2025-01-15T10:00:00Z")
+    }
+
+    fn journal_path() -> PathBuf {
+        std::env::temp_dir().join(format!("gemini-jobs-{}.jsonl", uuid::Uuid::new_v4()))
+    }
+
+    fn ids(jobs: &[GeminiJob]) -> Vec<&str> {
+        jobs.iter().map(|j| j.segment_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_transitions_survive_reopen() {
+        let path = journal_path();
+        {
+            let mut store = JobStore::open(&path).unwrap();
+            for id in ["a", "b", "c", "d"] {
+                store.enqueue(&test_job(id)).unwrap();
+            }
+            // Duplicate submission keeps the original position
+            store.enqueue(&test_job("a")).unwrap();
+
+            store.start(&job_key(&test_job("a"))).unwrap();
+            store.complete(&job_key(&test_job("a"))).unwrap();
+
+            let mut b = test_job("b");
+            store.start(&job_key(&b)).unwrap();
+            b.retry_count = 2;
+            store.update(&b).unwrap();
+            store.fail(&b, "Permanent error").unwrap();
+
+            store.remove(&job_key(&test_job("c"))).unwrap();
+            // Crash while "d" is being analyzed
+            store.start(&job_key(&test_job("d"))).unwrap();
+            assert!(store.is_queued(&job_key(&test_job("d"))));
+        }
+
+        let mut store = JobStore::open(&path).unwrap();
+        assert_eq!(ids(&store.pending()), vec!["d"]);
+        let failed = store.failed();
+        assert_eq!(failed.len(), 1);
+        assert_eq!((failed[0].job.retry_count, failed[0].error.as_str()), (2, "Permanent error"));
+        assert!(store.is_failed(&job_key(&failed[0].job)) && !store.is_queued(&job_key(&failed[0].job)));
+
+        // Retrying a failed job puts it back at the end of the queue
+        store.enqueue(&failed[0].job).unwrap();
+        assert_eq!(ids(&store.pending()), vec!["d", "b"]);
+        assert!(store.failed().is_empty());
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_torn_last_line_is_ignored() {
+        let path = journal_path();
+        {
+            let mut store = JobStore::open(&path).unwrap();
+            store.enqueue(&test_job("a")).unwrap();
+            store.enqueue(&test_job("b")).unwrap();
+        }
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(br#"{"op":"completed","segment_id":"a","disp"#).unwrap();
+        drop(file);
+
+        let store = JobStore::open(&path).unwrap();
+        assert_eq!(ids(&store.pending()), vec!["a", "b"]);
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_compaction_keeps_live_jobs_only() {
+        let path = journal_path();
+        let mut store = JobStore::open(&path).unwrap();
+
+        store.enqueue(&test_job("keep")).unwrap();
+        for i in 0..COMPACT_MIN_RECORDS {
+            let job = test_job(&format!("done_{}", i));
+            store.enqueue(&job).unwrap();
+            store.complete(&job_key(&job)).unwrap();
+        }
+
+        // Compaction ran along the way; the journal holds a few records, not thousands
+        let lines = std::fs::read_to_string(&path).unwrap().lines().count();
+        assert!(lines < COMPACT_MIN_RECORDS, "journal has {} lines", lines);
+        assert_eq!(ids(&store.pending()), vec!["keep"]);
+
+        drop(store);
+        let store = JobStore::open(&path).unwrap();
+        assert_eq!(ids(&store.pending()), vec!["keep"]);
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 1);
+
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -8,14 +8,23 @@
  * TYPES:
  * - TimelineEntry: Single activity event from video analysis
  * - TimelineAnalysis: Complete analysis result for a segment
+ * - TimelineQuality: Coverage and repair summary of an analysis
+ * - EntryFusion / FusionSummary: Agreement with ActivityWatch window events
+ * - SpeedupMode / SpeedupSummary: Speed-up preprocessing and its token savings
+ * - TokenUsage: Tokens used by a job's model requests
  * - GeminiJob: Queue job for processing
+ * - Reanalysis: Prompt and model overrides of a reanalysis job
+ * - DeadLetterJob: Permanently failed job kept for retry
  * - GeminiJobStatus: Processing status enum
  * - GeminiConfig: Configuration for Gemini integration
+ * - IdleAction: Handling of segments the user was away for
+ * - AnalyzerBackend / HttpAnalyzerConfig: Video analysis provider selection
+ * - ModelTarget / RoutingRule: Model fallback chain and per-job routing
  * 
  * ============================================================================
  */
 
-use crate::recording::types::RecordingMetadata;
+use crate::recording::types::{RecordingMetadata, ScreenClassification};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -104,6 +113,30 @@ pub struct TimelineEntry {
     /// Productivity score 1-5
     /// 5: Highly Productive, 4: Productive, 3: Neutral, 2: Low, 1: Distraction
     pub productive_score: u8,
+    
+    /// Agreement with ActivityWatch window events (None = not fused)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fusion: Option<EntryFusion>,
+}
+
+/**
+ * ActivityWatch agreement of one fused timeline entry
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryFusion {
+    /// Share of the entry ActivityWatch reports its application focused (0-1)
+    pub confidence: f64,
+    
+    /// ActivityWatch reports another application for most of the entry
+    pub disagreement: bool,
+    
+    /// Application the model named, when replaced by the ActivityWatch name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_application: Option<String>,
+    
+    /// Start or end moved to an ActivityWatch window switch
+    pub snapped: bool,
 }
 
 /**
@@ -138,6 +171,127 @@ pub struct TimelineAnalysis {
     
     /// All timeline entries from analysis
     pub timeline: Vec<TimelineEntry>,
+    
+    /// Backend that produced the analysis
+    #[serde(default)]
+    pub backend: AnalyzerBackend,
+    
+    /// Model that produced the analysis
+    #[serde(default)]
+    pub model: String,
+    
+    /// Prompt template used, "name@version" (empty for analyses made before templates)
+    #[serde(default)]
+    pub prompt_version: String,
+    
+    /// Timeline coverage and repair summary (None for analyses made before validation)
+    #[serde(default)]
+    pub quality: Option<TimelineQuality>,
+    
+    /// Speed-up preprocessing applied (None = analyzed at real time)
+    #[serde(default)]
+    pub speedup: Option<SpeedupSummary>,
+    
+    /// Tokens reported by the backend (None if it doesn't report usage)
+    #[serde(default)]
+    pub usage: Option<TokenUsage>,
+    
+    /// Generated from ActivityWatch AFK data instead of a model (away timeline)
+    #[serde(default)]
+    pub synthetic: bool,
+    
+    /// Fusion with ActivityWatch window events (None = not fused)
+    #[serde(default)]
+    pub fusion: Option<FusionSummary>,
+    
+    /// 1 = first analysis of the display, reanalyses count up
+    #[serde(default = "default_analysis_version")]
+    pub analysis_version: u32,
+}
+
+fn default_analysis_version() -> u32 {
+    1
+}
+
+/**
+ * Tokens used by a job's model requests (corrections and windows included)
+ */
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TokenUsage {
+    /// Text input (prompt, correction turns)
+    pub prompt_tokens: u64,
+    
+    /// Video (or frame) input
+    pub video_tokens: u64,
+    
+    /// Model output, thinking included
+    pub output_tokens: u64,
+}
+
+impl TokenUsage {
+    pub fn add(&mut self, other: &TokenUsage) {
+        self.prompt_tokens += other.prompt_tokens;
+        self.video_tokens += other.video_tokens;
+        self.output_tokens += other.output_tokens;
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.prompt_tokens == 0 && self.video_tokens == 0 && self.output_tokens == 0
+    }
+}
+
+/**
+ * Speed-up applied to an analysis and its estimated token savings
+ * Estimates use Gemini's default video sampling (1 frame/s, 258 tokens/frame)
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SpeedupSummary {
+    pub mode: SpeedupMode,
+    
+    /// Speed-up factor (timestamps were rescaled back to real time)
+    pub factor: f64,
+    
+    /// Estimated video tokens of the analyzed input
+    pub estimated_tokens: u64,
+    
+    /// Estimated video tokens saved compared to real-time analysis
+    pub estimated_tokens_saved: u64,
+}
+
+/**
+ * How well a model timeline covered the video and how much it was repaired
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimelineQuality {
+    /// Share of the video covered by the model's own entries (0-1)
+    pub coverage: f64,
+    
+    /// Entries clamped, reordered, trimmed or dropped during validation
+    pub repairs: u32,
+    
+    /// The model had to be asked to correct its first response
+    pub corrected: bool,
+    
+    /// Overall quality (0-1): coverage reduced per repair
+    pub score: f64,
+}
+
+/**
+ * What fusion with ActivityWatch window events changed in a timeline
+ */
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct FusionSummary {
+    /// Entry boundaries moved to window switches
+    pub boundaries_snapped: u32,
+    
+    /// Application names replaced by the ActivityWatch name
+    pub apps_corrected: u32,
+    
+    /// Entries ActivityWatch disagrees with
+    pub disagreements: u32,
+    
+    /// Duration-weighted confidence of the fused entries (0-1)
+    pub confidence: f64,
 }
 
 // =============================================================================
@@ -172,6 +326,40 @@ pub struct GeminiJob {
     
     /// When the job was created
     pub created_at: DateTime<Utc>,
+    
+    /// Position in the model chain currently being tried
+    #[serde(default)]
+    pub model_index: usize,
+    
+    /// Set for reanalysis of an already analyzed segment (reanalysis.rs)
+    #[serde(default)]
+    pub reanalysis: Option<Reanalysis>,
+}
+
+/**
+ * Prompt and model chosen for a reanalysis job
+ * Its result is saved as a new analysis version instead of replacing the
+ * earlier one
+ */
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Reanalysis {
+    /// Pinned prompt template ("name@N"; None = configured prompt)
+    #[serde(default)]
+    pub prompt_template: Option<String>,
+    
+    /// Model to use instead of the configured chain (None = configured chain)
+    #[serde(default)]
+    pub target: Option<ModelTarget>,
+}
+
+/**
+ * A permanently failed job kept in the dead-letter store for retry
+ */
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetterJob {
+    pub job: GeminiJob,
+    pub error: String,
+    pub failed_at: DateTime<Utc>,
 }
 
 /**
@@ -189,7 +377,7 @@ pub enum GeminiJobStatus {
     Analyzing,
     
     /// Analysis completed successfully
-    Completed(TimelineAnalysis),
+    Completed(Box<TimelineAnalysis>),
     
     /// Job failed with error message
     Failed(String),
@@ -211,6 +399,10 @@ pub struct GeminiConfig {
     #[serde(default)]
     pub rate_limit_per_minute: u32,
     
+    /// Jobs analyzed in parallel
+    #[serde(default = "default_max_concurrent_jobs")]
+    pub max_concurrent_jobs: u32,
+    
     /// Maximum retry attempts for failed jobs (non-rate-limit errors)
     #[serde(default = "default_max_retries")]
     pub max_retries: u32,
@@ -230,6 +422,267 @@ pub struct GeminiConfig {
     /// Maximum wait duration for a single rate limit (seconds)
     #[serde(default = "default_rate_limit_max_wait_seconds")]
     pub rate_limit_max_wait_seconds: u64,
+    
+    /// Display classifications that are not worth analyzing
+    #[serde(default = "default_skip_classifications")]
+    pub skip_classifications: Vec<ScreenClassification>,
+    
+    /// Segments with less ActivityWatch active (not-afk) time than this
+    /// (seconds) are idle, 0 = analyze every segment
+    #[serde(default = "default_idle_threshold_seconds")]
+    pub idle_threshold_seconds: u64,
+    
+    /// What to do with idle segments
+    #[serde(default)]
+    pub idle_action: IdleAction,
+    
+    /// Speed-up for idle segments with idle_action = reduced
+    #[serde(default = "default_idle_speedup_factor")]
+    pub idle_speedup_factor: f64,
+    
+    /// Which provider analyzes the videos
+    #[serde(default)]
+    pub backend: AnalyzerBackend,
+    
+    /// OpenAI-compatible chat completions endpoint (backend = openai_compatible)
+    #[serde(default = "default_openai_backend")]
+    pub openai: HttpAnalyzerConfig,
+    
+    /// Local model server, Ollama API (backend = local)
+    #[serde(default = "default_local_backend")]
+    pub local: HttpAnalyzerConfig,
+    
+    /// Ordered models to try; a model that stays unavailable or rate limited
+    /// falls through to the next. Empty = the selected backend's model only
+    #[serde(default)]
+    pub model_chain: Vec<ModelTarget>,
+    
+    /// Rules picking a different chain per job; first match wins
+    #[serde(default)]
+    pub routing_rules: Vec<RoutingRule>,
+    
+    /// Gemini videos larger than this (MB) are uploaded via the Files API
+    /// instead of being sent inline as base64
+    #[serde(default = "default_files_api_threshold_mb")]
+    pub files_api_threshold_mb: u64,
+    
+    /// Videos longer than this (minutes) are analyzed in overlapping windows
+    /// whose timelines are stitched back together, 0 = never split
+    #[serde(default = "default_analysis_window_minutes")]
+    pub analysis_window_minutes: u64,
+    
+    /// Overlap between consecutive analysis windows (seconds)
+    #[serde(default = "default_analysis_window_overlap_seconds")]
+    pub analysis_window_overlap_seconds: u64,
+    
+    /// Preprocessing that trades timeline granularity for token cost
+    #[serde(default)]
+    pub speedup_mode: SpeedupMode,
+    
+    /// Speed-up factor for speedup_mode (values below 1 count as 1)
+    #[serde(default = "default_speedup_factor")]
+    pub speedup_factor: f64,
+    
+    /// Prompt template: None = built-in, "name" = latest version, "name@N" = pinned
+    #[serde(default)]
+    pub prompt_template: Option<String>,
+    
+    /// Organization productivity rubric for the {{rubric}} template variable
+    /// (None = built-in 1-5 rubric)
+    #[serde(default)]
+    pub prompt_rubric: Option<String>,
+    
+    /// Include ActivityWatch app/window title switches in the prompt
+    /// ({{window_context}}; privacy rules apply)
+    #[serde(default = "default_window_context")]
+    pub window_context: bool,
+    
+    /// Fuse timelines with ActivityWatch window events before sending them
+    /// (snap boundaries, canonical app names, confidence)
+    #[serde(default = "default_window_fusion")]
+    pub window_fusion: bool,
+    
+    /// Entry boundaries within this distance (seconds) of a window switch
+    /// are moved to it
+    #[serde(default = "default_fusion_snap_seconds")]
+    pub fusion_snap_seconds: f64,
+    
+    /// Input (prompt and video) price in USD per million tokens, for cost estimates
+    #[serde(default = "default_input_price_per_million_tokens")]
+    pub input_price_per_million_tokens: f64,
+    
+    /// Output (thinking included) price in USD per million tokens
+    #[serde(default = "default_output_price_per_million_tokens")]
+    pub output_price_per_million_tokens: f64,
+    
+    /// Estimated spend per local day (USD) at which the queue pauses, None = no cap
+    #[serde(default)]
+    pub daily_budget_usd: Option<f64>,
+    
+    /// Estimated spend per local month (USD) at which the queue pauses, None = no cap
+    #[serde(default)]
+    pub monthly_budget_usd: Option<f64>,
+}
+
+impl GeminiConfig {
+    /**
+     * Model chain for a segment: first matching routing rule, else the
+     * default chain, else the selected backend alone
+     */
+    pub fn chain_for(&self, duration_seconds: f64, high_risk: bool) -> Vec<ModelTarget> {
+        if let Some(rule) = self
+            .routing_rules
+            .iter()
+            .find(|r| !r.chain.is_empty() && r.matches(duration_seconds, high_risk))
+        {
+            return rule.chain.clone();
+        }
+        
+        if !self.model_chain.is_empty() {
+            return self.model_chain.clone();
+        }
+        
+        vec![ModelTarget {
+            backend: self.backend,
+            model: None,
+            base_url: None,
+        }]
+    }
+    
+    /// Playback speed videos are re-encoded at (1 = not re-encoded)
+    pub fn playback_speed(&self) -> f64 {
+        match self.speedup_mode {
+            SpeedupMode::Reencode => self.speedup_factor.max(1.0),
+            _ => 1.0,
+        }
+    }
+    
+    /// How many times fewer frames are sampled than normal (1 = normal)
+    pub fn sampling_factor(&self) -> f64 {
+        match self.speedup_mode {
+            SpeedupMode::ReducedFrameRate => self.speedup_factor.max(1.0),
+            _ => 1.0,
+        }
+    }
+}
+
+/**
+ * Handling of segments the user was away for (ActivityWatch AFK)
+ */
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IdleAction {
+    /// Don't analyze; emit a synthetic "away" timeline instead
+    #[default]
+    Skip,
+    
+    /// Analyze re-encoded at idle_speedup_factor x speed
+    Reduced,
+}
+
+/**
+ * Speed-up preprocessing before analysis
+ */
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeedupMode {
+    /// Analyze at real time
+    #[default]
+    Off,
+    
+    /// Re-encode at speedup_factor x speed with FFmpeg; timestamps are
+    /// rescaled back to real time
+    Reencode,
+    
+    /// Keep the video but sample speedup_factor times fewer frames
+    /// (Gemini video frame rate, frame count of frame-based backends)
+    ReducedFrameRate,
+}
+
+/**
+ * One model/endpoint in a fallback chain
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ModelTarget {
+    pub backend: AnalyzerBackend,
+    
+    /// Model name (None = the backend's configured model)
+    #[serde(default)]
+    pub model: Option<String>,
+    
+    /// Endpoint override (None = the backend's configured base URL)
+    #[serde(default)]
+    pub base_url: Option<String>,
+}
+
+/**
+ * Route matching segments to their own model chain
+ * Unset conditions match everything
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RoutingRule {
+    /// Label for logs and settings UI
+    #[serde(default)]
+    pub name: String,
+    
+    /// Segment duration lower bound (seconds, inclusive)
+    #[serde(default)]
+    pub min_duration_seconds: Option<f64>,
+    
+    /// Segment duration upper bound (seconds, exclusive)
+    #[serde(default)]
+    pub max_duration_seconds: Option<f64>,
+    
+    /// Match only segments with this high-risk flag
+    #[serde(default)]
+    pub high_risk: Option<bool>,
+    
+    pub chain: Vec<ModelTarget>,
+}
+
+impl RoutingRule {
+    pub fn matches(&self, duration_seconds: f64, high_risk: bool) -> bool {
+        self.min_duration_seconds.is_none_or(|min| duration_seconds >= min)
+            && self.max_duration_seconds.is_none_or(|max| duration_seconds < max)
+            && self.high_risk.is_none_or(|flag| flag == high_risk)
+    }
+}
+
+/**
+ * Video analysis provider
+ */
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalyzerBackend {
+    /// Google Gemini (whole video uploaded)
+    #[default]
+    Gemini,
+    
+    /// Any OpenAI-compatible multimodal endpoint (sampled frames)
+    OpenaiCompatible,
+    
+    /// Local model server such as Ollama (sampled frames, nothing leaves the machine)
+    Local,
+}
+
+/**
+ * Endpoint settings for frame-based HTTP backends
+ */
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HttpAnalyzerConfig {
+    /// Base URL (e.g. "https://api.openai.com/v1", "http://localhost:11434")
+    pub base_url: String,
+    
+    /// Vision model name
+    pub model: String,
+    
+    /// Maximum frames sampled from a video per request
+    #[serde(default = "default_max_frames")]
+    pub max_frames: u32,
+}
+
+fn default_max_concurrent_jobs() -> u32 {
+    2
 }
 
 fn default_max_retries() -> u32 {
@@ -252,16 +705,110 @@ fn default_rate_limit_max_wait_seconds() -> u64 {
     120
 }
 
+fn default_openai_backend() -> HttpAnalyzerConfig {
+    HttpAnalyzerConfig {
+        base_url: "https://api.openai.com/v1".to_string(),
+        model: "gpt-4o-mini".to_string(),
+        max_frames: default_max_frames(),
+    }
+}
+
+fn default_local_backend() -> HttpAnalyzerConfig {
+    HttpAnalyzerConfig {
+        base_url: "http://localhost:11434".to_string(),
+        model: "qwen2.5vl".to_string(),
+        max_frames: default_max_frames(),
+    }
+}
+
+fn default_max_frames() -> u32 {
+    12
+}
+
+// Inline requests are capped at 20 MB and base64 adds a third
+fn default_files_api_threshold_mb() -> u64 {
+    15
+}
+
+fn default_analysis_window_minutes() -> u64 {
+    20
+}
+
+fn default_analysis_window_overlap_seconds() -> u64 {
+    30
+}
+
+fn default_speedup_factor() -> f64 {
+    4.0
+}
+
+// gemini-2.5-flash-lite list prices
+fn default_input_price_per_million_tokens() -> f64 {
+    0.10
+}
+
+fn default_output_price_per_million_tokens() -> f64 {
+    0.40
+}
+
+fn default_idle_threshold_seconds() -> u64 {
+    15
+}
+
+fn default_idle_speedup_factor() -> f64 {
+    8.0
+}
+
+fn default_window_context() -> bool {
+    true
+}
+
+fn default_window_fusion() -> bool {
+    true
+}
+
+fn default_fusion_snap_seconds() -> f64 {
+    10.0
+}
+
+fn default_skip_classifications() -> Vec<ScreenClassification> {
+    vec![ScreenClassification::Blank, ScreenClassification::Locked]
+}
+
 impl Default for GeminiConfig {
     fn default() -> Self {
         Self {
             enabled: false,
             rate_limit_per_minute: 0, // No rate limiting initially
+            max_concurrent_jobs: default_max_concurrent_jobs(),
             max_retries: default_max_retries(),
             retry_delay_seconds: default_retry_delay(),
             thinking_budget: default_thinking_budget(),
             rate_limit_max_waits: default_rate_limit_max_waits(),
             rate_limit_max_wait_seconds: default_rate_limit_max_wait_seconds(),
+            skip_classifications: default_skip_classifications(),
+            idle_threshold_seconds: default_idle_threshold_seconds(),
+            idle_action: IdleAction::default(),
+            idle_speedup_factor: default_idle_speedup_factor(),
+            backend: AnalyzerBackend::default(),
+            openai: default_openai_backend(),
+            local: default_local_backend(),
+            model_chain: Vec::new(),
+            routing_rules: Vec::new(),
+            files_api_threshold_mb: default_files_api_threshold_mb(),
+            analysis_window_minutes: default_analysis_window_minutes(),
+            analysis_window_overlap_seconds: default_analysis_window_overlap_seconds(),
+            speedup_mode: SpeedupMode::default(),
+            speedup_factor: default_speedup_factor(),
+            prompt_template: None,
+            prompt_rubric: None,
+            window_context: default_window_context(),
+            window_fusion: default_window_fusion(),
+            fusion_snap_seconds: default_fusion_snap_seconds(),
+            input_price_per_million_tokens: default_input_price_per_million_tokens(),
+            output_price_per_million_tokens: default_output_price_per_million_tokens(),
+            daily_budget_usd: None,
+            monthly_budget_usd: None,
         }
     }
 }
@@ -320,6 +867,77 @@ mod tests {
         assert_eq!(config.thinking_budget, 1024);
         assert_eq!(config.rate_limit_max_waits, 5);
         assert_eq!(config.rate_limit_max_wait_seconds, 120);
+        assert_eq!(config.backend, AnalyzerBackend::Gemini);
+        assert_eq!(config.playback_speed(), 1.0);
+        assert_eq!(config.sampling_factor(), 1.0);
+    }
+    
+    #[test]
+    fn test_speedup_modes() {
+        let mut config: GeminiConfig =
+            serde_json::from_str(r#"{"enabled": true, "speedup_mode": "reencode", "speedup_factor": 3}"#).unwrap();
+        assert_eq!(config.playback_speed(), 3.0);
+        assert_eq!(config.sampling_factor(), 1.0);
+        
+        config.speedup_mode = SpeedupMode::ReducedFrameRate;
+        config.speedup_factor = 0.5;
+        assert_eq!(config.playback_speed(), 1.0);
+        assert_eq!(config.sampling_factor(), 1.0);
+    }
+    
+    #[test]
+    fn test_gemini_config_backend_defaults() {
+        // Configs saved before backends existed keep using Gemini
+        let config: GeminiConfig = serde_json::from_str(r#"{"enabled": true}"#).unwrap();
+        assert_eq!(config.backend, AnalyzerBackend::Gemini);
+        assert_eq!(config.local.base_url, "http://localhost:11434");
+        
+        let config: GeminiConfig = serde_json::from_str(
+            r#"{"enabled": true, "backend": "openai_compatible", "openai": {"base_url": "http://10.0.0.5:8000/v1", "model": "llava"}}"#,
+        ).unwrap();
+        assert_eq!(config.backend, AnalyzerBackend::OpenaiCompatible);
+        assert_eq!(config.openai.model, "llava");
+        assert_eq!(config.openai.max_frames, 12);
+    }
+    
+    #[test]
+    fn test_chain_for_routing() {
+        let target = |model: &str| ModelTarget {
+            backend: AnalyzerBackend::Gemini,
+            model: Some(model.to_string()),
+            base_url: None,
+        };
+        
+        let mut config = GeminiConfig::default();
+        assert_eq!(config.chain_for(60.0, false), vec![ModelTarget {
+            backend: AnalyzerBackend::Gemini,
+            model: None,
+            base_url: None,
+        }]);
+        
+        config.model_chain = vec![target("gemini-2.5-flash-lite"), target("gemini-2.5-flash")];
+        config.routing_rules = vec![
+            RoutingRule {
+                name: "high risk".to_string(),
+                min_duration_seconds: None,
+                max_duration_seconds: None,
+                high_risk: Some(true),
+                chain: vec![target("gemini-2.5-pro")],
+            },
+            RoutingRule {
+                name: "long".to_string(),
+                min_duration_seconds: Some(600.0),
+                max_duration_seconds: None,
+                high_risk: None,
+                chain: vec![target("gemini-2.5-flash")],
+            },
+        ];
+        
+        assert_eq!(config.chain_for(60.0, false).len(), 2);
+        assert_eq!(config.chain_for(60.0, true)[0].model.as_deref(), Some("gemini-2.5-pro"));
+        assert_eq!(config.chain_for(900.0, true)[0].model.as_deref(), Some("gemini-2.5-pro"));
+        assert_eq!(config.chain_for(900.0, false)[0].model.as_deref(), Some("gemini-2.5-flash"));
+        assert_eq!(config.chain_for(599.0, false)[0].model.as_deref(), Some("gemini-2.5-flash-lite"));
     }
     
     #[test]
@@ -0,0 +1,294 @@
+/**
+ * ============================================================================
+ * GEMINI USAGE LEDGER MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Token usage and estimated cost of analyses, with budget caps
+ *
+ * LEDGER:
+ * - One JSON record per analyzed job in gemini_usage.jsonl (append-only),
+ *   with prompt, video and output tokens and the cost at the prices
+ *   configured when it was recorded
+ * - Records are kept as history; only per-day totals are held in memory
+ * - A torn last line (crash mid-write) is ignored
+ *
+ * DAYS:
+ * Days and months are local to the machine (the user's working day), the
+ * date is stored with each record.
+ *
+ * BUDGETS:
+ * daily_budget_usd / monthly_budget_usd in GeminiConfig; once reached the
+ * queue stops dispatching jobs until the next day (month) or the budget is
+ * raised (queue.rs).
+ *
+ * ============================================================================
+ */
+
+use crate::recording::gemini::types::{AnalyzerBackend, GeminiConfig, TokenUsage};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// Usage of one analyzed job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageRecord {
+    pub segment_id: String,
+    pub display_index: u32,
+    /// Local date the usage counts towards
+    pub date: NaiveDate,
+    pub recorded_at: DateTime<Utc>,
+    pub backend: AnalyzerBackend,
+    pub model: String,
+    #[serde(flatten)]
+    pub usage: TokenUsage,
+    pub cost_usd: f64,
+}
+
+/// Summed usage of a day or month
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UsageTotals {
+    /// Jobs that used tokens (failed attempts included)
+    pub jobs: u64,
+    pub prompt_tokens: u64,
+    pub video_tokens: u64,
+    pub output_tokens: u64,
+    pub estimated_cost_usd: f64,
+}
+
+impl UsageTotals {
+    fn add(&mut self, record: &UsageRecord) {
+        self.jobs += 1;
+        self.prompt_tokens += record.usage.prompt_tokens;
+        self.video_tokens += record.usage.video_tokens;
+        self.output_tokens += record.usage.output_tokens;
+        self.estimated_cost_usd += record.cost_usd;
+    }
+
+    fn merge(&mut self, other: &UsageTotals) {
+        self.jobs += other.jobs;
+        self.prompt_tokens += other.prompt_tokens;
+        self.video_tokens += other.video_tokens;
+        self.output_tokens += other.output_tokens;
+        self.estimated_cost_usd += other.estimated_cost_usd;
+    }
+}
+
+/**
+ * Append-only usage ledger with per-day totals
+ */
+pub struct UsageLedger {
+    file: File,
+    days: HashMap<NaiveDate, UsageTotals>,
+}
+
+impl UsageLedger {
+    /**
+     * Open (or create) the ledger and total its records per day
+     * A torn last record (crash mid-write) is cut off so the next record
+     * starts on its own line
+     */
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let mut days: HashMap<NaiveDate, UsageTotals> = HashMap::new();
+
+        if path.exists() {
+            let contents = std::fs::read(path).map_err(|e| format!("Failed to read usage ledger: {}", e))?;
+            let complete = contents.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+
+            if complete < contents.len() {
+                log::warn!(
+                    "[GEMINI-USAGE] Dropping torn ledger record ({} bytes)",
+                    contents.len() - complete
+                );
+                OpenOptions::new()
+                    .write(true)
+                    .open(path)
+                    .and_then(|file| file.set_len(complete as u64))
+                    .map_err(|e| format!("Failed to repair usage ledger: {}", e))?;
+            }
+
+            for line in contents[..complete].split(|&b| b == b'\n').filter(|line| !line.is_empty()) {
+                match serde_json::from_slice::<UsageRecord>(line) {
+                    Ok(record) => days.entry(record.date).or_default().add(&record),
+                    Err(e) => log::warn!("[GEMINI-USAGE] Skipping unreadable ledger record: {}", e),
+                }
+            }
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open usage ledger for writing: {}", e))?;
+
+        Ok(Self { file, days })
+    }
+
+    /// Append a record and count it towards its day
+    pub fn record(&mut self, record: &UsageRecord) -> Result<(), String> {
+        let line = serde_json::to_string(record).map_err(|e| format!("Failed to serialize usage: {}", e))?;
+        writeln!(self.file, "{}", line)
+            .and_then(|_| self.file.flush())
+            .map_err(|e| format!("Failed to write usage ledger: {}", e))?;
+
+        self.days.entry(record.date).or_default().add(record);
+        Ok(())
+    }
+
+    /// Totals of one day
+    pub fn day(&self, date: NaiveDate) -> UsageTotals {
+        self.days.get(&date).cloned().unwrap_or_default()
+    }
+
+    /// Totals of the month `date` falls in
+    pub fn month(&self, date: NaiveDate) -> UsageTotals {
+        let mut totals = UsageTotals::default();
+        for (day, day_totals) in &self.days {
+            if day.year() == date.year() && day.month() == date.month() {
+                totals.merge(day_totals);
+            }
+        }
+        totals
+    }
+}
+
+/**
+ * Estimated cost (USD) of token usage at the configured prices
+ * Video tokens are billed as input
+ */
+pub fn estimate_cost(usage: &TokenUsage, config: &GeminiConfig) -> f64 {
+    let input = (usage.prompt_tokens + usage.video_tokens) as f64 * config.input_price_per_million_tokens;
+    let output = usage.output_tokens as f64 * config.output_price_per_million_tokens;
+    (input + output) / 1_000_000.0
+}
+
+/**
+ * Check the day's and month's spend against the configured budgets
+ *
+ * # Returns
+ * * `Some(reason)` when a budget is reached
+ * * `None` when analysis may continue
+ */
+pub fn budget_exceeded(config: &GeminiConfig, today: &UsageTotals, month: &UsageTotals) -> Option<String> {
+    if let Some(budget) = config.daily_budget_usd
+        && today.estimated_cost_usd >= budget
+    {
+        return Some(format!(
+            "Daily analysis budget reached (${:.2} of ${:.2})",
+            today.estimated_cost_usd, budget
+        ));
+    }
+    if let Some(budget) = config.monthly_budget_usd
+        && month.estimated_cost_usd >= budget
+    {
+        return Some(format!(
+            "Monthly analysis budget reached (${:.2} of ${:.2})",
+            month.estimated_cost_usd, budget
+        ));
+    }
+    None
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(date: &str, prompt_tokens: u64, cost_usd: f64) -> UsageRecord {
+        UsageRecord {
+            segment_id: "segment_1".to_string(),
+            display_index: 0,
+            date: date.parse().unwrap(),
+            recorded_at: Utc::now(),
+            backend: AnalyzerBackend::Gemini,
+            model: "gemini-2.5-flash-lite".to_string(),
+            usage: TokenUsage {
+                prompt_tokens,
+                video_tokens: 1000,
+                output_tokens: 100,
+            },
+            cost_usd,
+        }
+    }
+
+    #[test]
+    fn test_ledger_totals_survive_reopen() {
+        let path = std::env::temp_dir().join(format!("gemini_usage_{}.jsonl", uuid::Uuid::new_v4()));
+
+        let mut ledger = UsageLedger::open(&path).unwrap();
+        ledger.record(&record("2025-01-14", 500, 0.25)).unwrap();
+        ledger.record(&record("2025-01-15", 500, 0.5)).unwrap();
+        ledger.record(&record("2025-01-15", 300, 0.5)).unwrap();
+        ledger.record(&record("2025-02-01", 300, 2.0)).unwrap();
+        drop(ledger);
+
+        // A torn last line is cut off, the next record stays readable
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "{{\"segment_id\": \"segm").unwrap();
+        drop(file);
+
+        let mut ledger = UsageLedger::open(&path).unwrap();
+        ledger.record(&record("2025-03-05", 100, 0.0)).unwrap();
+        drop(ledger);
+
+        let ledger = UsageLedger::open(&path).unwrap();
+        assert_eq!(ledger.day("2025-03-05".parse().unwrap()).jobs, 1);
+        let day = ledger.day("2025-01-15".parse().unwrap());
+        assert_eq!((day.jobs, day.prompt_tokens, day.video_tokens, day.output_tokens), (2, 800, 2000, 200));
+        assert_eq!(day.estimated_cost_usd, 1.0);
+
+        let month = ledger.month("2025-01-31".parse().unwrap());
+        assert_eq!(month.jobs, 3);
+        assert_eq!(month.estimated_cost_usd, 1.25);
+        assert_eq!(ledger.day("2025-01-16".parse().unwrap()), UsageTotals::default());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_estimate_cost() {
+        let config = GeminiConfig {
+            input_price_per_million_tokens: 0.1,
+            output_price_per_million_tokens: 0.4,
+            ..GeminiConfig::default()
+        };
+        let usage = TokenUsage {
+            prompt_tokens: 200_000,
+            video_tokens: 800_000,
+            output_tokens: 500_000,
+        };
+        assert!((estimate_cost(&usage, &config) - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_budget_exceeded() {
+        let mut config = GeminiConfig::default();
+        let today = UsageTotals {
+            estimated_cost_usd: 1.0,
+            ..UsageTotals::default()
+        };
+        let month = UsageTotals {
+            estimated_cost_usd: 20.0,
+            ..UsageTotals::default()
+        };
+
+        // No caps by default
+        assert_eq!(budget_exceeded(&config, &today, &month), None);
+
+        config.daily_budget_usd = Some(2.0);
+        config.monthly_budget_usd = Some(50.0);
+        assert_eq!(budget_exceeded(&config, &today, &month), None);
+
+        config.daily_budget_usd = Some(1.0);
+        assert!(budget_exceeded(&config, &today, &month).unwrap().starts_with("Daily"));
+
+        config.daily_budget_usd = None;
+        config.monthly_budget_usd = Some(20.0);
+        assert!(budget_exceeded(&config, &today, &month).unwrap().starts_with("Monthly"));
+    }
+}
@@ -0,0 +1,508 @@
+/**
+ * ============================================================================
+ * TIMELINE VALIDATION MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Validate and repair model timelines before they are stored
+ *
+ * REJECTED (the model is asked to correct itself once):
+ * - Empty timeline, unparseable times
+ * - productiveScore outside 1-5
+ *
+ * REPAIRED:
+ * - Reversed start/end swapped, times clamped to the video duration,
+ *   empty entries dropped
+ * - Overlaps trimmed (the later entry starts where the earlier one ends)
+ * - Adjacent duplicates (same app, title, description, score) merged
+ * - Gaps filled with an "Unknown" entry; gaps of a rounding error or less
+ *   are closed by stretching the neighbouring entry instead
+ *
+ * QUALITY:
+ * coverage = share of the video the model's own entries cover
+ * score = coverage, minus 5% per repair (clamped to 0..1)
+ *
+ * WINDOWS:
+ * Long videos are analyzed in overlapping windows (analyzer.rs). Their
+ * timelines are stitched by cutting each overlap at its midpoint and
+ * merging the same activity across the cut; quality is the average of the
+ * windows weighted by the time each contributes.
+ *
+ * SPEED-UP:
+ * Timelines of re-encoded (sped-up) videos are validated in video time and
+ * rescaled to real time afterwards (rescale_timeline).
+ *
+ * ============================================================================
+ */
+
+use crate::recording::gemini::{
+    formatter::parse_timestamp,
+    prompt::format_duration,
+    types::{TimelineEntry, TimelineQuality},
+};
+
+/// Gaps up to this long (seconds) are closed by stretching an entry
+const GAP_TOLERANCE_SECONDS: u32 = 2;
+
+/// Quality score penalty per repair
+const REPAIR_PENALTY: f64 = 0.05;
+
+/// Application name of entries filling unexplained gaps
+pub const UNKNOWN_APPLICATION: &str = "Unknown";
+
+/// Gap entries are scored neutral so they don't skew productivity averages
+const UNKNOWN_SCORE: u8 = 3;
+
+/**
+ * A timeline that passed validation, with its quality
+ */
+#[derive(Debug, Clone)]
+pub struct ValidatedTimeline {
+    pub timeline: Vec<TimelineEntry>,
+    pub quality: TimelineQuality,
+}
+
+/**
+ * Validated timeline of one analysis window of a long video
+ * (entry times relative to the window start)
+ */
+#[derive(Debug, Clone)]
+pub struct WindowTimeline {
+    pub start_seconds: u32,
+    pub end_seconds: u32,
+    pub timeline: ValidatedTimeline,
+}
+
+// Timeline entry with parsed times (seconds from video start)
+struct Span {
+    start: u32,
+    end: u32,
+    entry: TimelineEntry,
+}
+
+/**
+ * Validate a parsed timeline and repair what can be repaired
+ *
+ * # Arguments
+ * * `entries` - Timeline as returned by the model
+ * * `video_duration_seconds` - Video length (0 = unknown, no clamping)
+ *
+ * # Returns
+ * * `Ok(ValidatedTimeline)` - Contiguous timeline from 00:00 and its quality
+ * * `Err(String)` - Why the timeline is unusable (sent back to the model)
+ */
+pub fn validate_timeline(entries: Vec<TimelineEntry>, video_duration_seconds: f64) -> Result<ValidatedTimeline, String> {
+    if entries.is_empty() {
+        return Err("The timeline has no entries".to_string());
+    }
+
+    let duration = (video_duration_seconds > 0.0).then(|| video_duration_seconds.round() as u32);
+    let mut repairs = 0u32;
+    let mut spans = Vec::with_capacity(entries.len());
+
+    for (i, entry) in entries.into_iter().enumerate() {
+        if !(1..=5).contains(&entry.productive_score) {
+            return Err(format!(
+                "Entry {} has productiveScore {}, which is outside 1-5",
+                i + 1,
+                entry.productive_score
+            ));
+        }
+
+        let mut start = parse_seconds(&entry.start_time).map_err(|e| format!("Entry {}: {}", i + 1, e))?;
+        let mut end = parse_seconds(&entry.end_time).map_err(|e| format!("Entry {}: {}", i + 1, e))?;
+
+        if end < start {
+            std::mem::swap(&mut start, &mut end);
+            repairs += 1;
+        }
+        if let Some(duration) = duration
+            && end > duration
+        {
+            start = start.min(duration);
+            end = duration;
+            repairs += 1;
+        }
+        if end == start {
+            repairs += 1;
+            continue;
+        }
+
+        spans.push(Span { start, end, entry });
+    }
+
+    if spans.is_empty() {
+        return Err("No timeline entry lies within the video".to_string());
+    }
+
+    spans.sort_by_key(|span| span.start);
+
+    // Trim overlaps and merge adjacent duplicates
+    let mut merged: Vec<Span> = Vec::with_capacity(spans.len());
+    for mut span in spans {
+        if let Some(previous) = merged.last_mut() {
+            if span.start < previous.end {
+                span.start = previous.end;
+                repairs += 1;
+                if span.end <= span.start {
+                    continue;
+                }
+            }
+            if span.start - previous.end <= GAP_TOLERANCE_SECONDS && same_activity(&previous.entry, &span.entry) {
+                previous.end = span.end;
+                continue;
+            }
+        }
+        merged.push(span);
+    }
+
+    let covered: u32 = merged.iter().map(|span| span.end - span.start).sum();
+    let total = duration.unwrap_or_else(|| merged.last().map_or(0, |span| span.end));
+
+    let timeline = fill_gaps(merged, total);
+
+    let coverage = if total > 0 { (covered as f64 / total as f64).min(1.0) } else { 1.0 };
+    let score = (coverage * (1.0 - REPAIR_PENALTY * repairs as f64)).clamp(0.0, 1.0);
+
+    Ok(ValidatedTimeline {
+        timeline,
+        quality: TimelineQuality {
+            coverage: round_3(coverage),
+            repairs,
+            corrected: false,
+            score: round_3(score),
+        },
+    })
+}
+
+/**
+ * Stitch window timelines (in video order) into one timeline of the video
+ *
+ * Each overlap is cut at its midpoint: the earlier window's entries are
+ * kept up to the cut, the later window's from it. Entries meeting at a cut
+ * that describe the same activity are merged into one.
+ */
+pub fn stitch_windows(windows: Vec<WindowTimeline>) -> Result<ValidatedTimeline, String> {
+    let cuts: Vec<u32> = windows
+        .windows(2)
+        .map(|pair| (pair[1].start_seconds + pair[0].end_seconds.max(pair[1].start_seconds)) / 2)
+        .collect();
+    let total = windows.last().map_or(0, |window| window.end_seconds);
+
+    let mut spans: Vec<Span> = Vec::new();
+    let (mut coverage_sum, mut score_sum, mut kept_total) = (0.0, 0.0, 0u32);
+    let mut repairs = 0u32;
+    let mut corrected = false;
+
+    for (i, window) in windows.into_iter().enumerate() {
+        let keep_start = if i == 0 { 0 } else { cuts[i - 1] };
+        let keep_end = cuts.get(i).copied().unwrap_or(window.end_seconds);
+
+        let kept = keep_end.saturating_sub(keep_start);
+        coverage_sum += window.timeline.quality.coverage * kept as f64;
+        score_sum += window.timeline.quality.score * kept as f64;
+        kept_total += kept;
+        repairs += window.timeline.quality.repairs;
+        corrected |= window.timeline.quality.corrected;
+
+        for entry in window.timeline.timeline {
+            let start = (parse_seconds(&entry.start_time)? + window.start_seconds).max(keep_start);
+            let end = (parse_seconds(&entry.end_time)? + window.start_seconds).min(keep_end);
+            if end <= start {
+                continue;
+            }
+
+            match spans.last_mut() {
+                Some(previous)
+                    if start <= previous.end + GAP_TOLERANCE_SECONDS && same_activity(&previous.entry, &entry) =>
+                {
+                    previous.end = previous.end.max(end);
+                }
+                _ => spans.push(Span { start, end, entry }),
+            }
+        }
+    }
+
+    if spans.is_empty() {
+        return Err("No window produced a timeline".to_string());
+    }
+
+    let weighted = |sum: f64| if kept_total > 0 { round_3(sum / kept_total as f64) } else { 0.0 };
+    Ok(ValidatedTimeline {
+        timeline: fill_gaps(spans, total),
+        quality: TimelineQuality {
+            coverage: weighted(coverage_sum),
+            repairs,
+            corrected,
+            score: weighted(score_sum),
+        },
+    })
+}
+
+/**
+ * Convert a validated timeline of a sped-up video back to real time
+ *
+ * Times are multiplied by `factor` (granularity becomes `factor` seconds);
+ * the last entry ends at `real_duration_seconds` since the sped-up video
+ * was only analyzed in whole seconds (0 = unknown, times kept as scaled)
+ */
+pub fn rescale_timeline(
+    validated: ValidatedTimeline,
+    factor: f64,
+    real_duration_seconds: u32,
+) -> Result<ValidatedTimeline, String> {
+    let scale = |time: &str| parse_timestamp(time).map(|seconds| (seconds * factor).round() as u32);
+    let clamp = |seconds: u32| if real_duration_seconds > 0 { seconds.min(real_duration_seconds) } else { seconds };
+
+    let mut spans: Vec<Span> = Vec::with_capacity(validated.timeline.len());
+    for entry in validated.timeline {
+        let start = clamp(scale(&entry.start_time)?);
+        let end = clamp(scale(&entry.end_time)?);
+        if end > start {
+            spans.push(Span { start, end, entry });
+        }
+    }
+    if let Some(last) = spans.last_mut()
+        && real_duration_seconds > last.start
+    {
+        last.end = real_duration_seconds;
+    }
+
+    Ok(ValidatedTimeline {
+        timeline: spans
+            .into_iter()
+            .map(|span| TimelineEntry {
+                start_time: format_duration(span.start as f64),
+                end_time: format_duration(span.end as f64),
+                ..span.entry
+            })
+            .collect(),
+        quality: validated.quality,
+    })
+}
+
+// Make the timeline contiguous from 0 to `total` seconds
+fn fill_gaps(spans: Vec<Span>, total: u32) -> Vec<TimelineEntry> {
+    let mut filled: Vec<Span> = Vec::with_capacity(spans.len() + 2);
+
+    let close_gap = |filled: &mut Vec<Span>, until: u32| {
+        let cursor = filled.last().map_or(0, |span| span.end);
+        if until <= cursor {
+            return;
+        }
+        match filled.last_mut() {
+            Some(previous) if until - cursor <= GAP_TOLERANCE_SECONDS => previous.end = until,
+            _ => filled.push(Span {
+                start: cursor,
+                end: until,
+                entry: unknown_entry(),
+            }),
+        }
+    };
+
+    for mut span in spans {
+        if filled.is_empty() && span.start <= GAP_TOLERANCE_SECONDS {
+            span.start = 0;
+        }
+        close_gap(&mut filled, span.start);
+        filled.push(span);
+    }
+    close_gap(&mut filled, total);
+
+    filled
+        .into_iter()
+        .map(|span| TimelineEntry {
+            start_time: format_duration(span.start as f64),
+            end_time: format_duration(span.end as f64),
+            ..span.entry
+        })
+        .collect()
+}
+
+fn unknown_entry() -> TimelineEntry {
+    TimelineEntry {
+        start_time: String::new(),
+        end_time: String::new(),
+        description: "No activity identified by the analysis".to_string(),
+        active_application: UNKNOWN_APPLICATION.to_string(),
+        active_window_title: String::new(),
+        productive_score: UNKNOWN_SCORE,
+        fusion: None,
+    }
+}
+
+fn same_activity(a: &TimelineEntry, b: &TimelineEntry) -> bool {
+    a.productive_score == b.productive_score
+        && a.active_application.trim().eq_ignore_ascii_case(b.active_application.trim())
+        && a.active_window_title.trim().eq_ignore_ascii_case(b.active_window_title.trim())
+        && a.description.trim().eq_ignore_ascii_case(b.description.trim())
+}
+
+// Whole seconds of a timestamp (fractions rounded)
+fn parse_seconds(time: &str) -> Result<u32, String> {
+    parse_timestamp(time).map(|seconds| seconds.round() as u32)
+}
+
+fn round_3(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recording::gemini::fixtures::entry;
+
+    fn times(timeline: &[TimelineEntry]) -> Vec<(String, String, String)> {
+        timeline
+            .iter()
+            .map(|e| (e.start_time.clone(), e.end_time.clone(), e.active_application.clone()))
+            .collect()
+    }
+
+    fn row(start: &str, end: &str, app: &str) -> (String, String, String) {
+        (start.to_string(), end.to_string(), app.to_string())
+    }
+
+    #[test]
+    fn test_clean_timeline_passes_unchanged() {
+        let validated = validate_timeline(
+            vec![entry("00:00", "02:00", "VS Code", 5), entry("02:00", "05:00", "Slack", 4)],
+            300.0,
+        )
+        .unwrap();
+
+        assert_eq!(times(&validated.timeline), vec![row("00:00", "02:00", "VS Code"), row("02:00", "05:00", "Slack")]);
+        assert_eq!(validated.quality.coverage, 1.0);
+        assert_eq!(validated.quality.repairs, 0);
+        assert_eq!(validated.quality.score, 1.0);
+    }
+
+    #[test]
+    fn test_repairs_overlaps_duplicates_gaps_and_clamping() {
+        let validated = validate_timeline(
+            vec![
+                entry("00:01", "01:00", "VS Code", 5),
+                entry("00:50", "02:00", "Slack", 4),  // overlaps VS Code
+                entry("02:01", "03:00", "Slack", 4),  // duplicate of the previous
+                entry("04:00", "06:30", "Chrome", 2), // past the end of a 5:00 video
+            ],
+            300.0,
+        )
+        .unwrap();
+
+        assert_eq!(
+            times(&validated.timeline),
+            vec![
+                row("00:00", "01:00", "VS Code"),
+                row("01:00", "03:00", "Slack"),
+                row("03:00", "04:00", UNKNOWN_APPLICATION),
+                row("04:00", "05:00", "Chrome"),
+            ]
+        );
+        // 59 + 120 + 60 of 300 seconds covered by the model, two repairs
+        assert_eq!(validated.quality.coverage, 0.797);
+        assert_eq!(validated.quality.repairs, 2);
+        assert_eq!(validated.quality.score, 0.717);
+    }
+
+    #[test]
+    fn test_rejects_unusable_timelines() {
+        assert!(validate_timeline(vec![], 60.0).is_err());
+        assert!(validate_timeline(vec![entry("00:00", "01:00", "VS Code", 0)], 60.0)
+            .unwrap_err()
+            .contains("outside 1-5"));
+        assert!(validate_timeline(vec![entry("00:00", "01:00", "VS Code", 6)], 60.0).is_err());
+        assert!(validate_timeline(vec![entry("0:xx", "01:00", "VS Code", 3)], 60.0).is_err());
+        assert!(validate_timeline(vec![entry("02:00", "03:00", "VS Code", 3)], 60.0)
+            .unwrap_err()
+            .contains("within the video"));
+    }
+
+    #[test]
+    fn test_long_video_timestamps() {
+        let validated = validate_timeline(
+            vec![entry("00:00", "59:30", "VS Code", 5), entry("59:30", "1:45:00.4", "Zoom", 4)],
+            6300.0,
+        )
+        .unwrap();
+
+        assert_eq!(
+            times(&validated.timeline),
+            vec![row("00:00", "59:30", "VS Code"), row("59:30", "01:45:00", "Zoom")]
+        );
+        assert_eq!(validated.quality.repairs, 0);
+    }
+
+    #[test]
+    fn test_stitch_windows() {
+        let window = |start: u32, end: u32, entries: Vec<TimelineEntry>, score: f64| WindowTimeline {
+            start_seconds: start,
+            end_seconds: end,
+            timeline: ValidatedTimeline {
+                timeline: entries,
+                quality: TimelineQuality {
+                    coverage: score,
+                    repairs: 0,
+                    corrected: false,
+                    score,
+                },
+            },
+        };
+
+        // Windows 0-20:00 and 19:00-40:00 (cut at 19:30); Slack spans the overlap
+        let validated = stitch_windows(vec![
+            window(
+                0,
+                1200,
+                vec![entry("00:00", "18:00", "VS Code", 5), entry("18:00", "20:00", "Slack", 4)],
+                1.0,
+            ),
+            window(
+                1140,
+                2400,
+                vec![entry("00:00", "02:00", "Slack", 4), entry("02:00", "21:00", "Chrome", 2)],
+                0.5,
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            times(&validated.timeline),
+            vec![row("00:00", "18:00", "VS Code"), row("18:00", "21:00", "Slack"), row("21:00", "40:00", "Chrome")]
+        );
+        // 1170s kept at 1.0, 1230s at 0.5
+        assert_eq!(validated.quality.score, 0.744);
+    }
+
+    #[test]
+    fn test_rescale_timeline() {
+        // 4x speed: 601s of real time became a 150s video
+        let validated = validate_timeline(
+            vec![entry("00:00", "01:00", "VS Code", 5), entry("01:00", "02:30", "Slack", 4)],
+            150.0,
+        )
+        .unwrap();
+        let rescaled = rescale_timeline(validated, 4.0, 601).unwrap();
+
+        assert_eq!(
+            times(&rescaled.timeline),
+            vec![row("00:00", "04:00", "VS Code"), row("04:00", "10:01", "Slack")]
+        );
+        assert_eq!(rescaled.quality.score, 1.0);
+    }
+
+    #[test]
+    fn test_unknown_duration_keeps_model_end() {
+        let validated = validate_timeline(vec![entry("01:00", "00:10", "Terminal", 5)], 0.0).unwrap();
+
+        // Reversed times swapped; the 10s lead-in is too long to stretch over
+        assert_eq!(
+            times(&validated.timeline),
+            vec![row("00:00", "00:10", UNKNOWN_APPLICATION), row("00:10", "01:00", "Terminal")]
+        );
+        assert_eq!(validated.quality.repairs, 1);
+    }
+}
@@ -0,0 +1,315 @@
+/**
+ * ============================================================================
+ * RECORDING IMPORT MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Import external screen recordings (OBS, meeting tools) as segments
+ *
+ * FLOW:
+ * 1. Probe the file with bundled FFmpeg (duration, resolution, codec, fps)
+ * 2. Remux (H.264 input) or transcode to our encoding profile into the
+ *    date directory of the given start time
+ * 3. Write a generated RecordingMetadata sidecar
+ * 4. Caller enqueues Gemini analysis like a native segment
+ *
+ * ============================================================================
+ */
+
+use crate::recording::capture;
+use crate::recording::storage;
+use crate::recording::types::{DisplayRecording, RecordingConfig, RecordingMetadata};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Container formats accepted for import
+const SUPPORTED_EXTENSIONS: &[&str] = &["mp4", "mkv"];
+
+// =============================================================================
+// Probe
+// =============================================================================
+
+/// Stream information extracted from `ffmpeg -i`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeInfo {
+    pub duration_seconds: f64,
+    pub width: u32,
+    pub height: u32,
+    pub codec: String,
+    pub fps: f64,
+}
+
+/**
+ * Probe a video file with the bundled FFmpeg
+ * FFmpeg prints stream info to stderr and exits non-zero without an output file
+ */
+pub fn probe_video(path: &Path) -> Result<ProbeInfo, String> {
+    let ffmpeg_path = capture::get_ffmpeg_path()?;
+
+    let output = Command::new(&ffmpeg_path)
+        .arg("-hide_banner")
+        .arg("-i")
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()
+        .map_err(|e| format!("Failed to run FFmpeg at {:?}: {}", ffmpeg_path, e))?;
+
+    parse_probe_output(&String::from_utf8_lossy(&output.stderr))
+}
+
+/**
+ * Parse `ffmpeg -i` stderr output
+ *
+ * Relevant lines look like:
+ *   Duration: 00:12:34.56, start: 0.000000, bitrate: 1234 kb/s
+ *   Stream #0:0: Video: h264 (High), yuv420p(progressive), 1920x1080, 30 fps, ...
+ */
+pub fn parse_probe_output(output: &str) -> Result<ProbeInfo, String> {
+    let duration_re = Regex::new(r"Duration: (\d+):(\d{2}):(\d{2}(?:\.\d+)?)").unwrap();
+    let video_re = Regex::new(r"Stream #\S+.*Video: (\w+)").unwrap();
+    let size_re = Regex::new(r"\b(\d{2,5})x(\d{2,5})\b").unwrap();
+    let fps_re = Regex::new(r"(\d+(?:\.\d+)?) (?:fps|tbr)").unwrap();
+
+    let duration = duration_re
+        .captures(output)
+        .ok_or_else(|| "Could not determine video duration".to_string())?;
+    let hours: f64 = duration[1].parse().unwrap_or(0.0);
+    let minutes: f64 = duration[2].parse().unwrap_or(0.0);
+    let seconds: f64 = duration[3].parse().unwrap_or(0.0);
+    let duration_seconds = hours * 3600.0 + minutes * 60.0 + seconds;
+
+    let video_line = output
+        .lines()
+        .find(|line| video_re.is_match(line))
+        .ok_or_else(|| "No video stream found".to_string())?;
+
+    let codec = video_re
+        .captures(video_line)
+        .map(|c| c[1].to_string())
+        .unwrap_or_default();
+
+    let (width, height) = size_re
+        .captures(video_line)
+        .map(|c| (c[1].parse().unwrap_or(0), c[2].parse().unwrap_or(0)))
+        .ok_or_else(|| "Could not determine video resolution".to_string())?;
+
+    let fps = fps_re
+        .captures(video_line)
+        .and_then(|c| c[1].parse().ok())
+        .unwrap_or(0.0);
+
+    if duration_seconds <= 0.0 {
+        return Err("Video has zero duration".to_string());
+    }
+
+    Ok(ProbeInfo {
+        duration_seconds,
+        width,
+        height,
+        codec,
+        fps,
+    })
+}
+
+// =============================================================================
+// Import
+// =============================================================================
+
+/**
+ * Import an external video file as a single-display recording segment
+ *
+ * # Arguments
+ * * `source_path` - MP4 or MKV file to import
+ * * `start_time` - Wall-clock time the recording started
+ * * `display_index` - Display index to file the video under
+ * * `transcode` - Re-encode to the configured profile (forced for non-H.264 input)
+ * * `config` - Recording config (encoding profile)
+ *
+ * # Returns
+ * * `Ok(RecordingMetadata)` - Metadata of the new segment (sidecar already saved)
+ */
+pub fn import_video(
+    app: &tauri::AppHandle,
+    source_path: &Path,
+    start_time: DateTime<Utc>,
+    display_index: u32,
+    transcode: bool,
+    config: &RecordingConfig,
+) -> Result<RecordingMetadata, String> {
+    let extension = source_path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+
+    if !SUPPORTED_EXTENSIONS.contains(&extension.as_str()) {
+        return Err(format!(
+            "Unsupported file type '{}'. Supported: {}",
+            extension,
+            SUPPORTED_EXTENSIONS.join(", ")
+        ));
+    }
+
+    if !source_path.exists() {
+        return Err(format!("File not found: {:?}", source_path));
+    }
+
+    let probe = probe_video(source_path)?;
+    log::info!(
+        "[IMPORT] Probed {:?}: {:.1}s, {}x{}, {} @ {:.1} fps",
+        source_path, probe.duration_seconds, probe.width, probe.height, probe.codec, probe.fps
+    );
+
+    // Non-H.264 input can't be stored as-is in our MP4 profile
+    let transcode = transcode || probe.codec != "h264";
+
+    let date = start_time.date_naive();
+    storage::ensure_recording_dir(app, &date)?;
+
+    let segment_id = storage::generate_segment_id_at(start_time);
+    let output_path = storage::get_video_path(app, &date, &segment_id, display_index);
+
+    if transcode {
+        transcode_video(source_path, &output_path, config)?;
+    } else {
+        remux_video(source_path, &output_path)?;
+    }
+
+    // Re-probe the stored file so metadata describes what is actually on disk
+    let stored = probe_video(&output_path)?;
+    let file_size = storage::get_file_size(&output_path)?;
+    let framerate = stored.fps.round().clamp(1.0, 255.0) as u8;
+    let end_time = start_time + chrono::Duration::milliseconds((stored.duration_seconds * 1000.0) as i64);
+
+    let metadata = RecordingMetadata {
+        id: segment_id.clone(),
+        format: "mp4".to_string(),
+        codec: "h264".to_string(),
+        framerate,
+        start_time: start_time.to_rfc3339(),
+        end_time: end_time.to_rfc3339(),
+        duration_seconds: stored.duration_seconds,
+        total_file_size_bytes: file_size,
+        display_count: 1,
+        displays: vec![DisplayRecording {
+            display_index,
+            width: stored.width,
+            height: stored.height,
+            frame_count: (stored.duration_seconds * stored.fps).round() as u64,
+            file_size_bytes: file_size,
+            filename: format!("{}_d{}.mp4", segment_id, display_index),
+            classification: None,
+        }],
+        imported_from: Some(source_path.to_string_lossy().to_string()),
+        high_risk: false,
+    };
+
+    storage::save_metadata(app, &date, &metadata)?;
+
+    log::info!(
+        "[IMPORT] Imported {:?} as segment {} ({:.1}s, {} bytes, transcoded: {})",
+        source_path, segment_id, stored.duration_seconds, file_size, transcode
+    );
+
+    Ok(metadata)
+}
+
+// Copy the H.264 stream into an MP4 container without re-encoding
+fn remux_video(source_path: &Path, output_path: &Path) -> Result<(), String> {
+    run_ffmpeg(source_path, output_path, &["-c:v", "copy"])
+}
+
+// Re-encode to the same profile the capture pipeline produces
+fn transcode_video(source_path: &Path, output_path: &Path, config: &RecordingConfig) -> Result<(), String> {
+    let output_height = (config.output_width * 9) / 16;
+    let output_height = if output_height % 2 == 1 { output_height + 1 } else { output_height };
+
+    let scale_filter = format!(
+        "scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:black",
+        config.output_width, output_height, config.output_width, output_height
+    );
+
+    run_ffmpeg(
+        source_path,
+        output_path,
+        &[
+            "-vf", &scale_filter,
+            "-r", &config.framerate.to_string(),
+            "-c:v", "libx264",
+            "-preset", &config.preset,
+            "-crf", &config.crf.to_string(),
+            "-tune", "stillimage",
+            "-pix_fmt", "yuv420p",
+        ],
+    )
+}
+
+// Write the first video stream of `source_path` to `output_path` (audio dropped)
+fn run_ffmpeg(source_path: &Path, output_path: &Path, codec_args: &[&str]) -> Result<(), String> {
+    let ffmpeg_path = capture::get_ffmpeg_path()?;
+
+    let status = Command::new(&ffmpeg_path)
+        .args(["-y", "-i"])
+        .arg(source_path)
+        .args(["-map", "0:v:0"])
+        .args(codec_args)
+        .args(["-an", "-movflags", "+faststart"])
+        .arg(output_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| format!("Failed to run FFmpeg at {:?}: {}", ffmpeg_path, e))?;
+
+    if !status.success() {
+        let _ = std::fs::remove_file(output_path);
+        return Err(format!("FFmpeg exited with error: {:?}", status.code()));
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OBS_OUTPUT: &str = r#"Input #0, matroska,webm, from 'meeting.mkv':
+  Metadata:
+    ENCODER         : Lavf60.3.100
+  Duration: 01:02:03.50, start: 0.000000, bitrate: 2100 kb/s
+  Stream #0:0: Video: h264 (High), yuv420p(tv, bt709, progressive), 1920x1080 [SAR 1:1 DAR 16:9], 30 fps, 30 tbr, 1k tbn (default)
+  Stream #0:1: Audio: aac (LC), 48000 Hz, stereo, fltp (default)
+At least one output file must be specified"#;
+
+    #[test]
+    fn test_parse_probe_output() {
+        let info = parse_probe_output(OBS_OUTPUT).unwrap();
+        assert_eq!(info.duration_seconds, 3723.5);
+        assert_eq!(info.width, 1920);
+        assert_eq!(info.height, 1080);
+        assert_eq!(info.codec, "h264");
+        assert_eq!(info.fps, 30.0);
+    }
+
+    #[test]
+    fn test_parse_probe_output_hevc() {
+        let output = "  Duration: 00:00:10.00, start: 0.000000, bitrate: 500 kb/s\n  Stream #0:0[0x1](und): Video: hevc (Main) (hvc1 / 0x31637668), yuv420p(tv), 2560x1440, 480 kb/s, 59.94 fps, 59.94 tbr, 90k tbn";
+        let info = parse_probe_output(output).unwrap();
+        assert_eq!(info.codec, "hevc");
+        assert_eq!(info.width, 2560);
+        assert_eq!(info.fps, 59.94);
+    }
+
+    #[test]
+    fn test_parse_probe_output_no_video() {
+        let output = "  Duration: 00:00:10.00, start: 0.000000\n  Stream #0:0: Audio: aac (LC), 48000 Hz";
+        assert!(parse_probe_output(output).is_err());
+    }
+}
@@ -0,0 +1,380 @@
+/**
+ * ============================================================================
+ * INPUT ACTIVITY MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Show whether the user was actively working or just watching
+ *
+ * SUMMARIES:
+ * After a segment is finalized, aw-watcher-input events covering it are
+ * bucketed into fixed intervals (keypresses, clicks, mouse distance, scroll).
+ * Events spanning several intervals are split proportionally to overlap.
+ * The summary is stored as <segment_id>.input next to the sidecar and the
+ * segment's hash chain entry is amended.
+ *
+ * OVERLAY:
+ * aw-watcher-input only reports counts, not pointer positions, so the overlay
+ * is an activity bar along the bottom edge whose width follows the interval's
+ * intensity. Rendered on demand into app_data_dir/exports.
+ *
+ * ============================================================================
+ */
+
+use crate::activitywatch::types::EventInfo;
+use crate::recording::{capture, integrity, storage};
+use crate::recording::types::{InputActivitySummary, InputInterval, RecordingConfig, RecordingMetadata};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use tauri::{AppHandle, Manager};
+
+/// Mouse movement (pixels) counted as one unit of activity
+const MOUSE_PIXELS_PER_UNIT: f64 = 100.0;
+
+/// Height of the overlay bar (pixels)
+const OVERLAY_BAR_HEIGHT: u32 = 12;
+
+// =============================================================================
+// Summaries
+// =============================================================================
+
+/**
+ * Summarize input activity for a finalized segment and store it
+ * Runs in the background; errors are logged, never propagated to recording
+ */
+pub async fn summarize_segment(app: AppHandle, date: NaiveDate, metadata: RecordingMetadata, config: RecordingConfig) {
+    let Some(base_url) = crate::activitywatch::manager::current_base_url() else {
+        return;
+    };
+
+    let (start, end) = match (
+        DateTime::parse_from_rfc3339(&metadata.start_time),
+        DateTime::parse_from_rfc3339(&metadata.end_time),
+    ) {
+        (Ok(s), Ok(e)) => (s.with_timezone(&Utc), e.with_timezone(&Utc)),
+        _ => {
+            log::error!("[INPUT] Invalid time range for segment {}", metadata.id);
+            return;
+        }
+    };
+
+    let events = match crate::activitywatch::client::fetch_events_by_range(&base_url, start, end).await {
+        Ok(events) => events.input_events,
+        Err(e) => {
+            log::warn!("[INPUT] Could not fetch input events: {}", e);
+            return;
+        }
+    };
+
+    // No input watcher running - nothing to record
+    if events.is_empty() {
+        log::debug!("[INPUT] No input events for segment {}", metadata.id);
+        return;
+    }
+
+    let summary = summarize_input(&metadata.id, &events, start, end, config.input_activity.interval_seconds);
+
+    if let Err(e) = storage::save_input_activity(&app, &date, &summary) {
+        log::error!("[INPUT] Failed to save summary for {}: {}", metadata.id, e);
+        return;
+    }
+
+    if let Err(e) = integrity::amend_segment(&app, &date, &metadata) {
+        log::error!("[INPUT] Failed to amend hash chain for {}: {}", metadata.id, e);
+    }
+
+    log::info!(
+        "[INPUT] Segment {}: {} presses, {} clicks, {}/{} active intervals",
+        metadata.id,
+        summary.total_presses.round(),
+        summary.total_clicks.round(),
+        summary.active_intervals,
+        summary.intervals.len()
+    );
+}
+
+/**
+ * Bucket aw-watcher-input events into fixed intervals over a segment
+ *
+ * Event data fields: presses, clicks, deltaX, deltaY, scrollX, scrollY
+ */
+pub fn summarize_input(
+    segment_id: &str,
+    events: &[EventInfo],
+    segment_start: DateTime<Utc>,
+    segment_end: DateTime<Utc>,
+    interval_seconds: u32,
+) -> InputActivitySummary {
+    let interval = interval_seconds.max(1) as f64;
+    let segment_seconds = (segment_end - segment_start).num_milliseconds().max(0) as f64 / 1000.0;
+    let interval_count = (segment_seconds / interval).ceil() as usize;
+
+    let mut intervals: Vec<InputInterval> = (0..interval_count)
+        .map(|i| InputInterval {
+            start_offset_seconds: i as f64 * interval,
+            presses: 0.0,
+            clicks: 0.0,
+            mouse_distance: 0.0,
+            scroll_distance: 0.0,
+        })
+        .collect();
+
+    for event in events {
+        let Ok(timestamp) = DateTime::parse_from_rfc3339(&event.timestamp) else {
+            continue;
+        };
+        let event_start = (timestamp.with_timezone(&Utc) - segment_start).num_milliseconds() as f64 / 1000.0;
+        let event_end = event_start + event.duration.max(0.0);
+
+        let field = |name: &str| event.data.get(name).and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let presses = field("presses");
+        let clicks = field("clicks");
+        let mouse_distance = field("deltaX").hypot(field("deltaY"));
+        let scroll_distance = field("scrollX").abs() + field("scrollY").abs();
+
+        for bucket in intervals.iter_mut() {
+            let bucket_end = (bucket.start_offset_seconds + interval).min(segment_seconds);
+            let share = if event_end > event_start {
+                let overlap = event_end.min(bucket_end) - event_start.max(bucket.start_offset_seconds);
+                overlap.max(0.0) / (event_end - event_start)
+            } else if event_start >= bucket.start_offset_seconds && event_start < bucket_end {
+                // Zero-duration event: attribute entirely to its bucket
+                1.0
+            } else {
+                0.0
+            };
+
+            if share > 0.0 {
+                bucket.presses += presses * share;
+                bucket.clicks += clicks * share;
+                bucket.mouse_distance += mouse_distance * share;
+                bucket.scroll_distance += scroll_distance * share;
+            }
+        }
+    }
+
+    let active_intervals = intervals.iter().filter(|i| activity_score(i) > 0.0).count() as u32;
+
+    InputActivitySummary {
+        segment_id: segment_id.to_string(),
+        interval_seconds: interval_seconds.max(1),
+        total_presses: intervals.iter().map(|i| i.presses).sum(),
+        total_clicks: intervals.iter().map(|i| i.clicks).sum(),
+        total_mouse_distance: intervals.iter().map(|i| i.mouse_distance).sum(),
+        total_scroll_distance: intervals.iter().map(|i| i.scroll_distance).sum(),
+        active_intervals,
+        intervals,
+    }
+}
+
+// Combined activity of an interval (keys + clicks + scaled mouse movement)
+fn activity_score(interval: &InputInterval) -> f64 {
+    interval.presses + interval.clicks + interval.mouse_distance / MOUSE_PIXELS_PER_UNIT
+}
+
+// =============================================================================
+// Overlay Export
+// =============================================================================
+
+// Get the directory for rendered exports
+pub fn get_exports_dir(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .expect("app_data_dir available")
+        .join("exports")
+}
+
+/**
+ * Render a copy of a display video with the input activity bar burned in
+ *
+ * # Returns
+ * * `Ok(PathBuf)` - Path of the rendered MP4 in the exports directory
+ */
+pub fn export_overlay(
+    app: &AppHandle,
+    date: &NaiveDate,
+    segment_id: &str,
+    display_index: u32,
+    config: &RecordingConfig,
+) -> Result<PathBuf, String> {
+    let metadata = storage::get_segments_for_date(app, date)
+        .into_iter()
+        .find(|m| m.id == segment_id)
+        .ok_or_else(|| format!("Segment not found: {}", segment_id))?;
+
+    let display = metadata
+        .displays
+        .iter()
+        .find(|d| d.display_index == display_index)
+        .ok_or_else(|| format!("Segment {} has no display {}", segment_id, display_index))?;
+
+    let summary = storage::load_input_activity(app, date, segment_id)
+        .ok_or_else(|| format!("No input activity recorded for segment {}", segment_id))?;
+
+    // Intervals are wall-clock; the video may be shorter if frames were dropped
+    let video_seconds = display.frame_count as f64 / metadata.framerate.max(1) as f64;
+    let time_scale = if metadata.duration_seconds > 0.0 && video_seconds > 0.0 {
+        video_seconds / metadata.duration_seconds
+    } else {
+        1.0
+    };
+
+    let source_path = storage::find_video_path(app, date, segment_id, display_index);
+    let exports_dir = get_exports_dir(app);
+    std::fs::create_dir_all(&exports_dir)
+        .map_err(|e| format!("Failed to create exports directory: {}", e))?;
+    let output_path = exports_dir.join(format!("{}_d{}_input.mp4", segment_id, display_index));
+
+    let filter = build_overlay_filter(&summary, time_scale);
+    let ffmpeg_path = capture::get_ffmpeg_path()?;
+
+    let status = Command::new(&ffmpeg_path)
+        .args(["-y", "-i"])
+        .arg(&source_path)
+        .args([
+            "-vf", &filter,
+            "-an",
+            "-c:v", "libx264",
+            "-preset", &config.preset,
+            "-crf", &config.crf.to_string(),
+            "-pix_fmt", "yuv420p",
+            "-movflags", "+faststart",
+        ])
+        .arg(&output_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| format!("Failed to run FFmpeg at {:?}: {}", ffmpeg_path, e))?;
+
+    if !status.success() {
+        let _ = std::fs::remove_file(&output_path);
+        return Err(format!("FFmpeg exited with error: {:?}", status.code()));
+    }
+
+    log::info!("[INPUT] Exported overlay for {} display {} to {:?}", segment_id, display_index, output_path);
+
+    Ok(output_path)
+}
+
+/**
+ * Build the FFmpeg filter drawing one activity bar per active interval
+ * Bar width is relative to the busiest interval of the segment
+ */
+fn build_overlay_filter(summary: &InputActivitySummary, time_scale: f64) -> String {
+    let peak = summary
+        .intervals
+        .iter()
+        .map(activity_score)
+        .fold(0.0_f64, f64::max);
+
+    let interval = summary.interval_seconds as f64;
+    let bars: Vec<String> = summary
+        .intervals
+        .iter()
+        .filter(|i| activity_score(i) > 0.0)
+        .map(|i| {
+            let fraction = activity_score(i) / peak;
+            let start = i.start_offset_seconds * time_scale;
+            let end = (i.start_offset_seconds + interval) * time_scale;
+            format!(
+                "drawbox=x=0:y=ih-{h}:w=iw*{fraction:.3}:h={h}:color=orange@0.8:t=fill:enable='between(t,{start:.2},{end:.2})'",
+                h = OVERLAY_BAR_HEIGHT,
+            )
+        })
+        .collect();
+
+    if bars.is_empty() {
+        // ffmpeg needs a filter; keep the video unchanged
+        "null".to_string()
+    } else {
+        bars.join(",")
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn event(timestamp: &str, duration: f64, data: serde_json::Value) -> EventInfo {
+        EventInfo {
+            id: None,
+            timestamp: timestamp.to_string(),
+            duration,
+            data,
+        }
+    }
+
+    fn time(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_summarize_splits_events_across_intervals() {
+        let events = vec![
+            // 5s event straddling the 10s boundary: 4 presses split 2/2
+            event("2025-01-15T10:00:08Z", 4.0, json!({"presses": 4, "clicks": 0, "deltaX": 0, "deltaY": 0})),
+            event("2025-01-15T10:00:21Z", 1.0, json!({"presses": 0, "clicks": 2, "deltaX": 300, "deltaY": 400, "scrollY": -5})),
+        ];
+
+        let summary = summarize_input(
+            "seg",
+            &events,
+            time("2025-01-15T10:00:00Z"),
+            time("2025-01-15T10:00:30Z"),
+            10,
+        );
+
+        assert_eq!(summary.intervals.len(), 3);
+        assert_eq!(summary.intervals[0].presses, 2.0);
+        assert_eq!(summary.intervals[1].presses, 2.0);
+        assert_eq!(summary.intervals[2].clicks, 2.0);
+        assert_eq!(summary.intervals[2].mouse_distance, 500.0);
+        assert_eq!(summary.intervals[2].scroll_distance, 5.0);
+        assert_eq!(summary.total_presses, 4.0);
+        assert_eq!(summary.active_intervals, 3);
+    }
+
+    #[test]
+    fn test_summarize_ignores_events_outside_segment() {
+        let events = vec![event("2025-01-15T09:59:00Z", 10.0, json!({"presses": 10}))];
+
+        let summary = summarize_input(
+            "seg",
+            &events,
+            time("2025-01-15T10:00:00Z"),
+            time("2025-01-15T10:01:00Z"),
+            10,
+        );
+
+        assert_eq!(summary.intervals.len(), 6);
+        assert_eq!(summary.total_presses, 0.0);
+        assert_eq!(summary.active_intervals, 0);
+    }
+
+    #[test]
+    fn test_overlay_filter_scales_to_peak_and_video_time() {
+        let events = vec![
+            event("2025-01-15T10:00:00Z", 10.0, json!({"presses": 10})),
+            event("2025-01-15T10:00:20Z", 10.0, json!({"presses": 5})),
+        ];
+        let summary = summarize_input(
+            "seg",
+            &events,
+            time("2025-01-15T10:00:00Z"),
+            time("2025-01-15T10:00:30Z"),
+            10,
+        );
+
+        let filter = build_overlay_filter(&summary, 0.5);
+        let bars: Vec<&str> = filter.split(",drawbox").collect();
+        assert_eq!(bars.len(), 2);
+        assert!(bars[0].contains("w=iw*1.000") && bars[0].contains("between(t,0.00,5.00)"));
+        assert!(bars[1].contains("w=iw*0.500") && bars[1].contains("between(t,10.00,15.00)"));
+    }
+}
@@ -0,0 +1,759 @@
+/**
+ * ============================================================================
+ * RECORDING INTEGRITY MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Tamper-evident record of every finalized segment
+ *
+ * HASH CHAIN:
+ * Each entry lists SHA-256 digests of a segment's files (display videos,
+ * frame timestamps, input activity, metadata sidecar), the hash of the previous entry and a
+ * sequence number. The entry hash is signed with a per-device Ed25519 key.
+ *
+ * ENTRY KINDS:
+ * - sealed: Segment finalized (or imported)
+ * - amended: Sidecar legitimately rewritten (e.g. classification added)
+ * - deleted: Files removed by retention or quota cleanup
+ *
+ * VERIFICATION:
+ * Walks the chain checking hashes, signatures and links (detects removed or
+ * reordered entries), compares its end with the recorded chain head (detects
+ * entries removed from the end), then re-hashes every file still expected on
+ * disk (detects missing and modified files). A missing device key is
+ * reported, never replaced during verification.
+ *
+ * STORAGE:
+ * ~/.screenjournal/integrity/chain.jsonl      - One signed entry per line
+ * ~/.screenjournal/integrity/chain_head.json  - Sequence and hash of the last entry
+ * ~/.screenjournal/integrity/device_key.pk8   - Device signing key (0600)
+ *
+ * ============================================================================
+ */
+
+use crate::recording::storage;
+use crate::recording::types::{
+    IntegrityIssue, IntegrityIssueKind, IntegrityReport, RecordingMetadata,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use once_cell::sync::Lazy;
+use ring::digest::{Context, SHA256};
+use ring::rand::SystemRandom;
+use ring::signature::{Ed25519KeyPair, KeyPair, UnparsedPublicKey, ED25519};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::io::{BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// prev_hash of the first entry
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// File extensions that belong to a segment
+const SEGMENT_EXTENSIONS: &[&str] = &["mp4", "json", "frames", "input"];
+
+// Signing key and chain head, loaded on first append
+static CHAIN_WRITER: Lazy<Mutex<Option<ChainWriter>>> = Lazy::new(|| Mutex::new(None));
+
+struct ChainWriter {
+    key: Ed25519KeyPair,
+    public_key: String,
+    next_seq: u64,
+    last_hash: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum ChainEntryKind {
+    Sealed,
+    Amended,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct FileDigest {
+    name: String,
+    sha256: String,
+    size: u64,
+}
+
+// Signed part of an entry - field order is the canonical serialization order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EntryPayload {
+    seq: u64,
+    prev_hash: String,
+    kind: ChainEntryKind,
+    recorded_at: String,
+    date: String,
+    segment_id: String,
+    files: Vec<FileDigest>,
+    deleted: Vec<String>,
+    reason: Option<String>,
+    public_key: String,
+}
+
+// Last appended entry, kept outside the chain file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct ChainHead {
+    seq: u64,
+    entry_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ChainEntry {
+    #[serde(flatten)]
+    payload: EntryPayload,
+    entry_hash: String,
+    signature: String,
+}
+
+// =============================================================================
+// Sealing
+// =============================================================================
+
+/**
+ * Append a `sealed` entry for a newly finalized or imported segment
+ */
+pub fn seal_segment(app: &AppHandle, date: &NaiveDate, metadata: &RecordingMetadata) -> Result<(), String> {
+    append_files_entry(app, date, metadata, ChainEntryKind::Sealed)
+}
+
+/**
+ * Append an `amended` entry after a segment's sidecar was legitimately rewritten
+ */
+pub fn amend_segment(app: &AppHandle, date: &NaiveDate, metadata: &RecordingMetadata) -> Result<(), String> {
+    append_files_entry(app, date, metadata, ChainEntryKind::Amended)
+}
+
+fn append_files_entry(
+    app: &AppHandle,
+    date: &NaiveDate,
+    metadata: &RecordingMetadata,
+    kind: ChainEntryKind,
+) -> Result<(), String> {
+    let mut names: Vec<String> = metadata
+        .displays
+        .iter()
+        .flat_map(|d| {
+            [
+                d.filename.clone(),
+                format!("{}_d{}.frames", metadata.id, d.display_index),
+            ]
+        })
+        .collect();
+    names.push(format!("{}.input", metadata.id));
+    names.push(format!("{}.json", metadata.id));
+
+    let mut files = Vec::new();
+    for name in names {
+        let path = file_path(app, &date.format("%Y-%m-%d").to_string(), &name);
+        // Frame timestamps and input activity are optional (imports, no ActivityWatch)
+        if !path.exists() && (name.ends_with(".frames") || name.ends_with(".input")) {
+            continue;
+        }
+        let (sha256, size) = hash_file(&path)?;
+        files.push(FileDigest { name, sha256, size });
+    }
+
+    append_entry(app, kind, date, &metadata.id, files, Vec::new(), None)
+}
+
+/**
+ * Append `deleted` entries for recording files removed by cleanup
+ * One entry per segment; paths that aren't segment files are ignored
+ */
+pub fn record_deletions(app: &AppHandle, paths: &[PathBuf], reason: &str) {
+    let mut by_segment: BTreeMap<(String, String), Vec<String>> = BTreeMap::new();
+
+    for path in paths {
+        let (Some(name), Some(date)) = (
+            path.file_name().and_then(|n| n.to_str()),
+            path.parent().and_then(|p| p.file_name()).and_then(|d| d.to_str()),
+        ) else {
+            continue;
+        };
+        if let (Some(segment_id), Ok(_)) = (segment_id_of(name), NaiveDate::parse_from_str(date, "%Y-%m-%d")) {
+            by_segment
+                .entry((date.to_string(), segment_id))
+                .or_default()
+                .push(name.to_string());
+        }
+    }
+
+    for ((date, segment_id), deleted) in by_segment {
+        let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d").expect("validated above");
+        if let Err(e) = append_entry(app, ChainEntryKind::Deleted, &date, &segment_id, Vec::new(), deleted, Some(reason)) {
+            log::error!("[INTEGRITY] Failed to record deletion of {}: {}", segment_id, e);
+        }
+    }
+}
+
+fn append_entry(
+    app: &AppHandle,
+    kind: ChainEntryKind,
+    date: &NaiveDate,
+    segment_id: &str,
+    files: Vec<FileDigest>,
+    deleted: Vec<String>,
+    reason: Option<&str>,
+) -> Result<(), String> {
+    let mut guard = CHAIN_WRITER.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(open_writer(app)?);
+    }
+    let writer = guard.as_mut().expect("initialized above");
+
+    let payload = EntryPayload {
+        seq: writer.next_seq,
+        prev_hash: writer.last_hash.clone(),
+        kind,
+        recorded_at: Utc::now().to_rfc3339(),
+        date: date.format("%Y-%m-%d").to_string(),
+        segment_id: segment_id.to_string(),
+        files,
+        deleted,
+        reason: reason.map(str::to_string),
+        public_key: writer.public_key.clone(),
+    };
+    let entry = sign_entry(&writer.key, payload)?;
+
+    let line = serde_json::to_string(&entry)
+        .map_err(|e| format!("Failed to serialize chain entry: {}", e))?;
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(chain_path(app))
+        .map_err(|e| format!("Failed to open hash chain: {}", e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("Failed to append to hash chain: {}", e))?;
+    file.sync_data().map_err(|e| format!("Failed to sync hash chain: {}", e))?;
+
+    writer.next_seq += 1;
+    writer.last_hash = entry.entry_hash.clone();
+
+    // The entry is already appended; a stale head only lags behind the chain
+    let head = ChainHead {
+        seq: entry.payload.seq,
+        entry_hash: entry.entry_hash,
+    };
+    if let Err(e) = save_head(app, &head) {
+        log::error!("[INTEGRITY] Failed to record chain head: {}", e);
+    }
+    Ok(())
+}
+
+// Load the device key and find the chain head
+fn open_writer(app: &AppHandle) -> Result<ChainWriter, String> {
+    std::fs::create_dir_all(integrity_dir(app))
+        .map_err(|e| format!("Failed to create integrity directory: {}", e))?;
+
+    let key = load_or_create_key(app)?;
+    let public_key = to_hex(key.public_key().as_ref());
+
+    let (next_seq, last_hash) = match load_entries(app)?.last() {
+        Some(last) => {
+            // Chains started before heads were recorded get one now
+            if load_head(app)?.is_none() {
+                save_head(
+                    app,
+                    &ChainHead {
+                        seq: last.payload.seq,
+                        entry_hash: last.entry_hash.clone(),
+                    },
+                )?;
+            }
+            (last.payload.seq + 1, last.entry_hash.clone())
+        }
+        None => (0, GENESIS_HASH.to_string()),
+    };
+
+    Ok(ChainWriter {
+        key,
+        public_key,
+        next_seq,
+        last_hash,
+    })
+}
+
+fn sign_entry(key: &Ed25519KeyPair, payload: EntryPayload) -> Result<ChainEntry, String> {
+    let entry_hash = payload_hash(&payload)?;
+    let signature = to_hex(key.sign(entry_hash.as_bytes()).as_ref());
+
+    Ok(ChainEntry {
+        payload,
+        entry_hash,
+        signature,
+    })
+}
+
+fn payload_hash(payload: &EntryPayload) -> Result<String, String> {
+    let canonical = serde_json::to_vec(payload)
+        .map_err(|e| format!("Failed to serialize chain entry: {}", e))?;
+    Ok(to_hex(ring::digest::digest(&SHA256, &canonical).as_ref()))
+}
+
+// =============================================================================
+// Verification
+// =============================================================================
+
+/**
+ * Walk the chain and re-hash every file it still expects on disk
+ */
+pub fn verify(app: &AppHandle) -> Result<IntegrityReport, String> {
+    let entries = load_entries(app)?;
+    let public_key = load_key(app)?.map(|key| to_hex(key.public_key().as_ref()));
+
+    let mut issues = Vec::new();
+    if public_key.is_none() && !entries.is_empty() {
+        issues.push(IntegrityIssue {
+            kind: IntegrityIssueKind::MissingKey,
+            seq: None,
+            segment_id: None,
+            file: None,
+            detail: "Signing key missing; entries can't be attributed to this device".to_string(),
+        });
+    }
+    issues.extend(verify_chain(&entries, public_key.as_deref()));
+    issues.extend(verify_head(&entries, load_head(app)?.as_ref()));
+    let chain_valid = issues.is_empty();
+
+    // Latest expected digest of every file, with the entry that set it
+    let mut expected: BTreeMap<(String, String), (u64, String, FileDigest)> = BTreeMap::new();
+    let mut files_deleted: u64 = 0;
+
+    for entry in &entries {
+        let p = &entry.payload;
+        for file in &p.files {
+            expected.insert((p.date.clone(), file.name.clone()), (p.seq, p.segment_id.clone(), file.clone()));
+        }
+        for name in &p.deleted {
+            if expected.remove(&(p.date.clone(), name.clone())).is_some() {
+                files_deleted += 1;
+            }
+        }
+    }
+
+    let mut files_verified: u64 = 0;
+    for ((date, name), (seq, segment_id, digest)) in &expected {
+        let path = file_path(app, date, name);
+        let issue = |kind, detail: String| IntegrityIssue {
+            kind,
+            seq: Some(*seq),
+            segment_id: Some(segment_id.clone()),
+            file: Some(format!("{}/{}", date, name)),
+            detail,
+        };
+
+        if !path.exists() {
+            issues.push(issue(IntegrityIssueKind::MissingFile, "File is missing and no deletion was recorded".to_string()));
+            continue;
+        }
+
+        match hash_file(&path) {
+            Ok((sha256, _)) if sha256 == digest.sha256 => files_verified += 1,
+            Ok((sha256, size)) => issues.push(issue(
+                IntegrityIssueKind::ModifiedFile,
+                format!("Expected sha256 {} ({} bytes), found {} ({} bytes)", digest.sha256, digest.size, sha256, size),
+            )),
+            Err(e) => issues.push(issue(IntegrityIssueKind::MissingFile, e)),
+        }
+    }
+
+    issues.extend(find_untracked_files(app, &entries));
+
+    Ok(IntegrityReport {
+        public_key: public_key.unwrap_or_default(),
+        entries_checked: entries.len() as u64,
+        files_verified,
+        files_deleted,
+        chain_valid,
+        issues,
+    })
+}
+
+/**
+ * Check hashes, signatures, sequence numbers and prev-hash links
+ * Without a device key signatures are checked but not attributed
+ */
+fn verify_chain(entries: &[ChainEntry], device_public_key: Option<&str>) -> Vec<IntegrityIssue> {
+    let mut issues = Vec::new();
+    let mut expected_prev = GENESIS_HASH.to_string();
+
+    for (index, entry) in entries.iter().enumerate() {
+        let p = &entry.payload;
+        let issue = |kind, detail: String| IntegrityIssue {
+            kind,
+            seq: Some(p.seq),
+            segment_id: Some(p.segment_id.clone()),
+            file: None,
+            detail,
+        };
+
+        if p.seq != index as u64 || p.prev_hash != expected_prev {
+            issues.push(issue(
+                IntegrityIssueKind::BrokenLink,
+                format!("Entry at position {} does not follow the previous entry", index),
+            ));
+        }
+
+        match payload_hash(p) {
+            Ok(hash) if hash == entry.entry_hash => {}
+            _ => issues.push(issue(IntegrityIssueKind::TamperedEntry, "Entry hash does not match contents".to_string())),
+        }
+
+        let signature_ok = from_hex(&p.public_key)
+            .zip(from_hex(&entry.signature))
+            .map(|(key, signature)| {
+                UnparsedPublicKey::new(&ED25519, key)
+                    .verify(entry.entry_hash.as_bytes(), &signature)
+                    .is_ok()
+            })
+            .unwrap_or(false);
+        if !signature_ok {
+            issues.push(issue(IntegrityIssueKind::TamperedEntry, "Invalid signature".to_string()));
+        } else if device_public_key.is_some_and(|key| p.public_key != key) {
+            issues.push(issue(IntegrityIssueKind::TamperedEntry, "Signed by a different device key".to_string()));
+        }
+
+        // Continue from the recorded hash so one bad entry isn't reported for every later one
+        expected_prev = entry.entry_hash.clone();
+    }
+
+    issues
+}
+
+/**
+ * Check the chain still contains the recorded head
+ * Entries after the head are fine (appended before the head was saved)
+ */
+fn verify_head(entries: &[ChainEntry], head: Option<&ChainHead>) -> Option<IntegrityIssue> {
+    let detail = match head {
+        Some(head) => {
+            let found = entries
+                .get(head.seq as usize)
+                .is_some_and(|entry| entry.payload.seq == head.seq && entry.entry_hash == head.entry_hash);
+            if found {
+                return None;
+            }
+            format!(
+                "Chain has {} entries but the recorded head is entry {}",
+                entries.len(),
+                head.seq
+            )
+        }
+        None if !entries.is_empty() => {
+            "Chain head record is missing; entries removed from the end can't be ruled out".to_string()
+        }
+        None => return None,
+    };
+
+    Some(IntegrityIssue {
+        kind: IntegrityIssueKind::TruncatedChain,
+        seq: head.map(|h| h.seq),
+        segment_id: None,
+        file: None,
+        detail,
+    })
+}
+
+// Segment files written after the chain started that were never sealed
+fn find_untracked_files(app: &AppHandle, entries: &[ChainEntry]) -> Vec<IntegrityIssue> {
+    let Some(genesis) = entries
+        .first()
+        .and_then(|e| DateTime::parse_from_rfc3339(&e.payload.recorded_at).ok())
+    else {
+        return Vec::new();
+    };
+    let genesis: std::time::SystemTime = genesis.with_timezone(&Utc).into();
+
+    let known: HashSet<(String, String)> = entries
+        .iter()
+        .flat_map(|e| {
+            let date = e.payload.date.clone();
+            e.payload
+                .files
+                .iter()
+                .map(|f| f.name.clone())
+                .chain(e.payload.deleted.iter().cloned())
+                .map(move |name| (date.clone(), name))
+        })
+        .collect();
+
+    storage::list_recording_files(app)
+        .into_iter()
+        .filter(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|ext| SEGMENT_EXTENSIONS.contains(&ext))
+        })
+        .filter(|path| {
+            std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| modified > genesis)
+        })
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.to_string();
+            let date = path.parent()?.file_name()?.to_str()?.to_string();
+            if known.contains(&(date.clone(), name.clone())) {
+                return None;
+            }
+            Some(IntegrityIssue {
+                kind: IntegrityIssueKind::UntrackedFile,
+                seq: None,
+                segment_id: segment_id_of(&name),
+                file: Some(format!("{}/{}", date, name)),
+                detail: "File was never sealed into the hash chain".to_string(),
+            })
+        })
+        .collect()
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+fn integrity_dir(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .expect("app_data_dir available")
+        .join("integrity")
+}
+
+fn chain_path(app: &AppHandle) -> PathBuf {
+    integrity_dir(app).join("chain.jsonl")
+}
+
+// Resolve a segment file, following a recordings root migration
+fn file_path(app: &AppHandle, date: &str, name: &str) -> PathBuf {
+    storage::resolve_recording_path(app, &storage::get_recordings_dir(app).join(date).join(name))
+}
+
+fn load_entries(app: &AppHandle) -> Result<Vec<ChainEntry>, String> {
+    let file = match std::fs::File::open(chain_path(app)) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read hash chain: {}", e)),
+    };
+
+    std::io::BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.as_ref().map(|l| !l.trim().is_empty()).unwrap_or(true))
+        .map(|(i, line)| {
+            let line = line.map_err(|e| format!("Failed to read hash chain: {}", e))?;
+            serde_json::from_str(&line).map_err(|e| format!("Corrupt hash chain entry on line {}: {}", i + 1, e))
+        })
+        .collect()
+}
+
+fn head_path(app: &AppHandle) -> PathBuf {
+    integrity_dir(app).join("chain_head.json")
+}
+
+fn key_path(app: &AppHandle) -> PathBuf {
+    integrity_dir(app).join("device_key.pk8")
+}
+
+fn load_head(app: &AppHandle) -> Result<Option<ChainHead>, String> {
+    match std::fs::read_to_string(head_path(app)) {
+        Ok(contents) => serde_json::from_str(&contents)
+            .map(Some)
+            .map_err(|e| format!("Corrupt chain head: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read chain head: {}", e)),
+    }
+}
+
+// Write to a temp file and rename, so a crash never leaves a partial head
+fn save_head(app: &AppHandle, head: &ChainHead) -> Result<(), String> {
+    let path = head_path(app);
+    let tmp = path.with_extension("json.tmp");
+    let contents = serde_json::to_string(head).map_err(|e| format!("Failed to serialize chain head: {}", e))?;
+    std::fs::write(&tmp, contents).map_err(|e| format!("Failed to write chain head: {}", e))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("Failed to write chain head: {}", e))
+}
+
+// Load the device key without creating one (None if missing)
+fn load_key(app: &AppHandle) -> Result<Option<Ed25519KeyPair>, String> {
+    match std::fs::read(key_path(app)) {
+        Ok(bytes) => Ed25519KeyPair::from_pkcs8(&bytes)
+            .map(Some)
+            .map_err(|e| format!("Invalid device signing key: {}", e)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read device signing key: {}", e)),
+    }
+}
+
+fn load_or_create_key(app: &AppHandle) -> Result<Ed25519KeyPair, String> {
+    if let Some(key) = load_key(app)? {
+        return Ok(key);
+    }
+    let path = key_path(app);
+
+    let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+        .map_err(|_| "Failed to generate device signing key".to_string())?;
+
+    std::fs::create_dir_all(integrity_dir(app))
+        .map_err(|e| format!("Failed to create integrity directory: {}", e))?;
+    std::fs::write(&path, pkcs8.as_ref())
+        .map_err(|e| format!("Failed to write device signing key: {}", e))?;
+
+    // Set restrictive file permissions on the key file itself
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+    }
+
+    log::info!("[INTEGRITY] Generated new device signing key");
+    Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).map_err(|e| format!("Invalid device signing key: {}", e))
+}
+
+// SHA-256 of a file, streamed so large videos aren't loaded into memory
+fn hash_file(path: &Path) -> Result<(String, u64), String> {
+    let mut file = std::fs::File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    let mut context = Context::new(&SHA256);
+    let mut buffer = vec![0u8; 64 * 1024];
+    let mut size: u64 = 0;
+
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        if read == 0 {
+            break;
+        }
+        context.update(&buffer[..read]);
+        size += read as u64;
+    }
+
+    Ok((to_hex(context.finish().as_ref()), size))
+}
+
+// Segment ID from a segment file name (video, frames or sidecar)
+fn segment_id_of(name: &str) -> Option<String> {
+    let (stem, ext) = name.rsplit_once('.')?;
+    match ext {
+        "json" | "input" => Some(stem.to_string()),
+        "mp4" | "frames" => stem.rsplit_once("_d").map(|(id, _)| id.to_string()),
+        _ => None,
+    }
+    .filter(|id| id.starts_with("segment_"))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_chain(key: &Ed25519KeyPair, count: u64) -> Vec<ChainEntry> {
+        let public_key = to_hex(key.public_key().as_ref());
+        let mut prev_hash = GENESIS_HASH.to_string();
+        (0..count)
+            .map(|seq| {
+                let entry = sign_entry(
+                    key,
+                    EntryPayload {
+                        seq,
+                        prev_hash: prev_hash.clone(),
+                        kind: ChainEntryKind::Sealed,
+                        recorded_at: "2025-01-15T10:00:00+00:00".to_string(),
+                        date: "2025-01-15".to_string(),
+                        segment_id: format!("segment_{}_abc", seq),
+                        files: vec![FileDigest {
+                            name: format!("segment_{}_abc_d0.mp4", seq),
+                            sha256: "00".repeat(32),
+                            size: 1,
+                        }],
+                        deleted: Vec::new(),
+                        reason: None,
+                        public_key: public_key.clone(),
+                    },
+                )
+                .unwrap();
+                prev_hash = entry.entry_hash.clone();
+                entry
+            })
+            .collect()
+    }
+
+    fn test_key() -> Ed25519KeyPair {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap()
+    }
+
+    #[test]
+    fn test_valid_chain_has_no_issues() {
+        let key = test_key();
+        let chain = build_chain(&key, 3);
+        assert!(verify_chain(&chain, Some(&to_hex(key.public_key().as_ref()))).is_empty());
+        // Without the device key signatures still verify, nothing is attributed
+        assert!(verify_chain(&chain, None).is_empty());
+        assert!(!verify_chain(&chain, Some(&to_hex(test_key().public_key().as_ref()))).is_empty());
+    }
+
+    #[test]
+    fn test_detects_truncated_chain() {
+        let chain = build_chain(&test_key(), 3);
+        let head = |entry: &ChainEntry| ChainHead {
+            seq: entry.payload.seq,
+            entry_hash: entry.entry_hash.clone(),
+        };
+
+        assert!(verify_head(&chain, Some(&head(&chain[2]))).is_none());
+        // Head saved one entry behind (crash after appending)
+        assert!(verify_head(&chain, Some(&head(&chain[1]))).is_none());
+
+        let truncated = &chain[..2];
+        let issue = verify_head(truncated, Some(&head(&chain[2]))).unwrap();
+        assert_eq!((issue.kind, issue.seq), (IntegrityIssueKind::TruncatedChain, Some(2)));
+        assert!(verify_head(&[], Some(&head(&chain[2]))).is_some());
+
+        assert!(verify_head(&chain, None).is_some());
+        assert!(verify_head(&[], None).is_none());
+    }
+
+    #[test]
+    fn test_detects_tampered_and_reordered_entries() {
+        let key = test_key();
+        let public_key = to_hex(key.public_key().as_ref());
+
+        let mut tampered = build_chain(&key, 3);
+        tampered[1].payload.files[0].sha256 = "ff".repeat(32);
+        let issues = verify_chain(&tampered, Some(&public_key));
+        assert!(issues.iter().any(|i| i.kind == IntegrityIssueKind::TamperedEntry && i.seq == Some(1)));
+
+        let mut reordered = build_chain(&key, 3);
+        reordered.swap(1, 2);
+        let issues = verify_chain(&reordered, Some(&public_key));
+        assert!(issues.iter().any(|i| i.kind == IntegrityIssueKind::BrokenLink));
+
+        let mut removed = build_chain(&key, 3);
+        removed.remove(1);
+        assert!(!verify_chain(&removed, Some(&public_key)).is_empty());
+    }
+
+    #[test]
+    fn test_segment_id_of() {
+        assert_eq!(segment_id_of("segment_1736956800_abc123.json").as_deref(), Some("segment_1736956800_abc123"));
+        assert_eq!(segment_id_of("segment_1736956800_abc123_d1.mp4").as_deref(), Some("segment_1736956800_abc123"));
+        assert_eq!(segment_id_of("segment_1736956800_abc123_d0.frames").as_deref(), Some("segment_1736956800_abc123"));
+        assert_eq!(segment_id_of("segment_1736956800_abc123.input").as_deref(), Some("segment_1736956800_abc123"));
+        assert_eq!(segment_id_of("manifest.json"), None);
+    }
+}
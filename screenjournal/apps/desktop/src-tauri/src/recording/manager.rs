@@ -20,13 +20,13 @@
  * ============================================================================
  */
 
-use crate::recording::{capture, config, gemini, storage, types::*};
+use crate::recording::{capture, classify, config, coverage, gemini, import, input, integrity, migration, ocr, privacy, storage, timelapse, types::*};
 use chrono::Utc;
 use once_cell::sync::Lazy;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 
 // Global recording configuration
 static RECORDING_CONFIG: Lazy<Mutex<RecordingConfig>> =
@@ -81,9 +81,15 @@ impl std::fmt::Debug for RecordingStateHolder {
 
 // Initialize configuration on app startup
 pub fn init_config(config: RecordingConfig) {
+    storage::set_recordings_root(config.recordings_dir.as_ref().map(std::path::PathBuf::from));
     *RECORDING_CONFIG.lock().unwrap() = config;
 }
 
+// Get a snapshot of the current recording configuration (for background workers)
+pub fn current_config() -> RecordingConfig {
+    RECORDING_CONFIG.lock().unwrap().clone()
+}
+
 // Start screen recording
 #[tauri::command]
 pub async fn start_recording(app: AppHandle) -> Result<(), String> {
@@ -135,6 +141,8 @@ pub async fn stop_recording(app: AppHandle) -> Result<(), String> {
     // Finalize current segment
     finalize_current_segment(&app)?;
     
+    coverage::record_event(&app, CoverageIncidentKind::RecordingStopped, None, None);
+    
     // Set state to idle
     {
         let mut state = RECORDING_STATE.lock().unwrap();
@@ -237,6 +245,7 @@ fn finalize_current_segment(app: &AppHandle) -> Result<Option<RecordingMetadata>
     
     // Wait for all capture threads to finish and collect results
     let mut display_recordings = Vec::new();
+    let mut frame_timestamps = Vec::new();
     let mut total_file_size: u64 = 0;
     let date = start_time.date_naive();
     
@@ -259,13 +268,21 @@ fn finalize_current_segment(app: &AppHandle) -> Result<Option<RecordingMetadata>
                     frame_count: result.frame_count,
                     file_size_bytes: result.file_size,
                     filename,
+                    classification: None,
                 });
+                
+                frame_timestamps.push((result.display_index, FrameTimestamps {
+                    started_at: result.first_frame_at.to_rfc3339(),
+                    offsets_ms: result.frame_offsets_ms,
+                }));
             }
             Ok(Err(e)) => {
                 log::error!("Display {}: Capture thread error: {}", ct.display_index, e);
+                record_capture_error(app, start_time, Some(ct.display_index), e);
             }
             Err(_) => {
                 log::error!("Display {}: Capture thread panicked", ct.display_index);
+                record_capture_error(app, start_time, Some(ct.display_index), "Capture thread panicked".to_string());
             }
         }
     }
@@ -292,11 +309,25 @@ fn finalize_current_segment(app: &AppHandle) -> Result<Option<RecordingMetadata>
         total_file_size_bytes: total_file_size,
         display_count,
         displays: display_recordings,
+        imported_from: None,
+        high_risk: false,
     };
     
     // Save metadata JSON
     storage::save_metadata(app, &date, &metadata)?;
     
+    // Per-frame capture times (used for exact seeking; optional)
+    for (display_index, frames) in &frame_timestamps {
+        if let Err(e) = storage::save_frame_timestamps(app, &date, &segment_id, *display_index, frames) {
+            log::warn!("Display {}: {}", display_index, e);
+        }
+    }
+    
+    // Seal the segment files into the tamper-evident hash chain
+    if let Err(e) = integrity::seal_segment(app, &date, &metadata) {
+        log::error!("Failed to seal segment {}: {}", segment_id, e);
+    }
+    
     // Update statistics
     {
         let mut stats = RECORDING_STATS.lock().unwrap();
@@ -315,9 +346,57 @@ fn finalize_current_segment(app: &AppHandle) -> Result<Option<RecordingMetadata>
     storage::cleanup_old_recordings(app, &config).ok();
     storage::cleanup_by_quota(app, &config).ok();
     
-    // Submit Gemini analysis jobs for each display
+    // Queue OCR indexing in the background (never blocks rotation)
+    if config.ocr.enabled {
+        tauri::async_runtime::spawn(ocr::index_segment(
+            app.clone(),
+            date,
+            metadata.clone(),
+            config.clone(),
+        ));
+    }
+    
+    // Summarize keyboard/mouse activity from ActivityWatch in the background
+    if config.input_activity.enabled {
+        tauri::async_runtime::spawn(input::summarize_segment(
+            app.clone(),
+            date,
+            metadata.clone(),
+            config.clone(),
+        ));
+    }
+    
+    // Classify displays (blank/locked/static), then submit Gemini analysis jobs
+    classify_and_submit(app, date, metadata.clone());
+    
+    Ok(Some(metadata))
+}
+
+// Record a capture failure spanning from `start_time` until now (for coverage reports)
+fn record_capture_error(app: &AppHandle, start_time: chrono::DateTime<Utc>, display_index: Option<u32>, message: String) {
+    coverage::record_incident(app, CoverageIncident {
+        kind: CoverageIncidentKind::CaptureError,
+        start_time: start_time.to_rfc3339(),
+        end_time: Utc::now().to_rfc3339(),
+        display_index,
+        message: Some(message),
+    });
+}
+
+// Classify a segment's displays in the background, then queue analysis
+// (the queue skips blank and locked displays based on the classification)
+fn classify_and_submit(app: &AppHandle, date: chrono::NaiveDate, metadata: RecordingMetadata) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let metadata = classify::classify_segment(app.clone(), date, metadata).await;
+        submit_analysis_jobs(&app, &date, &metadata);
+    });
+}
+
+// Submit one Gemini analysis job per display of a segment
+fn submit_analysis_jobs(app: &AppHandle, date: &chrono::NaiveDate, metadata: &RecordingMetadata) {
     for display in &metadata.displays {
-        let video_path = storage::get_video_path(app, &date, &metadata.id, display.display_index);
+        let video_path = storage::find_video_path(app, date, &metadata.id, display.display_index);
         
         let job = gemini::GeminiJob {
             segment_id: metadata.id.clone(),
@@ -327,14 +406,14 @@ fn finalize_current_segment(app: &AppHandle) -> Result<Option<RecordingMetadata>
             retry_count: 0,
             rate_limit_waits: 0,
             created_at: chrono::Utc::now(),
+            model_index: 0,
+            reanalysis: None,
         };
         
         if let Err(e) = gemini::submit_job(job) {
             log::warn!("Failed to queue Gemini analysis job: {}", e);
         }
     }
-    
-    Ok(Some(metadata))
 }
 
 // Segment rotation loop
@@ -369,6 +448,7 @@ async fn segment_rotation_loop(app: AppHandle, segment_duration: Duration, gener
         }
         
         log::info!("Rotating segment...");
+        let rotated_at = Utc::now();
         
         // Signal current capture to stop
         SHUTDOWN_SIGNAL.store(true, Ordering::SeqCst);
@@ -377,9 +457,13 @@ async fn segment_rotation_loop(app: AppHandle, segment_duration: Duration, gener
         tokio::time::sleep(Duration::from_millis(500)).await;
         
         // Finalize current segment
-        if let Err(e) = finalize_current_segment(&app) {
-            log::error!("Failed to finalize segment: {}", e);
-        }
+        let finalized = match finalize_current_segment(&app) {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                log::error!("Failed to finalize segment: {}", e);
+                None
+            }
+        };
         
         // Reset shutdown signal for new segment
         SHUTDOWN_SIGNAL.store(false, Ordering::SeqCst);
@@ -388,6 +472,13 @@ async fn segment_rotation_loop(app: AppHandle, segment_duration: Duration, gener
         let config = RECORDING_CONFIG.lock().unwrap().clone();
         if let Err(e) = start_new_segment(&app, &config) {
             log::error!("Failed to start new segment: {}", e);
+            // Nothing is recorded from the end of the last segment until
+            // recording restarts; the coverage report attributes that gap here
+            let gap_start = finalized
+                .and_then(|m| chrono::DateTime::parse_from_rfc3339(&m.end_time).ok())
+                .map(|t| t.with_timezone(&Utc))
+                .unwrap_or(rotated_at);
+            record_capture_error(&app, gap_start, None, e);
             break;
         }
     }
@@ -476,6 +567,13 @@ pub async fn update_recording_config(
         ));
     }
     
+    // Validate privacy patterns (regexes)
+    privacy::PrivacyRules::from_config(&new_config.privacy)?;
+    
+    if new_config.ocr.sample_interval_seconds < 1 {
+        return Err("OCR sample interval must be at least 1 second".to_string());
+    }
+    
     let current_config = RECORDING_CONFIG.lock().unwrap().clone();
     
     if current_config == new_config {
@@ -483,6 +581,22 @@ pub async fn update_recording_config(
         return Ok(());
     }
     
+    // Validate a recordings root change before anything is switched
+    let root_changed = current_config.recordings_dir != new_config.recordings_dir;
+    let old_root = storage::get_recordings_dir(&app);
+    let new_root = new_config
+        .recordings_dir
+        .as_ref()
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| storage::default_recordings_dir(&app));
+    
+    if root_changed {
+        if migration::is_running() {
+            return Err("Cannot change recordings directory while a migration is in progress".to_string());
+        }
+        migration::validate_destination(&old_root, &new_root)?;
+    }
+    
     let needs_restart = current_config.needs_recording_restart(&new_config);
     let is_recording = {
         let state = RECORDING_STATE.lock().unwrap();
@@ -497,16 +611,19 @@ pub async fn update_recording_config(
     if is_recording && needs_restart {
         log::info!("Recording config change requires restart");
         stop_recording(app.clone()).await?;
-        
-        if new_config.enabled {
-            start_recording(app).await?;
+    }
+    
+    // Switch roots after the in-flight segment was finalized in the old one
+    if root_changed {
+        storage::set_recordings_root(Some(new_root.clone()));
+        migration::start(&app, old_root, new_root)?;
+    }
+    
+    if new_config.enabled && (needs_restart || !is_recording) {
+        if !is_recording {
+            log::info!("Starting recording (was not running)");
         }
-    } else if !is_recording && new_config.enabled {
-        log::info!("Starting recording (was not running)");
         start_recording(app).await?;
-    } else if is_recording && !new_config.enabled {
-        log::info!("Stopping recording (disabled in config)");
-        stop_recording(app).await?;
     }
     
     Ok(())
@@ -525,15 +642,7 @@ pub async fn get_recordings_by_date_range(
     start_time: String,
     end_time: String,
 ) -> Result<RecordingsResponse, String> {
-    // Parse the ISO 8601 timestamps
-    let start = chrono::DateTime::parse_from_rfc3339(&start_time)
-        .map_err(|e| format!("Invalid start_time format: {}", e))?
-        .with_timezone(&Utc);
-    
-    let end = chrono::DateTime::parse_from_rfc3339(&end_time)
-        .map_err(|e| format!("Invalid end_time format: {}", e))?
-        .with_timezone(&Utc);
-    
+    let (start, end) = parse_time_range(&start_time, &end_time)?;
     let recordings = storage::get_recordings_in_range(&app, &start, &end)?;
     let total_count = recordings.len() as u64;
     
@@ -543,6 +652,248 @@ pub async fn get_recordings_by_date_range(
     })
 }
 
+// =============================================================================
+// Timelapse Commands
+// =============================================================================
+
+// Get timelapse manifests for a date range (YYYY-MM-DD, inclusive)
+#[tauri::command]
+pub async fn get_timelapses(
+    app: AppHandle,
+    start_date: String,
+    end_date: String,
+) -> Result<Vec<TimelapseManifest>, String> {
+    let start = chrono::NaiveDate::parse_from_str(&start_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid start_date format (expected YYYY-MM-DD): {}", e))?;
+    
+    let end = chrono::NaiveDate::parse_from_str(&end_date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid end_date format (expected YYYY-MM-DD): {}", e))?;
+    
+    Ok(timelapse::get_manifests_in_range(&app, &start, &end))
+}
+
+// Generate (or regenerate) the timelapse for a specific day
+#[tauri::command]
+pub async fn generate_timelapse(
+    app: AppHandle,
+    date: String,
+) -> Result<Option<TimelapseManifest>, String> {
+    let date = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date format (expected YYYY-MM-DD): {}", e))?;
+    
+    let config = RECORDING_CONFIG.lock().unwrap().clone();
+    
+    tokio::task::spawn_blocking(move || timelapse::generate_timelapse(&app, &date, &config))
+        .await
+        .map_err(|e| format!("Timelapse task failed: {}", e))?
+}
+
+// =============================================================================
+// Recordings Directory Commands
+// =============================================================================
+
+// Get the effective recordings root directory
+#[tauri::command]
+pub async fn get_recordings_dir(app: AppHandle) -> Result<String, String> {
+    Ok(storage::get_recordings_dir(&app).to_string_lossy().to_string())
+}
+
+// Get progress of the current (or last) recordings migration, if any
+#[tauri::command]
+pub async fn get_recordings_migration_status() -> Result<Option<RecordingsMigrationProgress>, String> {
+    Ok(migration::get_progress())
+}
+
+// =============================================================================
+// Seek Commands
+// =============================================================================
+
+// Find the segment file and offset showing a moment on a display
+// timestamp is ISO 8601; the returned URL is served by the asset protocol
+#[tauri::command]
+pub async fn seek_recording(
+    app: AppHandle,
+    timestamp: String,
+    display_index: u32,
+) -> Result<SeekResult, String> {
+    let at = chrono::DateTime::parse_from_rfc3339(&timestamp)
+        .map_err(|e| format!("Invalid timestamp: {}", e))?
+        .with_timezone(&Utc);
+    
+    let result = storage::seek(&app, at, display_index)?;
+    
+    // A custom recordings root is outside the static $APPDATA asset scope
+    if let Some(dir) = std::path::Path::new(&result.file_path).parent() {
+        if let Err(e) = app.asset_protocol_scope().allow_directory(dir, false) {
+            log::warn!("Failed to allow asset access to {:?}: {}", dir, e);
+        }
+    }
+    
+    Ok(result)
+}
+
+// =============================================================================
+// Integrity Commands
+// =============================================================================
+
+// Verify the segment hash chain and every sealed file still on disk
+#[tauri::command]
+pub async fn verify_recording_integrity(app: AppHandle) -> Result<IntegrityReport, String> {
+    tokio::task::spawn_blocking(move || integrity::verify(&app))
+        .await
+        .map_err(|e| format!("Integrity verification failed: {}", e))?
+}
+
+// =============================================================================
+// Coverage Commands
+// =============================================================================
+
+// Get recording coverage and detected gaps for a day (YYYY-MM-DD, UTC)
+#[tauri::command]
+pub async fn get_recording_coverage(
+    app: AppHandle,
+    date: String,
+) -> Result<CoverageReport, String> {
+    let date = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date format (expected YYYY-MM-DD): {}", e))?;
+    
+    let config = RECORDING_CONFIG.lock().unwrap().clone();
+    coverage::build_report(&app, &date, &config).await
+}
+
+// =============================================================================
+// Import Commands
+// =============================================================================
+
+// Import an external MP4/MKV recording as a segment and queue it for analysis
+// start_time is ISO 8601; transcode re-encodes to the configured profile
+#[tauri::command]
+pub async fn import_recording(
+    app: AppHandle,
+    file_path: String,
+    start_time: String,
+    display_index: Option<u32>,
+    transcode: Option<bool>,
+) -> Result<RecordingMetadata, String> {
+    let start = chrono::DateTime::parse_from_rfc3339(&start_time)
+        .map_err(|e| format!("Invalid start time: {}", e))?
+        .with_timezone(&Utc);
+    
+    let config = RECORDING_CONFIG.lock().unwrap().clone();
+    let app_clone = app.clone();
+    
+    let metadata = tokio::task::spawn_blocking(move || {
+        import::import_video(
+            &app_clone,
+            std::path::Path::new(&file_path),
+            start,
+            display_index.unwrap_or(0),
+            transcode.unwrap_or(false),
+            &config,
+        )
+    })
+    .await
+    .map_err(|e| format!("Import task failed: {}", e))??;
+    
+    let date = start.date_naive();
+    if let Err(e) = integrity::seal_segment(&app, &date, &metadata) {
+        log::error!("Failed to seal imported segment {}: {}", metadata.id, e);
+    }
+    
+    {
+        let mut stats = RECORDING_STATS.lock().unwrap();
+        stats.total_segments += 1;
+    }
+    
+    let config = RECORDING_CONFIG.lock().unwrap().clone();
+    
+    if config.input_activity.enabled {
+        tauri::async_runtime::spawn(input::summarize_segment(app.clone(), date, metadata.clone(), config.clone()));
+    }
+    
+    if config.ocr.enabled {
+        tauri::async_runtime::spawn(ocr::index_segment(app.clone(), date, metadata.clone(), config));
+    }
+    
+    classify_and_submit(&app, date, metadata.clone());
+    
+    Ok(metadata)
+}
+
+// =============================================================================
+// Input Activity Commands
+// =============================================================================
+
+// Get the input activity summary for a segment (None if not summarized)
+#[tauri::command]
+pub async fn get_input_activity(
+    app: AppHandle,
+    date: String,
+    segment_id: String,
+) -> Result<Option<InputActivitySummary>, String> {
+    let date = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date format (expected YYYY-MM-DD): {}", e))?;
+    
+    Ok(storage::load_input_activity(&app, &date, &segment_id))
+}
+
+// Render a copy of a display video with the input activity bar burned in
+// Returns the path of the exported MP4
+#[tauri::command]
+pub async fn export_input_overlay(
+    app: AppHandle,
+    date: String,
+    segment_id: String,
+    display_index: u32,
+) -> Result<String, String> {
+    let date = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date format (expected YYYY-MM-DD): {}", e))?;
+    
+    let config = RECORDING_CONFIG.lock().unwrap().clone();
+    
+    tokio::task::spawn_blocking(move || {
+        input::export_overlay(&app, &date, &segment_id, display_index, &config)
+            .map(|path| path.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| format!("Overlay export task failed: {}", e))?
+}
+
+// =============================================================================
+// OCR Text Search Commands
+// =============================================================================
+
+// Default number of OCR search hits returned
+const DEFAULT_OCR_SEARCH_LIMIT: usize = 50;
+
+// Search text recognized in recorded frames
+// Dates are YYYY-MM-DD (inclusive); omit them to search every indexed day
+#[tauri::command]
+pub async fn search_recorded_text(
+    app: AppHandle,
+    query: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+    limit: Option<usize>,
+) -> Result<Vec<OcrSearchHit>, String> {
+    let parse = |value: Option<String>| -> Result<Option<chrono::NaiveDate>, String> {
+        value
+            .map(|v| {
+                chrono::NaiveDate::parse_from_str(&v, "%Y-%m-%d")
+                    .map_err(|e| format!("Invalid date '{}' (expected YYYY-MM-DD): {}", v, e))
+            })
+            .transpose()
+    };
+    
+    let start = parse(start_date)?;
+    let end = parse(end_date)?;
+    let limit = limit.unwrap_or(DEFAULT_OCR_SEARCH_LIMIT);
+    
+    tokio::task::spawn_blocking(move || ocr::search(&app, &query, start, end, limit))
+        .await
+        .map_err(|e| format!("OCR search task failed: {}", e))
+}
+
 // =============================================================================
 // Gemini AI Integration Commands
 // =============================================================================
@@ -565,6 +916,12 @@ pub async fn update_gemini_config(
     app: AppHandle,
     new_config: gemini::GeminiConfig,
 ) -> Result<(), String> {
+    // Reject a prompt template that doesn't exist or isn't valid
+    gemini::prompt::load_template(
+        &gemini::prompt::templates_dir(&app),
+        new_config.prompt_template.as_deref(),
+    )?;
+    
     // Save config to disk
     config::save_gemini_config(&app, &new_config)?;
     
@@ -575,12 +932,163 @@ pub async fn update_gemini_config(
     Ok(())
 }
 
+// List analysis prompt templates (built-in plus every saved version)
+#[tauri::command]
+pub async fn list_prompt_templates(app: AppHandle) -> Result<Vec<gemini::prompt::PromptTemplate>, String> {
+    Ok(gemini::prompt::list_templates(&gemini::prompt::templates_dir(&app)))
+}
+
+// Validate a prompt template and save it as the next version of `name`
+// Select it via GeminiConfig.prompt_template
+#[tauri::command]
+pub async fn save_prompt_template(
+    app: AppHandle,
+    name: String,
+    body: String,
+) -> Result<gemini::prompt::PromptTemplate, String> {
+    gemini::prompt::save_template(&gemini::prompt::templates_dir(&app), name.trim(), &body)
+}
+
 // Get Gemini queue status
 #[tauri::command]
 pub async fn get_gemini_queue_status() -> Result<gemini::QueueStatus, String> {
     Ok(gemini::get_queue_status())
 }
 
+// List pending, in-flight and failed (dead-letter) Gemini jobs
+#[tauri::command]
+pub async fn list_gemini_jobs() -> Result<gemini::QueueListing, String> {
+    Ok(gemini::queue::list_jobs())
+}
+
+// Cancel a pending or in-flight Gemini job
+// Returns false if no such job is queued
+#[tauri::command]
+pub async fn cancel_gemini_job(segment_id: String, display_index: u32) -> Result<bool, String> {
+    Ok(gemini::queue::cancel_job(&segment_id, display_index))
+}
+
+// Re-queue failed Gemini jobs: one job when segment_id is given, all otherwise
+// Returns the number of re-queued jobs
+#[tauri::command]
+pub async fn retry_failed_gemini_jobs(segment_id: Option<String>, display_index: Option<u32>) -> Result<usize, String> {
+    let job = segment_id.as_deref().map(|id| (id, display_index.unwrap_or(0)));
+    gemini::queue::retry_failed_jobs(job)
+}
+
+// Move pending Gemini jobs of a day (default: today) ahead of the backlog
+// Returns the number of jobs moved
+#[tauri::command]
+pub async fn prioritize_gemini_jobs(date: Option<String>) -> Result<usize, String> {
+    let date = match date {
+        Some(date) => chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid date format (expected YYYY-MM-DD): {}", e))?,
+        None => Utc::now().date_naive(),
+    };
+    
+    Ok(gemini::queue::prioritize_date(date))
+}
+
+// Get the stored timeline analyses of a segment (current one per analyzed display, or every version)
+#[tauri::command]
+pub async fn get_segment_timeline(
+    app: AppHandle,
+    date: String,
+    segment_id: String,
+    all_versions: Option<bool>,
+) -> Result<Vec<gemini::TimelineAnalysis>, String> {
+    let date = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date format (expected YYYY-MM-DD): {}", e))?;
+    
+    gemini::results::segment_analyses(&app, &date, &segment_id, all_versions.unwrap_or(false))
+}
+
+// Get the stored timeline analyses of segments starting in a time range, oldest first
+#[tauri::command]
+pub async fn get_timelines_by_date_range(
+    app: AppHandle,
+    start_time: String,
+    end_time: String,
+) -> Result<Vec<gemini::TimelineAnalysis>, String> {
+    let (start, end) = parse_time_range(&start_time, &end_time)?;
+    gemini::results::analyses_in_range(&app, &start, &end)
+}
+
+// Queue the stored timeline analyses of a time range to the collector again
+#[tauri::command]
+pub async fn resend_timelines(
+    app: AppHandle,
+    start_time: String,
+    end_time: String,
+) -> Result<gemini::results::ResendSummary, String> {
+    let (start, end) = parse_time_range(&start_time, &end_time)?;
+    gemini::results::resend_to_collector(&app, &start, &end)
+}
+
+// Queue reanalysis of a time range's segments with a chosen prompt and model
+// Results are saved and sent as new analysis versions
+#[tauri::command]
+pub async fn reanalyze_segments(
+    app: AppHandle,
+    start_time: String,
+    end_time: String,
+    filter: Option<gemini::reanalysis::ReanalysisFilter>,
+    prompt_template: Option<String>,
+    model: Option<gemini::types::ModelTarget>,
+) -> Result<gemini::reanalysis::ReanalysisSummary, String> {
+    let (start, end) = parse_time_range(&start_time, &end_time)?;
+    gemini::reanalysis::enqueue_reanalysis(
+        &app,
+        &start,
+        &end,
+        &filter.unwrap_or_default(),
+        prompt_template.as_deref(),
+        model,
+    )
+}
+
+// Parse an ISO 8601 start/end pair
+fn parse_time_range(start_time: &str, end_time: &str) -> Result<(chrono::DateTime<Utc>, chrono::DateTime<Utc>), String> {
+    let start = chrono::DateTime::parse_from_rfc3339(start_time)
+        .map_err(|e| format!("Invalid start_time format: {}", e))?
+        .with_timezone(&Utc);
+    
+    let end = chrono::DateTime::parse_from_rfc3339(end_time)
+        .map_err(|e| format!("Invalid end_time format: {}", e))?
+        .with_timezone(&Utc);
+    
+    Ok((start, end))
+}
+
+// Flag a segment as high-risk so analysis routes it via GeminiConfig::routing_rules
+// Applies to queued jobs too (the queue re-reads the flag before analyzing)
+#[tauri::command]
+pub async fn set_segment_high_risk(
+    app: AppHandle,
+    date: String,
+    segment_id: String,
+    high_risk: bool,
+) -> Result<RecordingMetadata, String> {
+    let date = chrono::NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+        .map_err(|e| format!("Invalid date format (expected YYYY-MM-DD): {}", e))?;
+    
+    let mut metadata = storage::get_segments_for_date(&app, &date)
+        .into_iter()
+        .find(|m| m.id == segment_id)
+        .ok_or_else(|| format!("Segment not found: {}", segment_id))?;
+    
+    if metadata.high_risk == high_risk {
+        return Ok(metadata);
+    }
+    
+    metadata.high_risk = high_risk;
+    storage::save_metadata(&app, &date, &metadata)?;
+    integrity::amend_segment(&app, &date, &metadata)?;
+    
+    log::info!("[RECORDING] Segment {} high_risk={}", segment_id, high_risk);
+    Ok(metadata)
+}
+
 // Set Gemini API key (user-provided from settings)
 #[tauri::command]
 pub async fn set_gemini_api_key(
@@ -622,3 +1130,30 @@ pub async fn delete_gemini_api_key(
     log::info!("Gemini API key deleted from secure storage");
     Ok(())
 }
+
+// Set API key for the OpenAI-compatible analyzer backend
+#[tauri::command]
+pub async fn set_openai_api_key(
+    app: AppHandle,
+    api_key: String,
+) -> Result<(), String> {
+    if api_key.trim().is_empty() {
+        return Err("API key cannot be empty".to_string());
+    }
+    
+    config::save_openai_api_key(&app, &api_key)
+}
+
+// Get whether an OpenAI-compatible API key is set (user-provided or env var)
+#[tauri::command]
+pub async fn get_openai_api_key_status(app: AppHandle) -> Result<bool, String> {
+    Ok(gemini::get_openai_api_key(Some(&app)).is_some())
+}
+
+// Delete the OpenAI-compatible API key
+#[tauri::command]
+pub async fn delete_openai_api_key(
+    app: AppHandle,
+) -> Result<(), String> {
+    config::delete_openai_api_key(&app)
+}
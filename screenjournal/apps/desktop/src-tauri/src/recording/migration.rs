@@ -0,0 +1,429 @@
+/**
+ * ============================================================================
+ * RECORDINGS MIGRATION MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Move existing recordings to a new recordings root in the background
+ *
+ * FLOW:
+ * 1. Config change switches the storage root; new segments go there at once
+ * 2. The previous root becomes the read fallback (storage::set_fallback_root)
+ * 3. Files are moved one by one (rename, or copy + delete across devices)
+ * 4. Progress is emitted as "recordings-migration-progress" events
+ * 5. When every file is moved the fallback is cleared and state removed
+ *
+ * RESUMING:
+ * ~/.screenjournal/recordings_migration.json records source and destination
+ * while a migration is in progress; it is resumed on next app launch.
+ * Files already present at the destination with the same size are skipped.
+ * A partial copy left by an interrupted run is discarded and copied again;
+ * leftover partial files are removed when the migration finishes.
+ *
+ * ============================================================================
+ */
+
+use crate::recording::storage;
+use crate::recording::types::RecordingsMigrationProgress;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use walkdir::WalkDir;
+
+/// Persisted migration state filename (in app_data_dir)
+const STATE_FILENAME: &str = "recordings_migration.json";
+
+/// Event emitted to the frontend with RecordingsMigrationProgress
+const PROGRESS_EVENT: &str = "recordings-migration-progress";
+
+/// Minimum time between progress events
+const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Suffix for files being copied across devices
+const PARTIAL_SUFFIX: &str = ".partial";
+
+// Whether a migration worker is running
+static MIGRATION_RUNNING: AtomicBool = AtomicBool::new(false);
+
+// Last reported progress (for status queries)
+static LAST_PROGRESS: Lazy<Mutex<Option<RecordingsMigrationProgress>>> =
+    Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MigrationState {
+    source: PathBuf,
+    destination: PathBuf,
+    started_at: String,
+}
+
+// =============================================================================
+// Public API
+// =============================================================================
+
+pub fn is_running() -> bool {
+    MIGRATION_RUNNING.load(Ordering::SeqCst)
+}
+
+// Last progress of the current or most recent migration in this session
+pub fn get_progress() -> Option<RecordingsMigrationProgress> {
+    LAST_PROGRESS.lock().unwrap().clone()
+}
+
+/**
+ * Check that a directory can be used as the recordings root
+ * Creates it if missing; roots must not be nested inside each other
+ */
+pub fn validate_destination(current_root: &Path, destination: &Path) -> Result<(), String> {
+    if !destination.is_absolute() {
+        return Err("Recordings directory must be an absolute path".to_string());
+    }
+
+    if destination != current_root
+        && (destination.starts_with(current_root) || current_root.starts_with(destination))
+    {
+        return Err("Recordings directory cannot be inside the current one (or contain it)".to_string());
+    }
+
+    std::fs::create_dir_all(destination)
+        .map_err(|e| format!("Cannot create recordings directory {:?}: {}", destination, e))?;
+
+    let probe = destination.join(".screenjournal_write_test");
+    std::fs::write(&probe, b"ok")
+        .map_err(|e| format!("Recordings directory {:?} is not writable: {}", destination, e))?;
+    let _ = std::fs::remove_file(&probe);
+
+    Ok(())
+}
+
+/**
+ * Start migrating recordings from `source` to `destination`
+ * The storage root must already point at `destination`
+ */
+pub fn start(app: &AppHandle, source: PathBuf, destination: PathBuf) -> Result<(), String> {
+    if source == destination || !source.exists() {
+        return Ok(());
+    }
+
+    if is_running() {
+        return Err("A recordings migration is already in progress".to_string());
+    }
+
+    let state = MigrationState {
+        source,
+        destination,
+        started_at: Utc::now().to_rfc3339(),
+    };
+    save_state(app, &state)?;
+
+    log::info!(
+        "[MIGRATION] Moving recordings from {:?} to {:?}",
+        state.source,
+        state.destination
+    );
+
+    spawn_worker(app, state);
+    Ok(())
+}
+
+/**
+ * Resume an interrupted migration (call once on startup, after init_config)
+ */
+pub fn resume(app: &AppHandle) {
+    let Some(state) = load_state(app) else {
+        return;
+    };
+
+    log::info!(
+        "[MIGRATION] Resuming recordings migration from {:?} to {:?} (started {})",
+        state.source,
+        state.destination,
+        state.started_at
+    );
+
+    spawn_worker(app, state);
+}
+
+// =============================================================================
+// Worker
+// =============================================================================
+
+fn spawn_worker(app: &AppHandle, state: MigrationState) {
+    MIGRATION_RUNNING.store(true, Ordering::SeqCst);
+    storage::set_fallback_root(Some(state.source.clone()));
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let app_clone = app.clone();
+        let result = tokio::task::spawn_blocking(move || run(&app_clone, &state)).await;
+
+        if let Err(e) = result {
+            log::error!("[MIGRATION] Worker panicked: {}", e);
+        }
+
+        MIGRATION_RUNNING.store(false, Ordering::SeqCst);
+    });
+}
+
+fn run(app: &AppHandle, state: &MigrationState) {
+    let files: Vec<(PathBuf, u64)> = WalkDir::new(&state.source)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| Some((e.path().to_path_buf(), e.metadata().ok()?.len())))
+        .collect();
+
+    let mut progress = RecordingsMigrationProgress {
+        source: state.source.to_string_lossy().to_string(),
+        destination: state.destination.to_string_lossy().to_string(),
+        files_total: files.len() as u64,
+        files_done: 0,
+        bytes_total: files.iter().map(|(_, size)| size).sum(),
+        bytes_done: 0,
+        files_failed: 0,
+        finished: false,
+    };
+    let mut last_emit = Instant::now();
+    emit_progress(app, &progress);
+
+    for (path, size) in files {
+        let Ok(relative) = path.strip_prefix(&state.source) else {
+            continue;
+        };
+
+        match move_file(&path, &state.destination.join(relative)) {
+            Ok(()) => progress.files_done += 1,
+            Err(e) => {
+                log::warn!("[MIGRATION] Failed to move {:?}: {}", path, e);
+                progress.files_failed += 1;
+            }
+        }
+        progress.bytes_done += size;
+
+        if last_emit.elapsed() >= PROGRESS_EMIT_INTERVAL {
+            emit_progress(app, &progress);
+            last_emit = Instant::now();
+        }
+    }
+
+    remove_partial_files(&state.destination);
+    remove_empty_dirs(&state.source);
+
+    if progress.files_failed == 0 {
+        storage::set_fallback_root(None);
+        let _ = std::fs::remove_file(state_path(app));
+        log::info!(
+            "[MIGRATION] Recordings migration complete ({} files, {} bytes)",
+            progress.files_done,
+            progress.bytes_done
+        );
+    } else {
+        // Keep state and fallback so reads still work; retried on next launch
+        log::warn!(
+            "[MIGRATION] Recordings migration finished with {} failed file(s)",
+            progress.files_failed
+        );
+    }
+
+    progress.finished = true;
+    emit_progress(app, &progress);
+}
+
+/**
+ * Move one file, preserving its modification time (retention uses mtime)
+ * Missing source files were deleted by cleanup meanwhile and count as moved
+ */
+fn move_file(source: &Path, destination: &Path) -> Result<(), String> {
+    let source_meta = match std::fs::metadata(source) {
+        Ok(meta) => meta,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    if let Ok(dest_meta) = std::fs::metadata(destination) {
+        if dest_meta.len() == source_meta.len() {
+            return std::fs::remove_file(source).map_err(|e| e.to_string());
+        }
+        return Err(format!("A different file already exists at {:?}", destination));
+    }
+
+    if let Some(parent) = destination.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    // Same filesystem: atomic and keeps mtime
+    if std::fs::rename(source, destination).is_ok() {
+        return Ok(());
+    }
+
+    copy_into_place(source, destination, &source_meta)?;
+    std::fs::remove_file(source).map_err(|e| format!("Copied but could not remove source: {}", e))
+}
+
+// Across devices: copy to a partial file, then rename into place
+fn copy_into_place(source: &Path, destination: &Path, source_meta: &std::fs::Metadata) -> Result<(), String> {
+    let partial = partial_path(destination);
+
+    // Leftover of an interrupted copy; its contents can't be trusted
+    match std::fs::remove_file(&partial) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            return Err(format!("Could not remove stale partial copy: {}", e));
+        }
+        _ => {}
+    }
+
+    std::fs::copy(source, &partial).map_err(|e| format!("Copy failed: {}", e))?;
+
+    if let Ok(modified) = source_meta.modified()
+        && let Ok(file) = std::fs::File::options().write(true).open(&partial)
+    {
+        let _ = file.set_modified(modified);
+    }
+
+    std::fs::rename(&partial, destination).map_err(|e| format!("Rename failed: {}", e))
+}
+
+fn partial_path(destination: &Path) -> PathBuf {
+    let mut partial = destination.as_os_str().to_owned();
+    partial.push(PARTIAL_SUFFIX);
+    PathBuf::from(partial)
+}
+
+// Remove partial copies whose source went away before the copy finished
+fn remove_partial_files(root: &Path) {
+    for entry in WalkDir::new(root)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.file_name().to_string_lossy().ends_with(PARTIAL_SUFFIX))
+    {
+        if let Err(e) = std::fs::remove_file(entry.path()) {
+            log::warn!("[MIGRATION] Failed to remove partial copy {:?}: {}", entry.path(), e);
+        }
+    }
+}
+
+// Remove now-empty date directories (and the root itself if empty)
+fn remove_empty_dirs(root: &Path) {
+    for entry in WalkDir::new(root)
+        .contents_first(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir())
+    {
+        // Fails harmlessly on non-empty directories
+        let _ = std::fs::remove_dir(entry.path());
+    }
+}
+
+fn emit_progress(app: &AppHandle, progress: &RecordingsMigrationProgress) {
+    *LAST_PROGRESS.lock().unwrap() = Some(progress.clone());
+    let _ = app.emit(PROGRESS_EVENT, progress);
+}
+
+// =============================================================================
+// State Persistence
+// =============================================================================
+
+fn state_path(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .expect("app_data_dir available")
+        .join(STATE_FILENAME)
+}
+
+fn save_state(app: &AppHandle, state: &MigrationState) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("Failed to serialize migration state: {}", e))?;
+
+    std::fs::write(state_path(app), contents)
+        .map_err(|e| format!("Failed to write migration state: {}", e))
+}
+
+fn load_state(app: &AppHandle) -> Option<MigrationState> {
+    let contents = std::fs::read_to_string(state_path(app)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("sj_migration_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_move_file_skips_identical_destination() {
+        let dir = temp_dir("identical");
+        let source = dir.join("src/2025-01-15/segment_1.json");
+        let destination = dir.join("dst/2025-01-15/segment_1.json");
+        std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+        std::fs::create_dir_all(destination.parent().unwrap()).unwrap();
+        std::fs::write(&source, b"{}").unwrap();
+        std::fs::write(&destination, b"{}").unwrap();
+
+        move_file(&source, &destination).unwrap();
+
+        assert!(!source.exists());
+        assert!(destination.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_move_file_conflict_keeps_source() {
+        let dir = temp_dir("conflict");
+        let source = dir.join("src/segment_1.json");
+        let destination = dir.join("dst/segment_1.json");
+        std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+        std::fs::create_dir_all(destination.parent().unwrap()).unwrap();
+        std::fs::write(&source, b"{\"a\":1}").unwrap();
+        std::fs::write(&destination, b"{}").unwrap();
+
+        assert!(move_file(&source, &destination).is_err());
+        assert!(source.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_copy_replaces_stale_partial() {
+        let dir = temp_dir("partial");
+        let source = dir.join("src/segment_1.json");
+        let destination = dir.join("dst/segment_1.json");
+        std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+        std::fs::create_dir_all(destination.parent().unwrap()).unwrap();
+        std::fs::write(&source, b"{\"a\":1}").unwrap();
+        std::fs::write(partial_path(&destination), b"{\"a\"").unwrap();
+        std::fs::write(partial_path(&dir.join("dst/segment_2.json")), b"{").unwrap();
+
+        copy_into_place(&source, &destination, &std::fs::metadata(&source).unwrap()).unwrap();
+        assert_eq!(std::fs::read(&destination).unwrap(), b"{\"a\":1}");
+        assert!(!partial_path(&destination).exists());
+
+        remove_partial_files(&dir.join("dst"));
+        assert!(!partial_path(&dir.join("dst/segment_2.json")).exists());
+        assert!(destination.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_validate_destination_rejects_nested() {
+        let dir = temp_dir("nested");
+        assert!(validate_destination(&dir, &dir.join("inner")).is_err());
+        assert!(validate_destination(&dir.join("inner"), &dir).is_err());
+        assert!(validate_destination(&dir.join("a"), &dir.join("b")).is_ok());
+        assert!(validate_destination(&dir, Path::new("relative/path")).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
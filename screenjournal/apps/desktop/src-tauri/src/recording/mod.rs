@@ -7,9 +7,18 @@
  * 
  * SUBMODULES:
  * - capture: Per-display frame capture and MP4 encoding via bundled FFmpeg
+ * - classify: Blank/static/locked screen detection for finalized segments
  * - config: Configuration persistence and management
+ * - coverage: Recording coverage reports and gap detection
+ * - import: Import external MP4/MKV recordings as segments
+ * - input: Per-segment keyboard/mouse activity summaries and overlay export
+ * - integrity: Signed hash chain over segment files (tamper evidence)
  * - manager: Lifecycle management and Tauri commands
+ * - migration: Background move of recordings to a new recordings root
  * - storage: Video file storage and cleanup
+ * - timelapse: Daily per-display timelapse generation
+ * - privacy: App/title exclusion and text redaction rules
+ * - ocr: Local OCR text index over recorded frames (bundled tesseract)
  * - types: Data structures and models
  * - gemini: AI-powered video analysis using Google Gemini
  * 
@@ -19,7 +28,11 @@
  * 2. Each thread pipes to bundled FFmpeg process for H.264 encoding
  * 3. Segment rotation: Finalizes all displays, starts new segment (60s default)
  * 4. Combined metadata sidecar: JSON file with per-display info
- * 5. Gemini analysis: After segment completion, video sent to AI for timeline extraction
+ * 5. Gemini analysis: After segment completion, each display is classified and
+ *    non-blank, unlocked videos are sent to AI for timeline extraction
+ * 6. Timelapse: Once a day is over, its segments are condensed into one video per display
+ * 7. OCR (optional): Sampled frames are OCR'd into a searchable local text index
+ * 8. Input activity: ActivityWatch input events are summarized per segment
  * 
  * OUTPUT FORMAT:
  * - segment_*_d0.mp4, segment_*_d1.mp4, ...: One H.264 MP4 per display
@@ -35,8 +48,17 @@
  */
 
 pub mod capture;
+pub mod classify;
 pub mod config;
+pub mod coverage;
 pub mod gemini;
+pub mod import;
+pub mod input;
+pub mod integrity;
 pub mod manager;
+pub mod migration;
+pub mod ocr;
+pub mod privacy;
 pub mod storage;
+pub mod timelapse;
 pub mod types;
@@ -0,0 +1,663 @@
+/**
+ * ============================================================================
+ * RECORDING OCR MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Local OCR text index over recorded frames
+ *
+ * FLOW:
+ * 1. Segment finalized -> index_segment() queued in background
+ * 2. Bundled FFmpeg samples one frame every N seconds per display
+ * 3. Bundled tesseract recognizes text in each frame
+ * 4. Privacy rules applied: frames of excluded apps/windows are skipped,
+ *    redaction patterns are applied to the recognized text. With exclusions
+ *    configured, frames without ActivityWatch window data are skipped too
+ * 5. Text stored with wall-clock timestamp in a per-day inverted index
+ *
+ * STORAGE:
+ * ~/.screenjournal/ocr_index/{date}.json (outside recordings/, survives retention)
+ *
+ * REQUIREMENTS:
+ * - Tesseract binary bundled in resources/tesseract/{platform}/{arch}/
+ * - Language data in resources/tesseract/tessdata/ (npm run setup-tesseract)
+ * - init_tesseract_path() must be called on app startup
+ *
+ * ============================================================================
+ */
+
+use crate::activitywatch::types::EventInfo;
+use crate::recording::capture;
+use crate::recording::privacy::{self, PrivacyRules};
+use crate::recording::storage;
+use crate::recording::types::{OcrSearchHit, RecordingConfig, RecordingMetadata};
+use chrono::{DateTime, NaiveDate, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+/// Minimum token length kept in the index
+const MIN_TOKEN_LEN: usize = 2;
+
+/// Characters of context shown on each side of a match in search snippets
+const SNIPPET_CONTEXT_CHARS: usize = 60;
+
+// =============================================================================
+// Global State
+// =============================================================================
+
+/// Global tesseract binary path - initialized on app startup
+static TESSERACT_PATH: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
+
+/// Serializes OCR work so only one segment is processed at a time (CPU heavy)
+static OCR_WORK_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Serializes read-modify-write of index files
+static INDEX_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+// =============================================================================
+// Index Types
+// =============================================================================
+
+/// Text recognized in a single sampled frame
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct OcrDocument {
+    segment_id: String,
+    display_index: u32,
+    timestamp: String,
+    offset_seconds: f64,
+    text: String,
+}
+
+/// Per-day inverted index: term -> document positions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct OcrDayIndex {
+    documents: Vec<OcrDocument>,
+    terms: BTreeMap<String, Vec<u32>>,
+}
+
+impl OcrDayIndex {
+    fn add_document(&mut self, document: OcrDocument) {
+        let position = self.documents.len() as u32;
+        let unique_terms: BTreeSet<String> = tokenize(&document.text).into_iter().collect();
+
+        for term in unique_terms {
+            self.terms.entry(term).or_default().push(position);
+        }
+
+        self.documents.push(document);
+    }
+
+    // Remove every document belonging to a segment/display (for re-indexing)
+    fn remove_segment(&mut self, segment_id: &str, display_index: u32) {
+        let keep: Vec<OcrDocument> = self
+            .documents
+            .drain(..)
+            .filter(|d| !(d.segment_id == segment_id && d.display_index == display_index))
+            .collect();
+
+        self.terms.clear();
+        for document in keep {
+            self.add_document(document);
+        }
+    }
+
+    // Find documents containing every query term, with occurrence score
+    fn search(&self, query_terms: &[String]) -> Vec<(&OcrDocument, u32)> {
+        let mut candidates: Option<BTreeSet<u32>> = None;
+
+        for term in query_terms {
+            let postings: BTreeSet<u32> = self
+                .terms
+                .get(term)
+                .map(|p| p.iter().copied().collect())
+                .unwrap_or_default();
+
+            candidates = Some(match candidates {
+                None => postings,
+                Some(current) => current.intersection(&postings).copied().collect(),
+            });
+        }
+
+        candidates
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|pos| self.documents.get(pos as usize))
+            .map(|doc| {
+                let tokens = tokenize(&doc.text);
+                let score = tokens.iter().filter(|t| query_terms.contains(t)).count() as u32;
+                (doc, score)
+            })
+            .collect()
+    }
+}
+
+// =============================================================================
+// Tesseract Path Management
+// =============================================================================
+
+// Get platform-specific tesseract binary name and subdirectory
+fn tesseract_platform_info() -> (&'static str, &'static str) {
+    #[cfg(target_os = "windows")]
+    {
+        ("windows/x86_64", "tesseract.exe")
+    }
+    #[cfg(target_os = "macos")]
+    {
+        #[cfg(target_arch = "aarch64")]
+        {
+            ("darwin/aarch64", "tesseract")
+        }
+        #[cfg(target_arch = "x86_64")]
+        {
+            ("darwin/x86_64", "tesseract")
+        }
+    }
+    #[cfg(target_os = "linux")]
+    {
+        ("linux/x86_64", "tesseract")
+    }
+}
+
+// Resolve the bundled tesseract binary path (same search order as FFmpeg)
+fn resolve_tesseract_path(app: &AppHandle) -> PathBuf {
+    let (platform_subdir, bin_name) = tesseract_platform_info();
+
+    if cfg!(debug_assertions) {
+        let candidates = [
+            PathBuf::from("src-tauri").join("resources").join("tesseract"),
+            PathBuf::from("resources").join("tesseract"),
+        ];
+        for root in candidates {
+            let candidate = root.join(platform_subdir).join(bin_name);
+            if candidate.exists() {
+                log::info!("Tesseract found at dev path: {:?}", candidate);
+                return candidate;
+            }
+        }
+    }
+
+    let prod_path = app
+        .path()
+        .resource_dir()
+        .expect("resource_dir available")
+        .join("tesseract")
+        .join(platform_subdir)
+        .join(bin_name);
+
+    log::info!("Tesseract path (prod): {:?}", prod_path);
+    prod_path
+}
+
+// Initialize tesseract path on app startup
+pub fn init_tesseract_path(app: &AppHandle) {
+    let path = resolve_tesseract_path(app);
+    *TESSERACT_PATH.lock().unwrap() = Some(path);
+}
+
+// Get the stored tesseract binary path
+fn get_tesseract_path() -> Result<PathBuf, String> {
+    let path = TESSERACT_PATH
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| "Tesseract path not initialized. Call init_tesseract_path() on app startup.".to_string())?;
+
+    if !path.exists() {
+        return Err(format!("Tesseract binary not found at {:?}", path));
+    }
+
+    Ok(path)
+}
+
+// tessdata lives at resources/tesseract/tessdata (two levels above the binary's platform dir)
+fn tessdata_dir(tesseract_path: &Path) -> Option<PathBuf> {
+    let dir = tesseract_path.parent()?.parent()?.parent()?.join("tessdata");
+    if dir.exists() { Some(dir) } else { None }
+}
+
+// =============================================================================
+// Indexing
+// =============================================================================
+
+/**
+ * OCR all displays of a finalized segment and add the text to the day's index
+ * Runs in the background; errors are logged, never propagated to recording
+ */
+pub async fn index_segment(app: AppHandle, date: NaiveDate, metadata: RecordingMetadata, config: RecordingConfig) {
+    let rules = match PrivacyRules::from_config(&config.privacy) {
+        Ok(rules) => rules,
+        Err(e) => {
+            log::error!("[OCR] Skipping segment {}: {}", metadata.id, e);
+            return;
+        }
+    };
+
+    let segment_start = match DateTime::parse_from_rfc3339(&metadata.start_time) {
+        Ok(t) => t.with_timezone(&Utc),
+        Err(e) => {
+            log::error!("[OCR] Invalid start time for segment {}: {}", metadata.id, e);
+            return;
+        }
+    };
+
+    // Window events are needed to honor app/title exclusions; without them
+    // excluded windows can't be told apart, so nothing is indexed
+    let window_events = match fetch_window_events(&metadata).await {
+        Some(events) => events,
+        None if rules.has_exclusions() => {
+            log::warn!("[OCR] Skipping segment {}: no window data to apply exclusions", metadata.id);
+            return;
+        }
+        None => Vec::new(),
+    };
+
+    let result = tokio::task::spawn_blocking(move || {
+        let _work = OCR_WORK_LOCK.lock().unwrap();
+        let mut indexed = 0;
+
+        for display in &metadata.displays {
+            let video_path = storage::find_video_path(&app, &date, &metadata.id, display.display_index);
+            match ocr_video(&video_path, segment_start, &window_events, &rules, &config) {
+                Ok(frames) => {
+                    indexed += frames.len();
+                    let documents = frames
+                        .into_iter()
+                        .map(|(timestamp, offset_seconds, text)| OcrDocument {
+                            segment_id: metadata.id.clone(),
+                            display_index: display.display_index,
+                            timestamp: timestamp.to_rfc3339(),
+                            offset_seconds,
+                            text,
+                        })
+                        .collect();
+                    if let Err(e) = store_documents(&app, &date, &metadata.id, display.display_index, documents) {
+                        log::error!("[OCR] Failed to store index for {}: {}", metadata.id, e);
+                    }
+                }
+                Err(e) => {
+                    log::warn!("[OCR] Segment {} display {}: {}", metadata.id, display.display_index, e);
+                }
+            }
+        }
+
+        log::info!("[OCR] Indexed {} frame(s) for segment {}", indexed, metadata.id);
+    })
+    .await;
+
+    if let Err(e) = result {
+        log::error!("[OCR] Indexing task panicked: {}", e);
+    }
+}
+
+// Fetch ActivityWatch window events covering the segment (None if AW is not running or unreachable)
+async fn fetch_window_events(metadata: &RecordingMetadata) -> Option<Vec<EventInfo>> {
+    let base_url = crate::activitywatch::manager::current_base_url()?;
+
+    let (start, end) = match (
+        DateTime::parse_from_rfc3339(&metadata.start_time),
+        DateTime::parse_from_rfc3339(&metadata.end_time),
+    ) {
+        (Ok(s), Ok(e)) => (s.with_timezone(&Utc), e.with_timezone(&Utc)),
+        _ => return None,
+    };
+
+    match crate::activitywatch::client::fetch_events_by_range(&base_url, start, end).await {
+        Ok(events) => Some(events.window_events),
+        Err(e) => {
+            log::warn!("[OCR] Could not fetch window events: {}", e);
+            None
+        }
+    }
+}
+
+/**
+ * Sample frames from one display video and recognize their text
+ *
+ * # Returns
+ * * `Ok(Vec<(timestamp, offset_seconds, text)>)` - Redacted text per kept frame
+ */
+fn ocr_video(
+    video_path: &Path,
+    segment_start: DateTime<Utc>,
+    window_events: &[EventInfo],
+    rules: &PrivacyRules,
+    config: &RecordingConfig,
+) -> Result<Vec<(DateTime<Utc>, f64, String)>, String> {
+    if !video_path.exists() {
+        return Err(format!("Video file not found: {:?}", video_path));
+    }
+
+    let interval = config.ocr.sample_interval_seconds.max(1);
+    let frames_dir = std::env::temp_dir().join(format!(
+        "screenjournal_ocr_{}",
+        video_path.file_stem().and_then(|s| s.to_str()).unwrap_or("segment")
+    ));
+    std::fs::create_dir_all(&frames_dir).map_err(|e| format!("Failed to create frame dir: {}", e))?;
+
+    let result = (|| -> Result<Vec<(DateTime<Utc>, f64, String)>, String> {
+        let frames = extract_frames(video_path, &frames_dir, interval)?;
+        let mut results = Vec::new();
+        let mut last_text = String::new();
+
+        for (frame_path, offset_seconds) in frames {
+            let timestamp = segment_start + chrono::Duration::milliseconds((offset_seconds * 1000.0) as i64);
+
+            match privacy::active_window_at(window_events, timestamp) {
+                Some((app, title)) if rules.is_excluded(app, title) => {
+                    log::debug!("[OCR] Skipping frame at {:.0}s (excluded window)", offset_seconds);
+                    continue;
+                }
+                // Fail closed: an unknown window may be an excluded one
+                None if rules.has_exclusions() => {
+                    log::debug!("[OCR] Skipping frame at {:.0}s (no window data)", offset_seconds);
+                    continue;
+                }
+                _ => {}
+            }
+
+            // One unreadable frame shouldn't discard the rest of the display
+            let text = match run_tesseract(&frame_path, &config.ocr.language) {
+                Ok(text) => rules.redact(text.trim()),
+                Err(e) => {
+                    log::warn!("[OCR] Skipping frame at {:.0}s: {}", offset_seconds, e);
+                    continue;
+                }
+            };
+
+            // Screens rarely change between samples; skip empty and repeated text
+            if text.is_empty() || text == last_text {
+                continue;
+            }
+
+            last_text = text.clone();
+            results.push((timestamp, offset_seconds, text));
+        }
+
+        Ok(results)
+    })();
+
+    let _ = std::fs::remove_dir_all(&frames_dir);
+    result
+}
+
+// Extract one PNG every `interval` seconds; returns (path, offset_seconds) pairs
+fn extract_frames(video_path: &Path, frames_dir: &Path, interval: u32) -> Result<Vec<(PathBuf, f64)>, String> {
+    let ffmpeg_path = capture::get_ffmpeg_path()?;
+
+    let status = Command::new(&ffmpeg_path)
+        .args(["-y", "-i"])
+        .arg(video_path)
+        .args(["-vf", &format!("fps=1/{}", interval)])
+        .arg(frames_dir.join("frame_%05d.png"))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| format!("Failed to run FFmpeg at {:?}: {}", ffmpeg_path, e))?;
+
+    if !status.success() {
+        return Err(format!("FFmpeg frame extraction failed: {:?}", status.code()));
+    }
+
+    let mut frames: Vec<PathBuf> = std::fs::read_dir(frames_dir)
+        .map_err(|e| format!("Failed to read frame dir: {}", e))?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().map(|ext| ext == "png").unwrap_or(false))
+        .collect();
+    frames.sort();
+
+    // frame_00001.png is the frame at 0s, frame_00002.png at `interval` seconds, ...
+    Ok(frames
+        .into_iter()
+        .enumerate()
+        .map(|(i, path)| (path, (i as u32 * interval) as f64))
+        .collect())
+}
+
+// Run tesseract on a single image and return the recognized text
+fn run_tesseract(image_path: &Path, language: &str) -> Result<String, String> {
+    let tesseract_path = get_tesseract_path()?;
+
+    let mut command = Command::new(&tesseract_path);
+    command
+        .arg(image_path)
+        .arg("stdout")
+        .args(["-l", language])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null());
+
+    if let Some(tessdata) = tessdata_dir(&tesseract_path) {
+        command.arg("--tessdata-dir").arg(tessdata);
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| format!("Failed to run tesseract at {:?}: {}", tesseract_path, e))?;
+
+    if !output.status.success() {
+        return Err(format!("Tesseract exited with error: {:?}", output.status.code()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+// =============================================================================
+// Index Persistence
+// =============================================================================
+
+fn index_dir(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .expect("app_data_dir available")
+        .join("ocr_index")
+}
+
+fn index_path(app: &AppHandle, date: &NaiveDate) -> PathBuf {
+    index_dir(app).join(format!("{}.json", date.format("%Y-%m-%d")))
+}
+
+fn load_day_index(path: &Path) -> OcrDayIndex {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+// Replace a segment/display's documents in the day's index
+fn store_documents(
+    app: &AppHandle,
+    date: &NaiveDate,
+    segment_id: &str,
+    display_index: u32,
+    documents: Vec<OcrDocument>,
+) -> Result<(), String> {
+    let _guard = INDEX_LOCK.lock().unwrap();
+
+    let path = index_path(app, date);
+    std::fs::create_dir_all(index_dir(app)).map_err(|e| format!("Failed to create OCR index dir: {}", e))?;
+
+    let mut index = load_day_index(&path);
+    index.remove_segment(segment_id, display_index);
+    for document in documents {
+        index.add_document(document);
+    }
+
+    let json = serde_json::to_string(&index).map_err(|e| format!("Failed to serialize OCR index: {}", e))?;
+
+    // Write to a temp file first so a crash never leaves a truncated index
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, json).map_err(|e| format!("Failed to write OCR index: {}", e))?;
+    std::fs::rename(&tmp_path, &path).map_err(|e| format!("Failed to replace OCR index: {}", e))
+}
+
+// =============================================================================
+// Search
+// =============================================================================
+
+/**
+ * Search recognized text
+ *
+ * # Arguments
+ * * `query` - Words to search for (all must appear in a frame)
+ * * `start` / `end` - Inclusive day range; None searches every indexed day
+ * * `limit` - Maximum number of hits returned
+ *
+ * # Returns
+ * Hits sorted by score (best first), then by time (newest first)
+ */
+pub fn search(
+    app: &AppHandle,
+    query: &str,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    limit: usize,
+) -> Vec<OcrSearchHit> {
+    let query_terms = tokenize(query);
+    if query_terms.is_empty() {
+        return Vec::new();
+    }
+
+    let _guard = INDEX_LOCK.lock().unwrap();
+
+    let entries = match std::fs::read_dir(index_dir(app)) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut hits = Vec::new();
+
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        let date = match path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+        {
+            Some(date) => date,
+            None => continue,
+        };
+
+        if start.map(|s| date < s).unwrap_or(false) || end.map(|e| date > e).unwrap_or(false) {
+            continue;
+        }
+
+        let index = load_day_index(&path);
+        for (doc, score) in index.search(&query_terms) {
+            hits.push(OcrSearchHit {
+                segment_id: doc.segment_id.clone(),
+                display_index: doc.display_index,
+                timestamp: doc.timestamp.clone(),
+                offset_seconds: doc.offset_seconds,
+                snippet: build_snippet(&doc.text, &query_terms[0]),
+                score,
+            });
+        }
+    }
+
+    hits.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| b.timestamp.cmp(&a.timestamp)));
+    hits.truncate(limit);
+    hits
+}
+
+// Split text into lowercase alphanumeric terms
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.chars().count() >= MIN_TOKEN_LEN)
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+// Excerpt of the text around the first occurrence of `term`
+fn build_snippet(text: &str, term: &str) -> String {
+    let flattened = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let chars: Vec<char> = flattened.chars().collect();
+    let lower: Vec<char> = flattened.to_lowercase().chars().collect();
+    let needle: Vec<char> = term.chars().collect();
+
+    let position = if lower.len() == chars.len() {
+        lower.windows(needle.len().max(1)).position(|w| w == needle.as_slice())
+    } else {
+        None
+    };
+
+    let center = position.unwrap_or(0);
+    let from = center.saturating_sub(SNIPPET_CONTEXT_CHARS);
+    let to = (center + needle.len() + SNIPPET_CONTEXT_CHARS).min(chars.len());
+
+    let mut snippet: String = chars[from..to].iter().collect();
+    if from > 0 {
+        snippet.insert_str(0, "…");
+    }
+    if to < chars.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(segment_id: &str, text: &str) -> OcrDocument {
+        OcrDocument {
+            segment_id: segment_id.to_string(),
+            display_index: 0,
+            timestamp: "2025-01-15T10:00:00Z".to_string(),
+            offset_seconds: 0.0,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("Error: Connection refused (os error 111)"),
+            vec!["error", "connection", "refused", "os", "error", "111"]
+        );
+    }
+
+    #[test]
+    fn test_index_search_requires_all_terms() {
+        let mut index = OcrDayIndex::default();
+        index.add_document(document("a", "TypeError: undefined is not a function"));
+        index.add_document(document("b", "Build succeeded"));
+        index.add_document(document("c", "TypeError: cannot read property"));
+
+        let hits = index.search(&tokenize("typeerror function"));
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].0.segment_id, "a");
+
+        let hits = index.search(&tokenize("typeerror"));
+        assert_eq!(hits.len(), 2);
+    }
+
+    #[test]
+    fn test_remove_segment_reindexes() {
+        let mut index = OcrDayIndex::default();
+        index.add_document(document("a", "first text"));
+        index.add_document(document("b", "second text"));
+
+        index.remove_segment("a", 0);
+
+        assert_eq!(index.documents.len(), 1);
+        assert!(index.search(&tokenize("first")).is_empty());
+        assert_eq!(index.search(&tokenize("second")).len(), 1);
+    }
+
+    #[test]
+    fn test_build_snippet() {
+        let text = format!("{} needle {}", "x".repeat(100), "y".repeat(100));
+        let snippet = build_snippet(&text, "needle");
+        assert!(snippet.contains("needle"));
+        assert!(snippet.starts_with('…'));
+        assert!(snippet.ends_with('…'));
+    }
+}
@@ -0,0 +1,180 @@
+/**
+ * ============================================================================
+ * RECORDING PRIVACY MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Apply exclusion and redaction rules to data derived from recordings
+ *
+ * RULES (from PrivacyConfig):
+ * - excluded_apps: Never extract content while these apps are active
+ * - excluded_title_patterns: Never extract content from matching window titles
+ * - redact_patterns: Replace matching text with [REDACTED]
+ *
+ * The active window at a given moment comes from ActivityWatch window events.
+ *
+ * ============================================================================
+ */
+
+use crate::activitywatch::types::EventInfo;
+use crate::recording::types::PrivacyConfig;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+
+/// Replacement text for redacted content
+pub const REDACTED: &str = "[REDACTED]";
+
+/**
+ * Compiled privacy rules
+ * Build once per batch of work instead of recompiling regexes per frame
+ */
+#[derive(Debug, Clone)]
+pub struct PrivacyRules {
+    excluded_apps: Vec<String>,
+    excluded_titles: Vec<Regex>,
+    redactions: Vec<Regex>,
+}
+
+impl PrivacyRules {
+    /**
+     * Compile rules from config
+     * Returns an error naming the first invalid pattern
+     */
+    pub fn from_config(config: &PrivacyConfig) -> Result<Self, String> {
+        let compile = |patterns: &[String]| -> Result<Vec<Regex>, String> {
+            patterns
+                .iter()
+                .filter(|p| !p.trim().is_empty())
+                .map(|p| Regex::new(p).map_err(|e| format!("Invalid privacy pattern '{}': {}", p, e)))
+                .collect()
+        };
+
+        Ok(Self {
+            excluded_apps: config
+                .excluded_apps
+                .iter()
+                .map(|a| a.trim().to_lowercase())
+                .filter(|a| !a.is_empty())
+                .collect(),
+            excluded_titles: compile(config.excluded_title_patterns.as_slice())?,
+            redactions: compile(config.redact_patterns.as_slice())?,
+        })
+    }
+
+    /**
+     * Check whether content from this app/window must be skipped
+     */
+    pub fn is_excluded(&self, app: &str, title: &str) -> bool {
+        let app = app.to_lowercase();
+        self.excluded_apps.iter().any(|a| *a == app)
+            || self.excluded_titles.iter().any(|re| re.is_match(title))
+    }
+
+    /**
+     * Whether any app or title is excluded (content then needs window data)
+     */
+    pub fn has_exclusions(&self) -> bool {
+        !self.excluded_apps.is_empty() || !self.excluded_titles.is_empty()
+    }
+
+    /**
+     * Replace every match of a redaction pattern with [REDACTED]
+     */
+    pub fn redact(&self, text: &str) -> String {
+        self.redactions
+            .iter()
+            .fold(text.to_string(), |acc, re| re.replace_all(&acc, REDACTED).into_owned())
+    }
+}
+
+/**
+ * Find the active window (app, title) at a given moment
+ *
+ * # Arguments
+ * * `window_events` - ActivityWatch window events, sorted chronologically
+ * * `at` - Wall-clock moment to look up
+ *
+ * # Returns
+ * * `Some((app, title))` if an event covers that moment
+ * * `None` if no window event covers it
+ */
+pub fn active_window_at<'a>(window_events: &'a [EventInfo], at: DateTime<Utc>) -> Option<(&'a str, &'a str)> {
+    window_events.iter().rev().find_map(|event| {
+        let start = DateTime::parse_from_rfc3339(&event.timestamp).ok()?.with_timezone(&Utc);
+        let end = start + chrono::Duration::milliseconds((event.duration * 1000.0) as i64);
+
+        if at >= start && at <= end {
+            let app = event.data.get("app").and_then(|v| v.as_str()).unwrap_or("");
+            let title = event.data.get("title").and_then(|v| v.as_str()).unwrap_or("");
+            Some((app, title))
+        } else {
+            None
+        }
+    })
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> PrivacyRules {
+        PrivacyRules::from_config(&PrivacyConfig {
+            excluded_apps: vec!["1Password".to_string()],
+            excluded_title_patterns: vec!["(?i)private browsing".to_string()],
+            redact_patterns: vec![r"\b\d{4}-\d{4}-\d{4}-\d{4}\b".to_string()],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_is_excluded() {
+        let rules = rules();
+        assert!(rules.is_excluded("1password", "Vault"));
+        assert!(rules.is_excluded("Firefox", "Mozilla Firefox Private Browsing"));
+        assert!(!rules.is_excluded("Code", "main.rs"));
+        assert!(rules.has_exclusions());
+
+        let redact_only = PrivacyRules::from_config(&PrivacyConfig {
+            redact_patterns: vec!["secret".to_string()],
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(!redact_only.has_exclusions());
+    }
+
+    #[test]
+    fn test_redact() {
+        let rules = rules();
+        assert_eq!(
+            rules.redact("card 1234-5678-9012-3456 declined"),
+            "card [REDACTED] declined"
+        );
+    }
+
+    #[test]
+    fn test_invalid_pattern_rejected() {
+        let config = PrivacyConfig {
+            redact_patterns: vec!["(unclosed".to_string()],
+            ..Default::default()
+        };
+        assert!(PrivacyRules::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_active_window_at() {
+        let events = vec![EventInfo {
+            id: None,
+            timestamp: "2025-01-15T10:00:00Z".to_string(),
+            duration: 60.0,
+            data: serde_json::json!({"app": "Slack", "title": "#general"}),
+        }];
+        let inside = DateTime::parse_from_rfc3339("2025-01-15T10:00:30Z").unwrap().with_timezone(&Utc);
+        let outside = DateTime::parse_from_rfc3339("2025-01-15T10:02:00Z").unwrap().with_timezone(&Utc);
+
+        assert_eq!(active_window_at(&events, inside), Some(("Slack", "#general")));
+        assert_eq!(active_window_at(&events, outside), None);
+    }
+}
@@ -9,35 +9,139 @@
  * - Generate paths for recording segments
  * - Calculate total storage usage
  * - Cleanup old recordings by age
- * - Cleanup by storage quota
+ * - Cleanup by storage quota (deletions are recorded in the integrity chain)
+ * - List segments recorded on a given day
+ * - Seek: map a wall-clock moment to segment file and video offset
+ * - Configurable recordings root, with reads falling back to the previous
+ *   root while a migration is in progress (see recording/migration.rs)
  * 
  * FILE STRUCTURE:
- * ~/.screenjournal/recordings/
+ * ~/.screenjournal/recordings/           # Default root (RecordingConfig.recordings_dir overrides)
  * ├── 2025-01-15/
  * │   ├── segment_1736956800_abc123.mp4   # H.264 encoded video
  * │   ├── segment_1736956800_abc123.json  # Metadata sidecar
+ * │   ├── segment_1736956800_abc123_d0.frames  # Per-frame capture times
+ * │   ├── segment_1736956800_abc123.input      # Input activity summary
+ * │   ├── segment_1736956800_abc123_d0.timeline     # Timeline analysis (gemini/results.rs)
+ * │   ├── segment_1736956800_abc123_d0.v2.timeline  # Reanalysis, version 2
  * │   └── ...
  * └── 2025-01-16/
  *     └── ...
  * 
+ * ~/.screenjournal/timelapses/           # Outside recordings/ so retention never touches it
+ * └── 2025-01-15/
+ *     ├── timelapse_d0.mp4
+ *     └── manifest.json
+ * 
  * ============================================================================
  */
 
-use crate::recording::types::{RecordingConfig, RecordingMetadata};
-use chrono::{NaiveDate, Utc};
-use std::path::PathBuf;
+use crate::recording::integrity;
+use crate::recording::types::{
+    DisplayRecording, FrameTimestamps, InputActivitySummary, RecordingConfig, RecordingMetadata,
+    SeekResult,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use once_cell::sync::Lazy;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use tauri::{AppHandle, Manager};
 use walkdir::WalkDir;
 
-// Get the base recordings directory
-pub fn get_recordings_dir(app: &AppHandle) -> PathBuf {
+// Custom recordings root (None = default under app_data_dir)
+static RECORDINGS_ROOT: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
+
+// Previous recordings root still being migrated from (read fallback)
+static FALLBACK_ROOT: Lazy<Mutex<Option<PathBuf>>> = Lazy::new(|| Mutex::new(None));
+
+// Set the recordings root from config (None = default location)
+pub fn set_recordings_root(root: Option<PathBuf>) {
+    *RECORDINGS_ROOT.lock().unwrap() = root;
+}
+
+// Set or clear the root that reads fall back to during a migration
+pub fn set_fallback_root(root: Option<PathBuf>) {
+    *FALLBACK_ROOT.lock().unwrap() = root;
+}
+
+// Get the default recordings directory under app_data_dir
+pub fn default_recordings_dir(app: &AppHandle) -> PathBuf {
     app.path()
         .app_data_dir()
         .expect("app_data_dir available")
         .join("recordings")
 }
 
-// Get the path for an MP4 recording segment file for a specific display
+// Get the base recordings directory (new segments are always written here)
+pub fn get_recordings_dir(app: &AppHandle) -> PathBuf {
+    RECORDINGS_ROOT
+        .lock()
+        .unwrap()
+        .clone()
+        .unwrap_or_else(|| default_recordings_dir(app))
+}
+
+// All roots that may hold recordings: current root first, then the migration source
+fn get_recording_roots(app: &AppHandle) -> Vec<PathBuf> {
+    let mut roots = vec![get_recordings_dir(app)];
+    if let Some(fallback) = FALLBACK_ROOT.lock().unwrap().clone() {
+        if !roots.contains(&fallback) {
+            roots.push(fallback);
+        }
+    }
+    roots.retain(|r| r.exists());
+    roots
+}
+
+// Walk every file under every recordings root
+fn walk_recording_files(app: &AppHandle) -> impl Iterator<Item = walkdir::DirEntry> {
+    get_recording_roots(app).into_iter().flat_map(|root| {
+        WalkDir::new(root)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+    })
+}
+
+// List every file under every recordings root
+pub fn list_recording_files(app: &AppHandle) -> Vec<PathBuf> {
+    walk_recording_files(app).map(|e| e.path().to_path_buf()).collect()
+}
+
+/**
+ * Locate an existing recording file by its date directory and filename
+ * Paths stored before a migration (e.g. queued Gemini jobs) keep working
+ * after the files move to the new root
+ */
+pub fn resolve_recording_path(app: &AppHandle, path: &Path) -> PathBuf {
+    if path.exists() {
+        return path.to_path_buf();
+    }
+    
+    let (Some(file_name), Some(date_dir)) = (
+        path.file_name(),
+        path.parent().and_then(|p| p.file_name()),
+    ) else {
+        return path.to_path_buf();
+    };
+    
+    get_recording_roots(app)
+        .into_iter()
+        .map(|root| root.join(date_dir).join(file_name))
+        .find(|candidate| candidate.exists())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+// Get the base timelapses directory
+pub fn get_timelapses_dir(app: &AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .expect("app_data_dir available")
+        .join("timelapses")
+}
+
+// Get the path for an MP4 recording segment file for a specific display (current root)
 pub fn get_video_path(app: &AppHandle, date: &NaiveDate, segment_id: &str, display_index: u32) -> PathBuf {
     let base_dir = get_recordings_dir(app);
     let date_dir = base_dir.join(date.format("%Y-%m-%d").to_string());
@@ -64,7 +168,12 @@ pub fn ensure_recording_dir(app: &AppHandle, date: &NaiveDate) -> Result<PathBuf
 
 // Generate a unique segment ID
 pub fn generate_segment_id() -> String {
-    let timestamp = Utc::now().timestamp();
+    generate_segment_id_at(Utc::now())
+}
+
+// Generate a unique segment ID for a segment that started at a given time (imports)
+pub fn generate_segment_id_at(start_time: DateTime<Utc>) -> String {
+    let timestamp = start_time.timestamp();
     let random_suffix: String = (0..6)
         .map(|_| {
             let byte: u8 = rand::random();
@@ -74,6 +183,105 @@ pub fn generate_segment_id() -> String {
     format!("segment_{}_{}", timestamp, random_suffix)
 }
 
+// Get the path of an existing segment video, falling back to the previous root
+pub fn find_video_path(app: &AppHandle, date: &NaiveDate, segment_id: &str, display_index: u32) -> PathBuf {
+    resolve_recording_path(app, &get_video_path(app, date, segment_id, display_index))
+}
+
+// Get the path for a display's per-frame timestamps file
+pub fn get_frame_timestamps_path(app: &AppHandle, date: &NaiveDate, segment_id: &str, display_index: u32) -> PathBuf {
+    let base_dir = get_recordings_dir(app);
+    let date_dir = base_dir.join(date.format("%Y-%m-%d").to_string());
+    date_dir.join(format!("{}_d{}.frames", segment_id, display_index))
+}
+
+// Save per-frame timestamps for a display video
+pub fn save_frame_timestamps(
+    app: &AppHandle,
+    date: &NaiveDate,
+    segment_id: &str,
+    display_index: u32,
+    frames: &FrameTimestamps,
+) -> Result<(), String> {
+    let path = get_frame_timestamps_path(app, date, segment_id, display_index);
+    
+    let contents = serde_json::to_string(frames)
+        .map_err(|e| format!("Failed to serialize frame timestamps: {}", e))?;
+    
+    std::fs::write(&path, contents)
+        .map_err(|e| format!("Failed to write frame timestamps: {}", e))
+}
+
+// Load per-frame timestamps for a display video (None for older or imported segments)
+pub fn load_frame_timestamps(app: &AppHandle, date: &NaiveDate, segment_id: &str, display_index: u32) -> Option<FrameTimestamps> {
+    let path = resolve_recording_path(app, &get_frame_timestamps_path(app, date, segment_id, display_index));
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+// Get the path for a segment's input activity summary
+pub fn get_input_activity_path(app: &AppHandle, date: &NaiveDate, segment_id: &str) -> PathBuf {
+    let base_dir = get_recordings_dir(app);
+    let date_dir = base_dir.join(date.format("%Y-%m-%d").to_string());
+    date_dir.join(format!("{}.input", segment_id))
+}
+
+// Get the path for a version of a display's timeline analysis
+// Version 1 is <segment>_d<N>.timeline, reanalyses <segment>_d<N>.v<K>.timeline
+pub fn get_analysis_path(app: &AppHandle, date: &NaiveDate, segment_id: &str, display_index: u32, version: u32) -> PathBuf {
+    let base_dir = get_recordings_dir(app);
+    let date_dir = base_dir.join(date.format("%Y-%m-%d").to_string());
+    if version <= 1 {
+        date_dir.join(format!("{}_d{}.timeline", segment_id, display_index))
+    } else {
+        date_dir.join(format!("{}_d{}.v{}.timeline", segment_id, display_index, version))
+    }
+}
+
+// Stored analysis versions of a display (any recordings root), ascending
+pub fn list_analysis_versions(app: &AppHandle, date: &NaiveDate, segment_id: &str, display_index: u32) -> Vec<u32> {
+    let prefix = format!("{}_d{}.", segment_id, display_index);
+    let mut versions: Vec<u32> = get_recording_roots(app)
+        .into_iter()
+        .filter_map(|root| std::fs::read_dir(root.join(date.format("%Y-%m-%d").to_string())).ok())
+        .flat_map(|entries| entries.filter_map(|e| e.ok()))
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            analysis_version_of(name.strip_prefix(&prefix)?)
+        })
+        .collect();
+    
+    versions.sort_unstable();
+    versions.dedup();
+    versions
+}
+
+// Analysis version from a sidecar name without its "<segment>_d<N>." prefix
+fn analysis_version_of(suffix: &str) -> Option<u32> {
+    match suffix {
+        "timeline" => Some(1),
+        _ => suffix.strip_suffix(".timeline")?.strip_prefix('v')?.parse().ok().filter(|v| *v > 1),
+    }
+}
+
+// Save a segment's input activity summary
+pub fn save_input_activity(app: &AppHandle, date: &NaiveDate, summary: &InputActivitySummary) -> Result<(), String> {
+    let path = get_input_activity_path(app, date, &summary.segment_id);
+    
+    let contents = serde_json::to_string(summary)
+        .map_err(|e| format!("Failed to serialize input activity: {}", e))?;
+    
+    std::fs::write(&path, contents)
+        .map_err(|e| format!("Failed to write input activity: {}", e))
+}
+
+// Load a segment's input activity summary (None if not summarized)
+pub fn load_input_activity(app: &AppHandle, date: &NaiveDate, segment_id: &str) -> Option<InputActivitySummary> {
+    let path = resolve_recording_path(app, &get_input_activity_path(app, date, segment_id));
+    let contents = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
 // Save recording metadata to JSON file
 pub fn save_metadata(app: &AppHandle, date: &NaiveDate, metadata: &RecordingMetadata) -> Result<(), String> {
     let path = get_metadata_path(app, date, &metadata.id);
@@ -97,25 +305,61 @@ pub fn load_metadata(path: &PathBuf) -> Result<RecordingMetadata, String> {
         .map_err(|e| format!("Failed to parse metadata: {}", e))
 }
 
-// Calculate total storage used by recordings
-pub fn calculate_total_storage(app: &AppHandle) -> Result<u64, String> {
-    let recordings_dir = get_recordings_dir(app);
+// Get all segments recorded on a given day, oldest first
+pub fn get_segments_for_date(app: &AppHandle, date: &NaiveDate) -> Vec<RecordingMetadata> {
+    let mut segments: Vec<RecordingMetadata> = Vec::new();
     
-    if !recordings_dir.exists() {
-        return Ok(0);
+    for root in get_recording_roots(app) {
+        let date_dir = root.join(date.format("%Y-%m-%d").to_string());
+        
+        let entries = match std::fs::read_dir(&date_dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        
+        for metadata in entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().map(|ext| ext == "json").unwrap_or(false))
+            .filter_map(|p| load_metadata(&p).ok())
+        {
+            // Current root wins if a sidecar exists in both
+            if !segments.iter().any(|s| s.id == metadata.id) {
+                segments.push(metadata);
+            }
+        }
     }
     
-    let mut total_size: u64 = 0;
-    
-    for entry in WalkDir::new(&recordings_dir)
-        .follow_links(false)
+    segments.sort_by(|a, b| a.start_time.cmp(&b.start_time));
+    segments
+}
+
+// Get all dates that have a recordings directory, oldest first
+pub fn get_recording_dates(app: &AppHandle) -> Vec<NaiveDate> {
+    let mut dates: Vec<NaiveDate> = get_recording_roots(app)
         .into_iter()
+        .filter_map(|root| std::fs::read_dir(root).ok())
+        .flatten()
         .filter_map(|e| e.ok())
-    {
-        if entry.file_type().is_file() {
-            if let Ok(metadata) = entry.metadata() {
-                total_size += metadata.len();
-            }
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| {
+            let name = e.file_name().into_string().ok()?;
+            NaiveDate::parse_from_str(&name, "%Y-%m-%d").ok()
+        })
+        .collect();
+    
+    dates.sort();
+    dates.dedup();
+    dates
+}
+
+// Calculate total storage used by recordings
+pub fn calculate_total_storage(app: &AppHandle) -> Result<u64, String> {
+    let mut total_size: u64 = 0;
+    
+    for entry in walk_recording_files(app) {
+        if let Ok(metadata) = entry.metadata() {
+            total_size += metadata.len();
         }
     }
     
@@ -124,53 +368,22 @@ pub fn calculate_total_storage(app: &AppHandle) -> Result<u64, String> {
 
 // Count total segments (by counting .mp4 files)
 pub fn count_segments(app: &AppHandle) -> u64 {
-    let recordings_dir = get_recordings_dir(app);
-    
-    if !recordings_dir.exists() {
-        return 0;
-    }
-    
-    let mut count: u64 = 0;
-    
-    for entry in WalkDir::new(&recordings_dir)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_type().is_file() {
-            if let Some(ext) = entry.path().extension() {
-                if ext == "mp4" {
-                    count += 1;
-                }
-            }
-        }
-    }
-    
-    count
+    walk_recording_files(app)
+        .filter(|e| e.path().extension().map(|ext| ext == "mp4").unwrap_or(false))
+        .count() as u64
 }
 
 // Get all recording files with metadata (for cleanup)
 fn get_all_recording_files(app: &AppHandle) -> Vec<(PathBuf, u64, std::time::SystemTime)> {
-    let recordings_dir = get_recordings_dir(app);
     let mut files = Vec::new();
     
-    if !recordings_dir.exists() {
-        return files;
-    }
-    
-    for entry in WalkDir::new(&recordings_dir)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_type().is_file() {
-            if let Some(ext) = entry.path().extension() {
-                // Include both .mp4 and .json files
-                if ext == "mp4" || ext == "json" {
-                    if let Ok(metadata) = entry.metadata() {
-                        let modified = metadata.modified().unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-                        files.push((entry.path().to_path_buf(), metadata.len(), modified));
-                    }
+    for entry in walk_recording_files(app) {
+        if let Some(ext) = entry.path().extension() {
+            // Include videos, sidecars and per-segment data files
+            if ext == "mp4" || ext == "json" || ext == "frames" || ext == "input" || ext == "timeline" {
+                if let Ok(metadata) = entry.metadata() {
+                    let modified = metadata.modified().unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+                    files.push((entry.path().to_path_buf(), metadata.len(), modified));
                 }
             }
         }
@@ -184,11 +397,7 @@ fn get_all_recording_files(app: &AppHandle) -> Vec<(PathBuf, u64, std::time::Sys
 
 // Cleanup recordings older than retention period
 pub fn cleanup_old_recordings(app: &AppHandle, config: &RecordingConfig) -> Result<u64, String> {
-    let recordings_dir = get_recordings_dir(app);
-    
-    if !recordings_dir.exists() {
-        return Ok(0);
-    }
+    let roots = get_recording_roots(app);
     
     let cutoff = Utc::now() - chrono::Duration::days(config.retention_days as i64);
     let cutoff_system_time = std::time::SystemTime::UNIX_EPOCH
@@ -196,31 +405,27 @@ pub fn cleanup_old_recordings(app: &AppHandle, config: &RecordingConfig) -> Resu
     
     let mut bytes_deleted: u64 = 0;
     let mut files_deleted: u32 = 0;
+    let mut deleted_paths = Vec::new();
     let mut dirs_to_check = Vec::new();
     
-    for entry in WalkDir::new(&recordings_dir)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_type().is_file() {
-            if let Ok(metadata) = entry.metadata() {
-                let modified = metadata.modified().unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+    for entry in walk_recording_files(app) {
+        if let Ok(metadata) = entry.metadata() {
+            let modified = metadata.modified().unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+            
+            if modified < cutoff_system_time {
+                let file_size = metadata.len();
                 
-                if modified < cutoff_system_time {
-                    let file_size = metadata.len();
+                if let Err(e) = std::fs::remove_file(entry.path()) {
+                    log::warn!("Failed to delete old recording {:?}: {}", entry.path(), e);
+                } else {
+                    bytes_deleted += file_size;
+                    files_deleted += 1;
+                    deleted_paths.push(entry.path().to_path_buf());
                     
-                    if let Err(e) = std::fs::remove_file(entry.path()) {
-                        log::warn!("Failed to delete old recording {:?}: {}", entry.path(), e);
-                    } else {
-                        bytes_deleted += file_size;
-                        files_deleted += 1;
-                        
-                        // Track parent directory for potential cleanup
-                        if let Some(parent) = entry.path().parent() {
-                            if !dirs_to_check.contains(&parent.to_path_buf()) {
-                                dirs_to_check.push(parent.to_path_buf());
-                            }
+                    // Track parent directory for potential cleanup
+                    if let Some(parent) = entry.path().parent() {
+                        if !dirs_to_check.contains(&parent.to_path_buf()) {
+                            dirs_to_check.push(parent.to_path_buf());
                         }
                     }
                 }
@@ -230,7 +435,7 @@ pub fn cleanup_old_recordings(app: &AppHandle, config: &RecordingConfig) -> Resu
     
     // Clean up empty date directories
     for dir in dirs_to_check {
-        if dir != recordings_dir {
+        if !roots.contains(&dir) {
             if let Ok(entries) = std::fs::read_dir(&dir) {
                 if entries.count() == 0 {
                     let _ = std::fs::remove_dir(&dir);
@@ -245,6 +450,7 @@ pub fn cleanup_old_recordings(app: &AppHandle, config: &RecordingConfig) -> Resu
             files_deleted,
             bytes_deleted
         );
+        integrity::record_deletions(app, &deleted_paths, "retention");
     }
     
     Ok(bytes_deleted)
@@ -272,6 +478,7 @@ pub fn cleanup_by_quota(app: &AppHandle, config: &RecordingConfig) -> Result<u64
     let files = get_all_recording_files(app);
     let mut bytes_deleted: u64 = 0;
     let mut files_deleted: u32 = 0;
+    let mut deleted_paths = Vec::new();
     
     for (path, size, _) in files {
         if bytes_deleted >= bytes_to_delete {
@@ -283,6 +490,7 @@ pub fn cleanup_by_quota(app: &AppHandle, config: &RecordingConfig) -> Result<u64
         } else {
             bytes_deleted += size;
             files_deleted += 1;
+            deleted_paths.push(path.clone());
             
             // Clean up empty parent directory
             if let Some(parent) = path.parent() {
@@ -300,6 +508,7 @@ pub fn cleanup_by_quota(app: &AppHandle, config: &RecordingConfig) -> Result<u64
         files_deleted,
         bytes_deleted
     );
+    integrity::record_deletions(app, &deleted_paths, "quota");
     
     Ok(bytes_deleted)
 }
@@ -317,32 +526,23 @@ pub fn get_recordings_in_range(
     start_time: &chrono::DateTime<Utc>,
     end_time: &chrono::DateTime<Utc>,
 ) -> Result<Vec<RecordingMetadata>, String> {
-    let recordings_dir = get_recordings_dir(app);
-    
-    if !recordings_dir.exists() {
-        return Ok(Vec::new());
-    }
-    
-    let mut recordings = Vec::new();
-    
-    // Walk through all .json metadata files
-    for entry in WalkDir::new(&recordings_dir)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        if entry.file_type().is_file() {
-            if let Some(ext) = entry.path().extension() {
-                if ext == "json" {
-                    // Try to load the metadata
-                    if let Ok(metadata) = load_metadata(&entry.path().to_path_buf()) {
-                        // Parse the start time and check if it's in range
-                        if let Ok(recording_start) = chrono::DateTime::parse_from_rfc3339(&metadata.start_time) {
-                            let recording_start_utc = recording_start.with_timezone(&Utc);
-                            
-                            if recording_start_utc >= *start_time && recording_start_utc <= *end_time {
-                                recordings.push(metadata);
-                            }
+    let mut recordings: Vec<RecordingMetadata> = Vec::new();
+    
+    // Walk through all .json metadata files (current root first)
+    for entry in walk_recording_files(app) {
+        if let Some(ext) = entry.path().extension() {
+            if ext == "json" {
+                // Try to load the metadata
+                if let Ok(metadata) = load_metadata(&entry.path().to_path_buf()) {
+                    // Parse the start time and check if it's in range
+                    if let Ok(recording_start) = chrono::DateTime::parse_from_rfc3339(&metadata.start_time) {
+                        let recording_start_utc = recording_start.with_timezone(&Utc);
+                        
+                        if recording_start_utc >= *start_time
+                            && recording_start_utc <= *end_time
+                            && !recordings.iter().any(|r| r.id == metadata.id)
+                        {
+                            recordings.push(metadata);
                         }
                     }
                 }
@@ -356,6 +556,168 @@ pub fn get_recordings_in_range(
     Ok(recordings)
 }
 
+// =============================================================================
+// Seek
+// =============================================================================
+
+/**
+ * Find the segment file and video offset showing a wall-clock moment
+ *
+ * At rotation boundaries the newer segment wins. If the moment falls in a
+ * gap, the nearest recorded position (start of the next segment, else end of
+ * the previous one) is returned with `exact: false`.
+ *
+ * # Returns
+ * * `Ok(SeekResult)` - Segment, file path, asset URL and offset
+ * * `Err(String)` - Nothing recorded for that display within a day
+ */
+pub fn seek(app: &AppHandle, timestamp: DateTime<Utc>, display_index: u32) -> Result<SeekResult, String> {
+    // Neighbouring days cover segments crossing midnight and gaps at day edges
+    let date = timestamp.date_naive();
+    let segments: Vec<RecordingMetadata> = [date.pred_opt(), Some(date), date.succ_opt()]
+        .into_iter()
+        .flatten()
+        .flat_map(|d| get_segments_for_date(app, &d))
+        .collect();
+    
+    let (segment, position) = find_segment_at(&segments, timestamp, display_index)
+        .ok_or_else(|| format!("No recording for display {} near {}", display_index, timestamp.to_rfc3339()))?;
+    
+    let segment_start = parse_rfc3339(&segment.start_time).unwrap_or(position);
+    let segment_date = segment_start.date_naive();
+    let display = segment
+        .displays
+        .iter()
+        .find(|d| d.display_index == display_index)
+        .ok_or_else(|| "Display missing from segment".to_string())?;
+    
+    let frames = load_frame_timestamps(app, &segment_date, &segment.id, display_index);
+    let offset_seconds = video_offset_seconds(position, segment, display, frames.as_ref());
+    let file_path = find_video_path(app, &segment_date, &segment.id, display_index);
+    let gap_seconds = (timestamp - position).num_milliseconds().abs() as f64 / 1000.0;
+    
+    Ok(SeekResult {
+        segment_id: segment.id.clone(),
+        display_index,
+        segment_start: segment.start_time.clone(),
+        segment_end: segment.end_time.clone(),
+        url: asset_url(&file_path),
+        file_path: file_path.to_string_lossy().to_string(),
+        offset_seconds,
+        exact: gap_seconds == 0.0,
+        gap_seconds,
+        frame_accurate: frames.is_some(),
+    })
+}
+
+/**
+ * Pick the segment holding `at` for a display, or the nearest one
+ * Returns the segment and the wall-clock position to show from it
+ */
+fn find_segment_at(
+    segments: &[RecordingMetadata],
+    at: DateTime<Utc>,
+    display_index: u32,
+) -> Option<(&RecordingMetadata, DateTime<Utc>)> {
+    let mut spans: Vec<(&RecordingMetadata, DateTime<Utc>, DateTime<Utc>)> = segments
+        .iter()
+        .filter(|s| s.displays.iter().any(|d| d.display_index == display_index))
+        .filter_map(|s| Some((s, parse_rfc3339(&s.start_time)?, parse_rfc3339(&s.end_time)?)))
+        .collect();
+    spans.sort_by_key(|(_, start, _)| *start);
+    
+    // Latest-starting segment that contains the moment (newer wins at boundaries)
+    if let Some((segment, _, _)) = spans.iter().rev().find(|(_, start, end)| *start <= at && at <= *end) {
+        return Some((segment, at));
+    }
+    
+    let next = spans.iter().find(|(_, start, _)| *start > at);
+    let previous = spans.iter().rev().find(|(_, _, end)| *end < at);
+    
+    match (previous, next) {
+        (Some((p, _, p_end)), Some((n, n_start, _))) => {
+            if at - *p_end < *n_start - at {
+                Some((p, *p_end))
+            } else {
+                Some((n, *n_start))
+            }
+        }
+        (None, Some((n, n_start, _))) => Some((n, *n_start)),
+        (Some((p, _, p_end)), None) => Some((p, *p_end)),
+        (None, None) => None,
+    }
+}
+
+/**
+ * Convert a wall-clock moment to a position in a display video
+ *
+ * Frames are encoded at a constant framerate but captured whenever the OS
+ * delivers them, so video time drifts from wall-clock time. With per-frame
+ * timestamps the exact frame is used; otherwise the wall-clock offset is
+ * scaled by (encoded duration / recorded duration).
+ */
+fn video_offset_seconds(
+    at: DateTime<Utc>,
+    segment: &RecordingMetadata,
+    display: &DisplayRecording,
+    frames: Option<&FrameTimestamps>,
+) -> f64 {
+    let fps = segment.framerate.max(1) as f64;
+    let video_duration = display.frame_count as f64 / fps;
+    
+    if let Some(frames) = frames {
+        if let Some(first_frame_at) = parse_rfc3339(&frames.started_at) {
+            let wall_ms = (at - first_frame_at).num_milliseconds().max(0) as u32;
+            // Last frame captured at or before the moment
+            let frame_index = frames.offsets_ms.partition_point(|&ms| ms <= wall_ms).saturating_sub(1);
+            return frame_index as f64 / fps;
+        }
+    }
+    
+    let wall_offset = match parse_rfc3339(&segment.start_time) {
+        Some(start) => (at - start).num_milliseconds() as f64 / 1000.0,
+        None => 0.0,
+    };
+    
+    let scale = if segment.duration_seconds > 0.0 && video_duration > 0.0 {
+        video_duration / segment.duration_seconds
+    } else {
+        1.0
+    };
+    
+    (wall_offset * scale).clamp(0.0, video_duration.max(0.0))
+}
+
+/**
+ * Build the asset protocol URL for a local file (same as convertFileSrc)
+ * The asset protocol serves HTTP range requests, so videos are seekable
+ */
+pub fn asset_url(path: &Path) -> String {
+    let encoded = encode_uri_component(&path.to_string_lossy());
+    
+    if cfg!(any(windows, target_os = "android")) {
+        format!("http://asset.localhost/{}", encoded)
+    } else {
+        format!("asset://localhost/{}", encoded)
+    }
+}
+
+// Percent-encode like JavaScript's encodeURIComponent
+fn encode_uri_component(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9'
+            | b'-' | b'_' | b'.' | b'!' | b'~' | b'*' | b'\'' | b'(' | b')' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+fn parse_rfc3339(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value).ok().map(|t| t.with_timezone(&Utc))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -370,6 +732,16 @@ mod tests {
         assert_ne!(id1, id2);
     }
 
+    #[test]
+    fn test_analysis_version_of() {
+        assert_eq!(analysis_version_of("timeline"), Some(1));
+        assert_eq!(analysis_version_of("v2.timeline"), Some(2));
+        assert_eq!(analysis_version_of("v12.timeline"), Some(12));
+        assert_eq!(analysis_version_of("v1.timeline"), None);
+        assert_eq!(analysis_version_of("frames"), None);
+        assert_eq!(analysis_version_of("vx.timeline"), None);
+    }
+
     #[test]
     fn test_segment_id_format() {
         let id = generate_segment_id();
@@ -380,4 +752,85 @@ mod tests {
         assert!(parts[1].parse::<i64>().is_ok());
         assert_eq!(parts[2].len(), 12);
     }
+
+    #[test]
+    fn test_segment_id_at_uses_start_time() {
+        let start = DateTime::parse_from_rfc3339("2025-01-15T10:00:00Z").unwrap().with_timezone(&Utc);
+        let id = generate_segment_id_at(start);
+
+        assert!(id.starts_with("segment_1736935200_"));
+    }
+
+    fn segment(id: &str, start: &str, end: &str, frame_count: u64) -> RecordingMetadata {
+        let start_time = parse_rfc3339(start).unwrap();
+        let end_time = parse_rfc3339(end).unwrap();
+        RecordingMetadata {
+            id: id.to_string(),
+            format: "mp4".to_string(),
+            codec: "h264".to_string(),
+            framerate: 4,
+            start_time: start.to_string(),
+            end_time: end.to_string(),
+            duration_seconds: (end_time - start_time).num_milliseconds() as f64 / 1000.0,
+            total_file_size_bytes: 0,
+            display_count: 1,
+            displays: vec![DisplayRecording {
+                display_index: 0,
+                width: 1280,
+                height: 720,
+                frame_count,
+                file_size_bytes: 0,
+                filename: format!("{}_d0.mp4", id),
+                classification: None,
+            }],
+            imported_from: None,
+            high_risk: false,
+        }
+    }
+
+    #[test]
+    fn test_find_segment_at_boundaries_and_gaps() {
+        let segments = vec![
+            segment("a", "2025-01-15T10:00:00Z", "2025-01-15T10:05:00Z", 1200),
+            segment("b", "2025-01-15T10:05:00Z", "2025-01-15T10:10:00Z", 1200),
+            segment("c", "2025-01-15T10:30:00Z", "2025-01-15T10:35:00Z", 1200),
+        ];
+        let at = |s: &str| parse_rfc3339(s).unwrap();
+
+        // Rotation boundary belongs to the newer segment
+        let (s, _) = find_segment_at(&segments, at("2025-01-15T10:05:00Z"), 0).unwrap();
+        assert_eq!(s.id, "b");
+
+        // Gap: closer to the end of b than the start of c
+        let (s, position) = find_segment_at(&segments, at("2025-01-15T10:12:00Z"), 0).unwrap();
+        assert_eq!(s.id, "b");
+        assert_eq!(position, at("2025-01-15T10:10:00Z"));
+
+        // Unknown display
+        assert!(find_segment_at(&segments, at("2025-01-15T10:02:00Z"), 1).is_none());
+    }
+
+    #[test]
+    fn test_video_offset_seconds() {
+        // 300s recorded but only 1000 frames at 4fps = 250s of video
+        let seg = segment("a", "2025-01-15T10:00:00Z", "2025-01-15T10:05:00Z", 1000);
+        let at = parse_rfc3339("2025-01-15T10:01:00Z").unwrap();
+
+        let interpolated = video_offset_seconds(at, &seg, &seg.displays[0], None);
+        assert!((interpolated - 50.0).abs() < 1e-9);
+
+        // Frame 0 at 0ms, frame 1 at 250ms, frame 2 stalled until 60s
+        let frames = FrameTimestamps {
+            started_at: "2025-01-15T10:00:00Z".to_string(),
+            offsets_ms: vec![0, 250, 60_000, 60_250],
+        };
+        let exact = video_offset_seconds(at, &seg, &seg.displays[0], Some(&frames));
+        assert_eq!(exact, 0.5);
+    }
+
+    #[test]
+    fn test_asset_url_encoding() {
+        let url = asset_url(Path::new("/data/my recordings/a.mp4"));
+        assert!(url.ends_with("%2Fdata%2Fmy%20recordings%2Fa.mp4"));
+    }
 }
@@ -0,0 +1,397 @@
+/**
+ * ============================================================================
+ * RECORDING TIMELAPSE MODULE
+ * ============================================================================
+ *
+ * PURPOSE: Build a compressed per-display timelapse for each finished day
+ *
+ * FUNCTIONALITY:
+ * - Background worker that looks for completed days without a timelapse
+ * - Concatenate a day's segments per display with bundled FFmpeg
+ * - Speed-up (e.g. 60x) or frame sampling (e.g. one frame per 10s)
+ * - Per-day manifest.json describing what was generated
+ * - Days with a failed display are generated again on the next pass
+ *
+ * STORAGE:
+ * Timelapses are written to ~/.screenjournal/timelapses/{date}/, outside the
+ * recordings directory, so retention and quota cleanup never delete them.
+ *
+ * ============================================================================
+ */
+
+use crate::recording::capture;
+use crate::recording::storage;
+use crate::recording::types::{
+    RecordingConfig, RecordingMetadata, TimelapseConfig, TimelapseEntry, TimelapseManifest,
+};
+use chrono::{NaiveDate, Utc};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tauri::AppHandle;
+use tokio::time::{sleep, Duration};
+
+/// How often the worker checks for days that still need a timelapse
+const WORKER_INTERVAL_SECS: u64 = 3600; // 1 hour
+
+/// Delay before the first check so startup isn't slowed down by encoding
+const WORKER_STARTUP_DELAY_SECS: u64 = 120;
+
+/// Manifest filename inside each day's timelapse directory
+const MANIFEST_FILENAME: &str = "manifest.json";
+
+// =============================================================================
+// Background Worker
+// =============================================================================
+
+/**
+ * Start the timelapse background worker
+ * Must be called once on app startup; reads the recording config on each pass
+ */
+pub fn start_worker(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        sleep(Duration::from_secs(WORKER_STARTUP_DELAY_SECS)).await;
+
+        loop {
+            let config = crate::recording::manager::current_config();
+
+            if config.timelapse.enabled {
+                let app_clone = app.clone();
+                let result = tokio::task::spawn_blocking(move || {
+                    generate_pending_timelapses(&app_clone, &config)
+                })
+                .await;
+
+                match result {
+                    Ok(Ok(count)) if count > 0 => {
+                        log::info!("[TIMELAPSE] Generated timelapses for {} day(s)", count);
+                    }
+                    Ok(Ok(_)) => {}
+                    Ok(Err(e)) => log::error!("[TIMELAPSE] Worker pass failed: {}", e),
+                    Err(e) => log::error!("[TIMELAPSE] Worker task panicked: {}", e),
+                }
+            }
+
+            sleep(Duration::from_secs(WORKER_INTERVAL_SECS)).await;
+        }
+    });
+
+    log::info!("Timelapse worker started");
+}
+
+/**
+ * Generate timelapses for every finished day without a manifest, or whose
+ * manifest lists failed displays
+ *
+ * # Returns
+ * * `Ok(usize)` - Number of days processed
+ */
+pub fn generate_pending_timelapses(app: &AppHandle, config: &RecordingConfig) -> Result<usize, String> {
+    let today = Utc::now().date_naive();
+    let mut generated = 0;
+
+    for date in storage::get_recording_dates(app) {
+        // Only finished days - today's segments are still being written
+        if date >= today {
+            continue;
+        }
+
+        if !needs_timelapse(app, &date) {
+            continue;
+        }
+
+        match generate_timelapse(app, &date, config) {
+            Ok(Some(_)) => generated += 1,
+            Ok(None) => {}
+            Err(e) => log::error!("[TIMELAPSE] Failed to generate timelapse for {}: {}", date, e),
+        }
+    }
+
+    Ok(generated)
+}
+
+// =============================================================================
+// Generation
+// =============================================================================
+
+/**
+ * Generate the timelapse for one day (all displays) and write its manifest
+ * Overwrites any existing timelapse for that day
+ *
+ * # Returns
+ * * `Ok(Some(TimelapseManifest))` - Timelapse generated
+ * * `Ok(None)` - No segments recorded on that day
+ * * `Err(String)` - Error message
+ */
+pub fn generate_timelapse(
+    app: &AppHandle,
+    date: &NaiveDate,
+    config: &RecordingConfig,
+) -> Result<Option<TimelapseManifest>, String> {
+    let segments = storage::get_segments_for_date(app, date);
+    if segments.is_empty() {
+        return Ok(None);
+    }
+
+    let output_dir = timelapse_dir(app, date);
+    std::fs::create_dir_all(&output_dir)
+        .map_err(|e| format!("Failed to create timelapse directory: {}", e))?;
+
+    log::info!(
+        "[TIMELAPSE] Generating timelapse for {} from {} segment(s)",
+        date,
+        segments.len()
+    );
+
+    let mut displays = Vec::new();
+    let mut failed_displays = Vec::new();
+
+    for (display_index, sources) in group_by_display(app, &segments, date) {
+        let filename = format!("timelapse_d{}.mp4", display_index);
+        let output_path = output_dir.join(&filename);
+        let list_path = output_dir.join(format!("concat_d{}.txt", display_index));
+
+        let paths: Vec<&Path> = sources.iter().map(|(path, _)| path.as_path()).collect();
+        std::fs::write(&list_path, build_concat_list(&paths))
+            .map_err(|e| format!("Failed to write concat list: {}", e))?;
+
+        let result = run_ffmpeg(&list_path, &output_path, config);
+        let _ = std::fs::remove_file(&list_path);
+
+        if let Err(e) = result {
+            log::error!("[TIMELAPSE] Display {} failed for {}: {}", display_index, date, e);
+            failed_displays.push(display_index);
+            continue;
+        }
+
+        displays.push(TimelapseEntry {
+            display_index,
+            filename,
+            source_segments: sources.iter().map(|(_, m)| m.id.clone()).collect(),
+            source_duration_seconds: sources.iter().map(|(_, m)| m.duration_seconds).sum(),
+            file_size_bytes: storage::get_file_size(&output_path).unwrap_or(0),
+        });
+    }
+
+    if displays.is_empty() {
+        return Err("No display timelapse could be generated".to_string());
+    }
+
+    let timelapse = &config.timelapse;
+    let manifest = TimelapseManifest {
+        date: date.format("%Y-%m-%d").to_string(),
+        generated_at: Utc::now().to_rfc3339(),
+        speed_factor: if timelapse.frame_interval_seconds > 0 { 0 } else { timelapse.speed_factor },
+        frame_interval_seconds: timelapse.frame_interval_seconds,
+        output_fps: timelapse.output_fps,
+        displays,
+        failed_displays,
+    };
+
+    save_manifest(app, date, &manifest)?;
+
+    log::info!(
+        "[TIMELAPSE] Timelapse for {} complete ({} display(s), {} failed)",
+        date,
+        manifest.displays.len(),
+        manifest.failed_displays.len()
+    );
+
+    Ok(Some(manifest))
+}
+
+// Group a day's segment files by display index, keeping chronological order
+fn group_by_display<'a>(
+    app: &AppHandle,
+    segments: &'a [RecordingMetadata],
+    date: &NaiveDate,
+) -> BTreeMap<u32, Vec<(PathBuf, &'a RecordingMetadata)>> {
+    let mut by_display: BTreeMap<u32, Vec<(PathBuf, &RecordingMetadata)>> = BTreeMap::new();
+
+    for segment in segments {
+        for display in &segment.displays {
+            let path = storage::find_video_path(app, date, &segment.id, display.display_index);
+            if path.exists() {
+                by_display
+                    .entry(display.display_index)
+                    .or_default()
+                    .push((path, segment));
+            }
+        }
+    }
+
+    by_display
+}
+
+// Build FFmpeg concat demuxer input list
+fn build_concat_list(paths: &[&Path]) -> String {
+    paths
+        .iter()
+        .map(|p| {
+            // Single quotes inside a quoted path are written as '\''
+            let escaped = p.to_string_lossy().replace('\'', "'\\''");
+            format!("file '{}'\n", escaped)
+        })
+        .collect()
+}
+
+// Build the video filter for the configured timelapse mode
+fn build_filter(timelapse: &TimelapseConfig, output_width: u32) -> String {
+    let output_fps = timelapse.output_fps.max(1);
+
+    let timing = if timelapse.frame_interval_seconds > 0 {
+        // Keep one frame every N seconds, then play them back-to-back
+        format!(
+            "fps=1/{},setpts=N/{}/TB",
+            timelapse.frame_interval_seconds, output_fps
+        )
+    } else {
+        format!(
+            "setpts=PTS/{},fps={}",
+            timelapse.speed_factor.max(1),
+            output_fps
+        )
+    };
+
+    // Normalize size in case output_width changed during the day
+    let output_height = (output_width * 9) / 16;
+    let output_height = if output_height % 2 == 1 { output_height + 1 } else { output_height };
+
+    format!(
+        "{},scale={}:{}:force_original_aspect_ratio=decrease,pad={}:{}:(ow-iw)/2:(oh-ih)/2:black",
+        timing, output_width, output_height, output_width, output_height
+    )
+}
+
+// Encode the timelapse for one display
+fn run_ffmpeg(list_path: &Path, output_path: &Path, config: &RecordingConfig) -> Result<(), String> {
+    let ffmpeg_path = capture::get_ffmpeg_path()?;
+    let filter = build_filter(&config.timelapse, config.output_width);
+
+    let status = Command::new(&ffmpeg_path)
+        .args(["-y", "-f", "concat", "-safe", "0", "-i"])
+        .arg(list_path)
+        .args([
+            "-vf", &filter,
+            "-an",
+            "-c:v", "libx264",
+            "-preset", &config.preset,
+            "-crf", &config.crf.to_string(),
+            "-pix_fmt", "yuv420p",
+            "-movflags", "+faststart",
+        ])
+        .arg(output_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| format!("Failed to run FFmpeg at {:?}: {}", ffmpeg_path, e))?;
+
+    if !status.success() {
+        return Err(format!("FFmpeg exited with error: {:?}", status.code()));
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// Manifest Persistence
+// =============================================================================
+
+// Get the timelapse directory for a date
+pub fn timelapse_dir(app: &AppHandle, date: &NaiveDate) -> PathBuf {
+    storage::get_timelapses_dir(app).join(date.format("%Y-%m-%d").to_string())
+}
+
+fn manifest_path(app: &AppHandle, date: &NaiveDate) -> PathBuf {
+    timelapse_dir(app, date).join(MANIFEST_FILENAME)
+}
+
+// Whether a day has no manifest yet or a display failed last time
+fn needs_timelapse(app: &AppHandle, date: &NaiveDate) -> bool {
+    if !manifest_path(app, date).exists() {
+        return true;
+    }
+    load_manifest(app, date).is_some_and(|manifest| !manifest.failed_displays.is_empty())
+}
+
+fn save_manifest(app: &AppHandle, date: &NaiveDate, manifest: &TimelapseManifest) -> Result<(), String> {
+    let contents = serde_json::to_string_pretty(manifest)
+        .map_err(|e| format!("Failed to serialize timelapse manifest: {}", e))?;
+
+    std::fs::write(manifest_path(app, date), contents)
+        .map_err(|e| format!("Failed to write timelapse manifest: {}", e))
+}
+
+// Load the timelapse manifest for a date, if one exists
+pub fn load_manifest(app: &AppHandle, date: &NaiveDate) -> Option<TimelapseManifest> {
+    let contents = std::fs::read_to_string(manifest_path(app, date)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+// Get all timelapse manifests between two dates (inclusive), oldest first
+pub fn get_manifests_in_range(app: &AppHandle, start: &NaiveDate, end: &NaiveDate) -> Vec<TimelapseManifest> {
+    start
+        .iter_days()
+        .take_while(|d| d <= end)
+        .filter_map(|d| load_manifest(app, &d))
+        .collect()
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_filter_speed_factor() {
+        let config = TimelapseConfig {
+            enabled: true,
+            speed_factor: 60,
+            frame_interval_seconds: 0,
+            output_fps: 30,
+        };
+        let filter = build_filter(&config, 1280);
+        assert!(filter.starts_with("setpts=PTS/60,fps=30,"));
+        assert!(filter.contains("scale=1280:720"));
+    }
+
+    #[test]
+    fn test_build_filter_frame_interval() {
+        let config = TimelapseConfig {
+            enabled: true,
+            speed_factor: 60,
+            frame_interval_seconds: 10,
+            output_fps: 30,
+        };
+        let filter = build_filter(&config, 1280);
+        assert!(filter.starts_with("fps=1/10,setpts=N/30/TB,"));
+    }
+
+    #[test]
+    fn test_build_concat_list_escapes_quotes() {
+        let a = PathBuf::from("/data/rec/segment_1_d0.mp4");
+        let b = PathBuf::from("/data/it's here/segment_2_d0.mp4");
+        let list = build_concat_list(&[a.as_path(), b.as_path()]);
+
+        assert_eq!(
+            list,
+            "file '/data/rec/segment_1_d0.mp4'\nfile '/data/it'\\''s here/segment_2_d0.mp4'\n"
+        );
+    }
+
+    #[test]
+    fn test_manifest_without_failed_displays() {
+        // Manifests written before failures were recorded
+        let manifest: TimelapseManifest = serde_json::from_str(
+            r#"{"date":"2025-01-15","generated_at":"2025-01-16T00:00:00Z","speed_factor":60,
+                "frame_interval_seconds":0,"output_fps":30,"displays":[]}"#,
+        )
+        .unwrap();
+        assert!(manifest.failed_displays.is_empty());
+    }
+}
@@ -9,8 +9,22 @@
  * - RecordingConfig: Capture configuration
  * - RecordingMetadata: Metadata for captured segments (JSON sidecar)
  * - DisplayRecording: Per-display recording information
+ * - ScreenClassification: Post-capture content class of a display video
  * - MonitorInfo: Display information
  * - RecordingStatus: Status for frontend display
+ * - TimelapseConfig: Daily timelapse generation settings
+ * - TimelapseManifest: Per-day record of generated timelapses
+ * - PrivacyConfig: App/title exclusion and text redaction rules
+ * - OcrConfig: Local OCR text indexing settings
+ * - OcrSearchHit: Result of a text search over recorded frames
+ * - CoverageSchedule: Expected recording hours used for coverage reports
+ * - CoverageIncident: Capture failures and lifecycle events explaining gaps
+ * - CoverageReport: Per-day, per-display coverage and detected gaps
+ * - RecordingsMigrationProgress: Progress of moving recordings to a new root
+ * - FrameTimestamps: Per-frame capture times for a display video
+ * - SeekResult: Segment file and offset holding a wall-clock moment
+ * - IntegrityReport: Result of verifying the segment hash chain
+ * - InputActivityConfig / InputActivitySummary: Per-segment keyboard/mouse activity
  * 
  * Gemini-related types are in recording/gemini/types.rs
  * 
@@ -54,6 +68,32 @@ pub struct RecordingConfig {
     // Default: "fast" - good compression with reasonable CPU usage
     #[serde(default = "default_preset")]
     pub preset: String,
+    
+    // Daily timelapse generation (built from the previous day's segments)
+    #[serde(default)]
+    pub timelapse: TimelapseConfig,
+    
+    // Exclusion and redaction rules applied to anything derived from recordings
+    #[serde(default)]
+    pub privacy: PrivacyConfig,
+    
+    // Local OCR text index over recorded frames
+    #[serde(default)]
+    pub ocr: OcrConfig,
+    
+    // Custom recordings root (e.g. a second disk or encrypted volume)
+    // None = ~/.screenjournal/recordings; changing it migrates existing segments
+    #[serde(default)]
+    pub recordings_dir: Option<String>,
+    
+    // Per-segment input activity summaries from ActivityWatch input events
+    #[serde(default)]
+    pub input_activity: InputActivityConfig,
+    
+    // Expected recording hours for coverage reports
+    // None = expect recording whenever ActivityWatch reports the user as not-afk
+    #[serde(default)]
+    pub coverage_schedule: Option<CoverageSchedule>,
 }
 
 impl Default for RecordingConfig {
@@ -67,6 +107,12 @@ impl Default for RecordingConfig {
             output_width: default_output_width(),
             crf: default_crf(),
             preset: default_preset(),
+            timelapse: TimelapseConfig::default(),
+            privacy: PrivacyConfig::default(),
+            ocr: OcrConfig::default(),
+            recordings_dir: None,
+            input_activity: InputActivityConfig::default(),
+            coverage_schedule: None,
         }
     }
 }
@@ -92,9 +138,122 @@ impl RecordingConfig {
             || self.output_width != other.output_width
             || self.crf != other.crf
             || self.preset != other.preset
+            || self.recordings_dir != other.recordings_dir
+    }
+}
+
+// Configuration for daily timelapse generation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimelapseConfig {
+    // Timelapse generation enabled/disabled
+    pub enabled: bool,
+    
+    // Speed-up factor applied to the day's footage (60 = one minute per second)
+    #[serde(default = "default_timelapse_speed_factor")]
+    pub speed_factor: u32,
+    
+    // Alternative sampling: keep one frame every N seconds of recording
+    // 0 = use speed_factor instead
+    #[serde(default)]
+    pub frame_interval_seconds: u32,
+    
+    // Framerate of the generated timelapse video
+    #[serde(default = "default_timelapse_output_fps")]
+    pub output_fps: u8,
+}
+
+impl Default for TimelapseConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            speed_factor: default_timelapse_speed_factor(),
+            frame_interval_seconds: 0,
+            output_fps: default_timelapse_output_fps(),
+        }
+    }
+}
+
+fn default_timelapse_speed_factor() -> u32 {
+    60
+}
+
+fn default_timelapse_output_fps() -> u8 {
+    30
+}
+
+// Privacy rules applied to data derived from recordings (OCR text, prompts, ...)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PrivacyConfig {
+    // Applications whose content must never be extracted (case-insensitive match)
+    #[serde(default)]
+    pub excluded_apps: Vec<String>,
+    
+    // Regex patterns; windows whose title matches any of them are excluded
+    #[serde(default)]
+    pub excluded_title_patterns: Vec<String>,
+    
+    // Regex patterns; matching text is replaced with [REDACTED]
+    #[serde(default)]
+    pub redact_patterns: Vec<String>,
+}
+
+// Configuration for per-segment input activity summaries
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InputActivityConfig {
+    // Summarize input activity after each segment (needs ActivityWatch input events; off by default)
+    pub enabled: bool,
+    
+    // Length of each summary interval (seconds)
+    #[serde(default = "default_input_interval")]
+    pub interval_seconds: u32,
+}
+
+impl Default for InputActivityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_seconds: default_input_interval(),
+        }
+    }
+}
+
+fn default_input_interval() -> u32 {
+    10
+}
+
+// Configuration for the local OCR text index
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OcrConfig {
+    // OCR indexing enabled/disabled
+    pub enabled: bool,
+    
+    // Sample one frame every N seconds of each segment
+    #[serde(default = "default_ocr_sample_interval")]
+    pub sample_interval_seconds: u32,
+    
+    // Tesseract language code(s), e.g. "eng" or "eng+deu"
+    #[serde(default = "default_ocr_language")]
+    pub language: String,
+}
+
+impl Default for OcrConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sample_interval_seconds: default_ocr_sample_interval(),
+            language: default_ocr_language(),
+        }
     }
 }
 
+fn default_ocr_sample_interval() -> u32 {
+    15
+}
+
+fn default_ocr_language() -> String {
+    "eng".to_string()
+}
+
 // Information about a monitor/display
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MonitorInfo {
@@ -127,6 +286,24 @@ pub struct DisplayRecording {
     
     // Filename (relative to segment directory)
     pub filename: String,
+    
+    // Content class from post-capture frame sampling (None until classified)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classification: Option<ScreenClassification>,
+}
+
+// What a display showed during a segment
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreenClassification {
+    // Screen content changed
+    Active,
+    // Content visible but unchanged for the whole segment
+    Static,
+    // Solid black (sleeping monitor, screensaver)
+    Blank,
+    // Lock screen was in the foreground
+    Locked,
 }
 
 // Metadata for a captured recording segment (stored as JSON sidecar)
@@ -164,6 +341,14 @@ pub struct RecordingMetadata {
     
     // Per-display recording information
     pub displays: Vec<DisplayRecording>,
+    
+    // Original file path when the segment was imported rather than recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imported_from: Option<String>,
+    
+    // Flagged for closer review (routes analysis to the high-risk model chain)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub high_risk: bool,
 }
 
 fn default_format() -> String {
@@ -187,6 +372,308 @@ pub struct RecordingStatus {
     pub total_storage_bytes: u64,
 }
 
+// Timelapse generated for a single display on a given day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelapseEntry {
+    // Display index
+    pub display_index: u32,
+    
+    // Filename (relative to the day's timelapse directory)
+    pub filename: String,
+    
+    // Segment IDs the timelapse was built from, in chronological order
+    pub source_segments: Vec<String>,
+    
+    // Total recorded duration covered by the source segments (seconds)
+    pub source_duration_seconds: f64,
+    
+    // File size in bytes
+    pub file_size_bytes: u64,
+}
+
+// Per-day timelapse manifest (stored as manifest.json in the day's timelapse directory)
+// Lives outside the recordings directory so it survives retention cleanup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelapseManifest {
+    // Day covered by the timelapse (YYYY-MM-DD)
+    pub date: String,
+    
+    // ISO 8601 timestamp when the timelapse was generated
+    pub generated_at: String,
+    
+    // Speed-up factor used (0 if frame sampling was used)
+    pub speed_factor: u32,
+    
+    // Frame sampling interval used (0 if speed_factor was used)
+    pub frame_interval_seconds: u32,
+    
+    // Framerate of the generated videos
+    pub output_fps: u8,
+    
+    // One entry per display
+    pub displays: Vec<TimelapseEntry>,
+    
+    // Displays whose timelapse failed (the day is generated again)
+    #[serde(default)]
+    pub failed_displays: Vec<u32>,
+}
+
+// A single OCR text search hit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcrSearchHit {
+    // Segment containing the matching frame
+    pub segment_id: String,
+    
+    // Display the frame was captured from
+    pub display_index: u32,
+    
+    // ISO 8601 wall-clock time of the frame
+    pub timestamp: String,
+    
+    // Offset of the frame within the segment video (seconds)
+    pub offset_seconds: f64,
+    
+    // Excerpt of the recognized text around the first match
+    pub snippet: String,
+    
+    // Occurrences of the query terms in the frame text (higher = better)
+    pub score: u32,
+}
+
+// Expected recording hours (local time) for coverage reports
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CoverageSchedule {
+    // Start of expected recording, "HH:MM" local time
+    pub start_time: String,
+    
+    // End of expected recording, "HH:MM" local time
+    pub end_time: String,
+    
+    // Days recording is expected (1 = Monday ... 7 = Sunday)
+    #[serde(default = "default_schedule_weekdays")]
+    pub weekdays: Vec<u32>,
+}
+
+fn default_schedule_weekdays() -> Vec<u32> {
+    vec![1, 2, 3, 4, 5]
+}
+
+// What happened when a coverage incident was recorded
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverageIncidentKind {
+    // A capture thread errored or panicked
+    CaptureError,
+    // Recording was stopped (user action or config change)
+    RecordingStopped,
+    // The app was launched
+    AppStarted,
+}
+
+// Event recorded during recording that can explain a coverage gap
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageIncident {
+    pub kind: CoverageIncidentKind,
+    
+    // ISO 8601 timestamp the incident starts
+    pub start_time: String,
+    
+    // ISO 8601 timestamp the incident ends (same as start for instant events)
+    pub end_time: String,
+    
+    // Affected display (None = all displays)
+    #[serde(default)]
+    pub display_index: Option<u32>,
+    
+    // Error message or other detail
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+// Likely reason a coverage gap exists
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GapCause {
+    // Capture for this display failed (see gap detail)
+    CaptureError,
+    // Other displays were recorded but this one wasn't (disconnected or no frames)
+    DisplayMissing,
+    // Recording was stopped and not restarted
+    RecordingStopped,
+    // The app was relaunched during/after the gap without a clean stop (likely crash)
+    AppRestarted,
+    // Nothing was recorded and no incident explains it (app not running)
+    NotRunning,
+}
+
+// A period where recording was expected but nothing was recorded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageGap {
+    pub start_time: String,
+    pub end_time: String,
+    pub duration_seconds: f64,
+    pub cause: GapCause,
+    pub detail: Option<String>,
+}
+
+// Coverage for one display on one day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplayCoverage {
+    pub display_index: u32,
+    pub expected_seconds: f64,
+    pub recorded_seconds: f64,
+    
+    // recorded / expected within the expected windows (0.0 - 1.0)
+    pub coverage_ratio: f64,
+    
+    pub gaps: Vec<CoverageGap>,
+}
+
+// Recording coverage report for a day (UTC date, matching recordings/ layout)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageReport {
+    pub date: String,
+    pub generated_at: String,
+    
+    // Where expected windows came from: "schedule", "activitywatch" or "segments"
+    pub expected_source: String,
+    
+    pub displays: Vec<DisplayCoverage>,
+}
+
+// Progress of a recordings root migration (emitted as "recordings-migration-progress")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingsMigrationProgress {
+    pub source: String,
+    pub destination: String,
+    pub files_total: u64,
+    pub files_done: u64,
+    pub bytes_total: u64,
+    pub bytes_done: u64,
+    
+    // Files that could not be moved (left in the source; retried on next launch)
+    pub files_failed: u64,
+    
+    pub finished: bool,
+}
+
+// Capture times of each encoded frame of one display video (<segment>_d<N>.frames)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameTimestamps {
+    // ISO 8601 wall-clock time of the first frame
+    pub started_at: String,
+    
+    // Capture time of frame i, in ms since the first frame
+    pub offsets_ms: Vec<u32>,
+}
+
+// Where a wall-clock moment can be found in the recordings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeekResult {
+    pub segment_id: String,
+    pub display_index: u32,
+    
+    // ISO 8601 start/end of the segment
+    pub segment_start: String,
+    pub segment_end: String,
+    
+    // Absolute path of the display video
+    pub file_path: String,
+    
+    // Asset protocol URL (supports HTTP range requests for seeking)
+    pub url: String,
+    
+    // Position in the video to seek to (seconds)
+    pub offset_seconds: f64,
+    
+    // False if the moment fell in a gap and the nearest segment was returned
+    pub exact: bool,
+    
+    // Distance from the requested moment to the returned position (0 if exact)
+    pub gap_seconds: f64,
+    
+    // True if the offset came from per-frame capture times rather than interpolation
+    pub frame_accurate: bool,
+}
+
+// Problem found while verifying the segment hash chain
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityIssueKind {
+    // Entry hash doesn't match its contents, or signature is invalid
+    TamperedEntry,
+    // Sequence number or previous-hash link is wrong (entries removed/reordered)
+    BrokenLink,
+    // A sealed file no longer exists and no deletion was recorded
+    MissingFile,
+    // A sealed file's contents changed
+    ModifiedFile,
+    // A recording file appeared after sealing started but was never sealed
+    UntrackedFile,
+    // Chain ends before the recorded chain head (entries removed from the end)
+    TruncatedChain,
+    // Device signing key is missing, signatures can't be attributed to this device
+    MissingKey,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityIssue {
+    pub kind: IntegrityIssueKind,
+    pub seq: Option<u64>,
+    pub segment_id: Option<String>,
+    pub file: Option<String>,
+    pub detail: String,
+}
+
+// Result of walking the segment hash chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    // Hex-encoded Ed25519 public key of this device
+    pub public_key: String,
+    
+    pub entries_checked: u64,
+    pub files_verified: u64,
+    
+    // Files removed by recorded retention/quota deletions
+    pub files_deleted: u64,
+    
+    // True if the chain itself (hashes, signatures, links) is intact
+    pub chain_valid: bool,
+    
+    pub issues: Vec<IntegrityIssue>,
+}
+
+// Input activity during one interval of a segment
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InputInterval {
+    // Start of the interval, seconds since segment start (wall-clock)
+    pub start_offset_seconds: f64,
+    pub presses: f64,
+    pub clicks: f64,
+    
+    // Mouse movement in pixels
+    pub mouse_distance: f64,
+    
+    // Scroll amount (scroll wheel units)
+    pub scroll_distance: f64,
+}
+
+// Input activity for a segment (stored as <segment_id>.input next to the sidecar)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputActivitySummary {
+    pub segment_id: String,
+    pub interval_seconds: u32,
+    pub total_presses: f64,
+    pub total_clicks: f64,
+    pub total_mouse_distance: f64,
+    pub total_scroll_distance: f64,
+    
+    // Intervals with any keyboard or mouse input
+    pub active_intervals: u32,
+    
+    pub intervals: Vec<InputInterval>,
+}
+
 // Response for recordings query
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecordingsResponse {
@@ -208,6 +695,22 @@ mod tests {
         assert_eq!(config.output_width, 1280);
         assert_eq!(config.crf, 30);
         assert_eq!(config.preset, "fast");
+        assert!(!config.timelapse.enabled);
+        assert_eq!(config.timelapse.speed_factor, 60);
+    }
+
+    #[test]
+    fn test_recording_config_without_timelapse_deserializes() {
+        let json = r#"{
+            "enabled": true,
+            "segment_duration_seconds": 300,
+            "framerate": 4,
+            "retention_days": 3,
+            "max_storage_bytes": 5000000000
+        }"#;
+        
+        let config: RecordingConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.timelapse, TimelapseConfig::default());
     }
 
     #[test]
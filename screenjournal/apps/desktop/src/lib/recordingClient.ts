@@ -205,9 +205,54 @@ export const DEFAULT_RECORDING_CONFIG: RecordingConfig = {
 export type GeminiConfig = {
   enabled: boolean;
   rate_limit_per_minute: number;
+  max_concurrent_jobs: number;
   max_retries: number;
   retry_delay_seconds: number;
   thinking_budget: number;
+  /** null = built-in; "name" = latest version; "name@N" = pinned version */
+  prompt_template?: string | null;
+  /** Organization productivity rubric; null = built-in 1-5 rubric */
+  prompt_rubric?: string | null;
+  /** Include ActivityWatch app/window title switches in the prompt (privacy rules apply). Default: true */
+  window_context?: boolean;
+  /** Snap timelines to ActivityWatch window switches and add confidence. Default: true */
+  window_fusion?: boolean;
+  /** Largest boundary move (seconds) when snapping to a window switch. Default: 10 */
+  fusion_snap_seconds?: number;
+  /** Segments with less ActivityWatch active time (seconds) are idle; 0 = off. Default: 15 */
+  idle_threshold_seconds?: number;
+  /** Idle segments: 'skip' emits an "Away" timeline, 'reduced' analyzes sped up. Default: 'skip' */
+  idle_action?: 'skip' | 'reduced';
+  /** Speed-up for idle segments analyzed with idle_action 'reduced'. Default: 8 */
+  idle_speedup_factor?: number;
+  /** Trade timeline granularity for token cost. Default: 'off' */
+  speedup_mode?: SpeedupMode;
+  /** Speed-up factor (e.g. 4 = 4x speed or 4x fewer frames). Default: 4 */
+  speedup_factor?: number;
+  /** Input (prompt and video) price, USD per million tokens. Default: 0.10 */
+  input_price_per_million_tokens?: number;
+  /** Output price, USD per million tokens. Default: 0.40 */
+  output_price_per_million_tokens?: number;
+  /** Estimated daily spend (USD) that pauses the queue; null = no cap */
+  daily_budget_usd?: number | null;
+  /** Estimated monthly spend (USD) that pauses the queue; null = no cap */
+  monthly_budget_usd?: number | null;
+};
+
+/**
+ * Speed-up preprocessing before analysis
+ * - reencode: video re-encoded at N× speed, timestamps rescaled to real time
+ * - reduced_frame_rate: N× fewer frames sampled from the original video
+ */
+export type SpeedupMode = 'off' | 'reencode' | 'reduced_frame_rate';
+
+/**
+ * Versioned analysis prompt template
+ */
+export type PromptTemplate = {
+  name: string;
+  version: number;
+  body: string;
 };
 
 /**
@@ -228,14 +273,40 @@ export type GeminiQueueStatus = {
   running: boolean;
   stats: GeminiQueueStats;
   config: GeminiConfig;
+  active_workers: number;
+  rate_limit_pause_seconds: number | null;
+  /** Token usage and estimated cost of the current local day */
+  usage_today: GeminiUsageTotals;
+  /** Token usage and estimated cost of the current local month */
+  usage_month: GeminiUsageTotals;
+  /** Why the queue is paused for the budget, null when not paused */
+  budget_pause: string | null;
+};
+
+/**
+ * Summed token usage of a day or month
+ */
+export type GeminiUsageTotals = {
+  jobs: number;
+  prompt_tokens: number;
+  video_tokens: number;
+  output_tokens: number;
+  estimated_cost_usd: number;
 };
 
+/**
+ * Event emitted when a budget cap pauses the Gemini queue
+ * Payload: { reason: string, usage_today, usage_month }
+ */
+export const GEMINI_BUDGET_REACHED_EVENT = 'gemini-budget-reached';
+
 /**
  * Default Gemini configuration
  */
 export const DEFAULT_GEMINI_CONFIG: GeminiConfig = {
   enabled: false,
   rate_limit_per_minute: 0, // No rate limiting
+  max_concurrent_jobs: 2,
   max_retries: 3,
   retry_delay_seconds: 5,
   thinking_budget: 1024,
@@ -283,6 +354,149 @@ export async function updateGeminiConfig(config: GeminiConfig): Promise<void> {
   return await invoke('update_gemini_config', { newConfig: config });
 }
 
+/**
+ * List prompt templates (built-in plus every saved version)
+ */
+export async function listPromptTemplates(): Promise<PromptTemplate[]> {
+  return await invoke('list_prompt_templates');
+}
+
+/**
+ * Validate and save a prompt template as the next version of `name`
+ */
+export async function savePromptTemplate(name: string, body: string): Promise<PromptTemplate> {
+  return await invoke('save_prompt_template', { name, body });
+}
+
+/**
+ * ActivityWatch agreement of a fused timeline entry
+ */
+export type EntryFusion = {
+  /** Share of the entry ActivityWatch reports its application focused (0-1) */
+  confidence: number;
+  /** ActivityWatch reports another application for most of the entry */
+  disagreement: boolean;
+  /** Application the model named, when replaced by the ActivityWatch name */
+  modelApplication?: string;
+  snapped: boolean;
+};
+
+/**
+ * One activity of an analyzed timeline (times relative to the segment start)
+ */
+export type TimelineEntry = {
+  startTime: string;
+  endTime: string;
+  description: string;
+  activeApplication: string;
+  activeWindowTitle: string;
+  /** 1 (distraction) - 5 (highly productive) */
+  productiveScore: number;
+  fusion?: EntryFusion;
+};
+
+/**
+ * Stored timeline analysis of one display of a segment
+ */
+export type TimelineAnalysis = {
+  segment_id: string;
+  display_index: number;
+  analyzed_at: string;
+  video_duration_seconds: number;
+  segment_start_time: string;
+  timeline: TimelineEntry[];
+  backend: 'gemini' | 'openai_compatible' | 'local';
+  model: string;
+  prompt_version: string;
+  /** Generated from ActivityWatch AFK data (away timeline) */
+  synthetic: boolean;
+  /** 1 for the first analysis, higher for reanalyses */
+  analysis_version: number;
+};
+
+/**
+ * Result of re-sending stored timelines to the collector
+ */
+export type ResendSummary = {
+  analyses: number;
+  entries: number;
+};
+
+/**
+ * Model to analyze with (unset fields use the backend's configured model/endpoint)
+ */
+export type ModelTarget = {
+  backend: 'gemini' | 'openai_compatible' | 'local';
+  model?: string;
+  base_url?: string;
+};
+
+/**
+ * Which analyzed displays to reanalyze (unset fields match everything)
+ */
+export type ReanalysisFilter = {
+  /** "name@N" for one template version, "name" for all its versions */
+  prompt_version?: string;
+  /** Model the current analysis was made with */
+  model?: string;
+};
+
+/**
+ * Result of queuing a reanalysis
+ */
+export type ReanalysisSummary = {
+  queued: number;
+  /** Matching displays not queued (video deleted, blank screen, already queued, failed job) */
+  skipped: number;
+  /** Pinned prompt of the queued jobs (null = configured prompt) */
+  prompt_version: string | null;
+};
+
+/**
+ * Get the stored timeline analyses of a segment: the current one per analyzed
+ * display, or every version with allVersions
+ */
+export async function getSegmentTimeline(
+  date: string,
+  segmentId: string,
+  allVersions = false
+): Promise<TimelineAnalysis[]> {
+  return await invoke('get_segment_timeline', { date, segmentId, allVersions });
+}
+
+/**
+ * Get the stored timeline analyses of segments starting in a time range, oldest first
+ */
+export async function getTimelinesByDateRange(startTime: string, endTime: string): Promise<TimelineAnalysis[]> {
+  return await invoke('get_timelines_by_date_range', { startTime, endTime });
+}
+
+/**
+ * Queue the stored timelines of a time range to the collector again
+ * Fails when the collector is disabled
+ */
+export async function resendTimelines(startTime: string, endTime: string): Promise<ResendSummary> {
+  return await invoke('resend_timelines', { startTime, endTime });
+}
+
+/**
+ * Queue reanalysis of the segments of a time range with a chosen prompt and model
+ * Results are saved and sent to the collector as new analysis versions
+ */
+export async function reanalyzeSegments(
+  startTime: string,
+  endTime: string,
+  options: { filter?: ReanalysisFilter; promptTemplate?: string; model?: ModelTarget } = {}
+): Promise<ReanalysisSummary> {
+  return await invoke('reanalyze_segments', {
+    startTime,
+    endTime,
+    filter: options.filter ?? null,
+    promptTemplate: options.promptTemplate ?? null,
+    model: options.model ?? null,
+  });
+}
+
 /**
  * Get Gemini queue status
  */